@@ -42,7 +42,9 @@ pub struct Deserializer<'de> {
     reader: PeekableReader<Box<dyn Read + 'de>>,
     buf: Vec<u8>,
     encapsulator_tag_stack: Vec<Tag>,
+    implicit_tag: Option<Tag>,
     header_only: bool,
+    raw_der: bool,
     max_len: usize,
 }
 
@@ -57,7 +59,9 @@ impl<'de> Deserializer<'de> {
             reader: PeekableReader::new(Box::new(reader)),
             buf: Vec::new(),
             encapsulator_tag_stack: Vec::with_capacity(3),
+            implicit_tag: None,
             header_only: false,
+            raw_der: false,
             max_len,
         }
     }
@@ -97,6 +101,40 @@ impl<'de> Deserializer<'de> {
         Ok(tag)
     }
 
+    /// Reads the next DER object as is (tag, length and value) into `self.buf`
+    fn h_next_raw_object(&mut self) -> Result<()> {
+        self.buf.clear();
+        self.buf.push(self.reader.read_one()?);
+
+        let first_len_byte = self.reader.read_one()?;
+        self.buf.push(first_len_byte);
+        let len = if first_len_byte & 0x80 == 0 {
+            usize::from(first_len_byte)
+        } else {
+            let len_bytes_count = usize::from(first_len_byte & 0x7F);
+            if len_bytes_count > std::mem::size_of::<usize>() {
+                return Err(Asn1DerError::UnsupportedValue);
+            }
+            let start = self.buf.len();
+            self.buf.resize(start + len_bytes_count, 0);
+            self.reader.read_exact(&mut self.buf[start..])?;
+            self.buf[start..]
+                .iter()
+                .fold(0, |len, byte| (len << 8) | usize::from(*byte))
+        };
+
+        if len > self.max_len {
+            debug_log!("TRUNCATED DATA (invalid len: found {}, max is {})", len, self.max_len);
+            return Err(Asn1DerError::TruncatedData);
+        }
+
+        let start = self.buf.len();
+        self.buf.resize(start + len, 0);
+        self.reader.read_exact(&mut self.buf[start..])?;
+
+        Ok(())
+    }
+
     /// Peek next DER object tag (ignoring encapsulator)
     fn h_peek_object(&mut self) -> Result<Tag> {
         if self.encapsulator_tag_stack.is_empty() {
@@ -198,6 +236,7 @@ impl<'de, 'a> serde::de::Deserializer<'de> for &'a mut Deserializer<'de> {
                 debug_log!("deserialize_any: can't be used on INTEGER");
                 Err(Asn1DerError::InvalidData)
             }
+            Tag::ENUMERATED => self.deserialize_byte_buf(visitor),
             Tag::NULL => self.deserialize_unit(visitor),
             Tag::OCTET_STRING => self.deserialize_byte_buf(visitor),
             Tag::SEQUENCE => self.deserialize_seq(visitor),
@@ -209,6 +248,8 @@ impl<'de, 'a> serde::de::Deserializer<'de> for &'a mut Deserializer<'de> {
             Tag::PRINTABLE_STRING => self.deserialize_byte_buf(visitor),
             Tag::NUMERIC_STRING => self.deserialize_byte_buf(visitor),
             Tag::IA5_STRING => self.deserialize_byte_buf(visitor),
+            Tag::VISIBLE_STRING => self.deserialize_byte_buf(visitor),
+            Tag::BMP_STRING => self.deserialize_byte_buf(visitor),
             Tag::APP_0 => self.deserialize_newtype_struct(ApplicationTag0::<()>::NAME, visitor),
             Tag::APP_1 => self.deserialize_newtype_struct(ApplicationTag1::<()>::NAME, visitor),
             Tag::APP_2 => self.deserialize_newtype_struct(ApplicationTag2::<()>::NAME, visitor),
@@ -420,6 +461,7 @@ impl<'de, 'a> serde::de::Deserializer<'de> for &'a mut Deserializer<'de> {
             Tag::OID => {}
             Tag::BIT_STRING => {}
             Tag::INTEGER => {}
+            Tag::ENUMERATED => {}
             Tag::UTC_TIME => {}
             Tag::GENERALIZED_TIME => {}
             tag if tag.is_context_specific() => {}
@@ -442,14 +484,24 @@ impl<'de, 'a> serde::de::Deserializer<'de> for &'a mut Deserializer<'de> {
 
     fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
         debug_log!("deserialize_byte_buf");
+        if self.raw_der {
+            self.raw_der = false;
+            self.h_decapsulate()?;
+            self.h_next_raw_object()?;
+            return visitor.visit_byte_buf(self.buf.to_vec());
+        }
+
         match self.h_peek_object()? {
             Tag::OCTET_STRING => {}
             Tag::BIT_STRING => {}
             Tag::INTEGER => {}
+            Tag::ENUMERATED => {}
             Tag::UTF8_STRING => {}
             Tag::PRINTABLE_STRING => {}
             Tag::NUMERIC_STRING => {}
             Tag::IA5_STRING => {}
+            Tag::VISIBLE_STRING => {}
+            Tag::BMP_STRING => {}
             tag if tag.is_context_specific() => {}
             _tag => {
                 debug_log!("deserialize_byte_buf: INVALID (found {})", _tag);
@@ -522,7 +574,24 @@ impl<'de, 'a> serde::de::Deserializer<'de> for &'a mut Deserializer<'de> {
             ContextTag13::<()>::NAME => self.h_encapsulate(Tag::CTX_13),
             ContextTag14::<()>::NAME => self.h_encapsulate(Tag::CTX_14),
             ContextTag15::<()>::NAME => self.h_encapsulate(Tag::CTX_15),
+            ImplicitContextTag0::<()>::NAME => self.implicit_tag = Some(Tag::APP_0),
+            ImplicitContextTag1::<()>::NAME => self.implicit_tag = Some(Tag::APP_1),
+            ImplicitContextTag2::<()>::NAME => self.implicit_tag = Some(Tag::APP_2),
+            ImplicitContextTag3::<()>::NAME => self.implicit_tag = Some(Tag::APP_3),
+            ImplicitContextTag4::<()>::NAME => self.implicit_tag = Some(Tag::APP_4),
+            ImplicitContextTag5::<()>::NAME => self.implicit_tag = Some(Tag::APP_5),
+            ImplicitContextTag6::<()>::NAME => self.implicit_tag = Some(Tag::APP_6),
+            ImplicitContextTag7::<()>::NAME => self.implicit_tag = Some(Tag::APP_7),
+            ImplicitContextTag8::<()>::NAME => self.implicit_tag = Some(Tag::APP_8),
+            ImplicitContextTag9::<()>::NAME => self.implicit_tag = Some(Tag::APP_9),
+            ImplicitContextTag10::<()>::NAME => self.implicit_tag = Some(Tag::APP_10),
+            ImplicitContextTag11::<()>::NAME => self.implicit_tag = Some(Tag::APP_11),
+            ImplicitContextTag12::<()>::NAME => self.implicit_tag = Some(Tag::APP_12),
+            ImplicitContextTag13::<()>::NAME => self.implicit_tag = Some(Tag::APP_13),
+            ImplicitContextTag14::<()>::NAME => self.implicit_tag = Some(Tag::APP_14),
+            ImplicitContextTag15::<()>::NAME => self.implicit_tag = Some(Tag::APP_15),
             HeaderOnly::<()>::NAME => self.header_only = true,
+            RawDerAsn1::NAME => self.raw_der = true,
             _ => {}
         }
 
@@ -537,10 +606,16 @@ impl<'de, 'a> serde::de::Deserializer<'de> for &'a mut Deserializer<'de> {
         // Read tag and length
         let (tag, len) = self.h_next_tag_len()?;
         debug_log!("tag: {}, len: {}", tag, len);
-        match tag {
-            Tag::SEQUENCE => {}
-            Asn1SetOf::<()>::TAG => {}
-            tag => {
+        match (tag, self.implicit_tag.take()) {
+            (tag, Some(implicit_tag)) => {
+                if tag != implicit_tag {
+                    debug_log!("deserialize_seq: INVALID (found {}, expected {})", tag, implicit_tag);
+                    return Err(Asn1DerError::InvalidData);
+                }
+            }
+            (Tag::SEQUENCE, None) => {}
+            (Asn1SetOf::<()>::TAG, None) => {}
+            (tag, None) => {
                 if !tag.is_context_specific() {
                     debug_log!("deserialize_seq: INVALID (found {})", tag);
                     return Err(Asn1DerError::InvalidData);
@@ -18,16 +18,21 @@
 //!
 //! More advanced types are supported through wrappers:
 //! - Integer (as big integer)
+//! - Enumerated
 //! - Bit String
 //! - Object Identifier
 //! - Utf8 String
 //! - Numeric String
 //! - Printable String
 //! - IA5 String
+//! - Visible String
+//! - BMP String
 //! - Generalized Time
 //! - UTC Time
 //! - Application Tags from 0 to 15
 //! - Context Tags from 0 to 15
+//! - Implicit Context Tags from 0 to 15 (constructed types)
+//! - Raw DER (pre-encoded elements)
 //!
 //! Everything sequence-like combined out of this types is also supported out of the box.
 //!
@@ -46,8 +46,10 @@ pub struct Serializer<'se> {
     writer: Box<dyn Write + 'se>,
     tag_for_next_bytes: Tag,
     tag_for_next_seq: Tag,
+    implicit_tag: Option<Tag>,
     encapsulators: Vec<Tag>,
     no_header: bool,
+    raw_der: bool,
 }
 
 impl<'se> Serializer<'se> {
@@ -67,8 +69,10 @@ impl<'se> Serializer<'se> {
             writer: Box::new(writer),
             tag_for_next_bytes: Tag::OCTET_STRING,
             tag_for_next_seq: Tag::SEQUENCE,
+            implicit_tag: None,
             encapsulators: Vec::with_capacity(3),
             no_header: false,
+            raw_der: false,
         }
     }
 
@@ -113,6 +117,13 @@ impl<'se> Serializer<'se> {
             return Ok(0);
         }
 
+        if self.raw_der {
+            self.raw_der = false; // reset state
+
+            // `len` is the length of the whole pre-encoded element
+            return self.h_write_encapsulator(len);
+        }
+
         let mut written;
         if let Some(last_encapsulator_tag) = self.encapsulators.last() {
             if last_encapsulator_tag.is_context_specific() {
@@ -270,12 +281,15 @@ impl<'a, 'se> serde::ser::Serializer for &'a mut Serializer<'se> {
             ObjectIdentifierAsn1::NAME => self.tag_for_next_bytes = Tag::OID,
             BitStringAsn1::NAME => self.tag_for_next_bytes = Tag::BIT_STRING,
             IntegerAsn1::NAME => self.tag_for_next_bytes = Tag::INTEGER,
+            EnumeratedAsn1::NAME => self.tag_for_next_bytes = Tag::ENUMERATED,
             UTCTimeAsn1::NAME => self.tag_for_next_bytes = Tag::UTC_TIME,
             GeneralizedTimeAsn1::NAME => self.tag_for_next_bytes = Tag::GENERALIZED_TIME,
             Utf8StringAsn1::NAME => self.tag_for_next_bytes = Tag::UTF8_STRING,
             PrintableStringAsn1::NAME => self.tag_for_next_bytes = Tag::PRINTABLE_STRING,
             NumericStringAsn1::NAME => self.tag_for_next_bytes = Tag::NUMERIC_STRING,
             IA5StringAsn1::NAME => self.tag_for_next_bytes = Tag::IA5_STRING,
+            VisibleStringAsn1::NAME => self.tag_for_next_bytes = Tag::VISIBLE_STRING,
+            BMPStringAsn1::NAME => self.tag_for_next_bytes = Tag::BMP_STRING,
             Asn1SetOf::<()>::NAME => self.tag_for_next_seq = Tag::SET,
             Asn1SequenceOf::<()>::NAME => self.tag_for_next_seq = Tag::SEQUENCE,
             BitStringAsn1Container::<()>::NAME => self.h_encapsulate(Tag::BIT_STRING),
@@ -312,7 +326,24 @@ impl<'a, 'se> serde::ser::Serializer for &'a mut Serializer<'se> {
             ContextTag13::<()>::NAME => self.h_encapsulate(Tag::CTX_13),
             ContextTag14::<()>::NAME => self.h_encapsulate(Tag::CTX_14),
             ContextTag15::<()>::NAME => self.h_encapsulate(Tag::CTX_15),
+            ImplicitContextTag0::<()>::NAME => self.implicit_tag = Some(Tag::APP_0),
+            ImplicitContextTag1::<()>::NAME => self.implicit_tag = Some(Tag::APP_1),
+            ImplicitContextTag2::<()>::NAME => self.implicit_tag = Some(Tag::APP_2),
+            ImplicitContextTag3::<()>::NAME => self.implicit_tag = Some(Tag::APP_3),
+            ImplicitContextTag4::<()>::NAME => self.implicit_tag = Some(Tag::APP_4),
+            ImplicitContextTag5::<()>::NAME => self.implicit_tag = Some(Tag::APP_5),
+            ImplicitContextTag6::<()>::NAME => self.implicit_tag = Some(Tag::APP_6),
+            ImplicitContextTag7::<()>::NAME => self.implicit_tag = Some(Tag::APP_7),
+            ImplicitContextTag8::<()>::NAME => self.implicit_tag = Some(Tag::APP_8),
+            ImplicitContextTag9::<()>::NAME => self.implicit_tag = Some(Tag::APP_9),
+            ImplicitContextTag10::<()>::NAME => self.implicit_tag = Some(Tag::APP_10),
+            ImplicitContextTag11::<()>::NAME => self.implicit_tag = Some(Tag::APP_11),
+            ImplicitContextTag12::<()>::NAME => self.implicit_tag = Some(Tag::APP_12),
+            ImplicitContextTag13::<()>::NAME => self.implicit_tag = Some(Tag::APP_13),
+            ImplicitContextTag14::<()>::NAME => self.implicit_tag = Some(Tag::APP_14),
+            ImplicitContextTag15::<()>::NAME => self.implicit_tag = Some(Tag::APP_15),
             HeaderOnly::<()>::NAME => self.no_header = true,
+            RawDerAsn1::NAME => self.raw_der = true,
             _ => {}
         }
 
@@ -334,6 +365,7 @@ impl<'a, 'se> serde::ser::Serializer for &'a mut Serializer<'se> {
         debug_log!("serialize_seq");
         let mut tag = Tag::SEQUENCE;
         std::mem::swap(&mut tag, &mut self.tag_for_next_seq);
+        let tag = self.implicit_tag.take().unwrap_or(tag);
         Ok(Sequence::serialize_lazy(self, tag))
     }
 
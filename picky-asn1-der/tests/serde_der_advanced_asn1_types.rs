@@ -7,7 +7,7 @@ use oid::prelude::*;
 use picky_asn1::{
     bit_string::BitString,
     date::{Date, GeneralizedTime, UTCTime},
-    restricted_string::{IA5String, PrintableString, Utf8String},
+    restricted_string::{BMPString, IA5String, PrintableString, Utf8String, VisibleString},
     wrapper::*,
 };
 use pretty_assertions::assert_eq;
@@ -223,6 +223,51 @@ fn application_tag0() {
     check(&buffer, application_tag);
 }
 
+#[test]
+fn enumerated() {
+    let buffer = [0x0A, 0x01, 0x03];
+    check(&buffer, EnumeratedAsn1::from(vec![3]));
+}
+
+#[test]
+fn implicit_context_tag() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Revoked {
+        reason: u8,
+    }
+
+    let buffer = [0xA1, 0x03, 0x02, 0x01, 0x05];
+    check(&buffer, ImplicitContextTag1(Revoked { reason: 5 }));
+
+    let buffer = [0xA0, 0x06, 0x02, 0x01, 0x05, 0x02, 0x01, 0x06];
+    check(&buffer, ImplicitContextTag0(Asn1SequenceOf(vec![5u8, 6])));
+
+    // wrong tag
+    assert!(picky_asn1_der::from_bytes::<ImplicitContextTag1<Revoked>>(&[0x30, 0x03, 0x02, 0x01, 0x05]).is_err());
+}
+
+#[test]
+fn raw_der() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Signed {
+        tbs: RawDerAsn1,
+        signature: u8,
+    }
+
+    // non-minimal length encoding is kept as is
+    let buffer = [0x30, 0x09, 0x30, 0x81, 0x03, 0x02, 0x01, 0x05, 0x02, 0x01, 0x07];
+    check(
+        &buffer,
+        Signed {
+            tbs: RawDerAsn1(buffer[2..8].to_vec()),
+            signature: 7,
+        },
+    );
+
+    let buffer = [0xA0, 0x03, 0x02, 0x01, 0x05];
+    check(&buffer, ApplicationTag0(RawDerAsn1(buffer[2..].to_vec())));
+}
+
 #[test]
 fn restricted_strings() {
     let printable_string_buffer = b"\x13\x02\x4E\x4C";
@@ -236,6 +281,14 @@ fn restricted_strings() {
     let ia5_string_buffer = b"\x16\x10\x50\x6F\x6C\x61\x72\x53\x53\x4C\x20\x54\x65\x73\x74\x20\x43\x41";
     let ia5_string = IA5String::from_str("PolarSSL Test CA").unwrap();
     check::<IA5StringAsn1>(ia5_string_buffer, ia5_string.into());
+
+    let visible_string_buffer = b"\x1A\x07\x45\x78\x61\x6D\x70\x6C\x65";
+    let visible_string = VisibleString::from_str("Example").unwrap();
+    check::<VisibleStringAsn1>(visible_string_buffer, visible_string.into());
+
+    let bmp_string_buffer = b"\x1E\x04\x00\x4E\x00\xE9";
+    let bmp_string = BMPString::new(&bmp_string_buffer[2..]).unwrap();
+    check::<BMPStringAsn1>(bmp_string_buffer, bmp_string.into());
 }
 
 #[test]
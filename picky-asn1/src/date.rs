@@ -292,7 +292,7 @@ impl TimeRepr for GeneralizedTimeRepr {
 mod chrono_conversion {
     use super::*;
     use chrono::{naive::NaiveDateTime, DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
-    use serde::export::TryFrom;
+    use std::convert::TryFrom;
 
     impl<TR: TimeRepr> TryFrom<Duration> for Date<TR> {
         type Error = ();
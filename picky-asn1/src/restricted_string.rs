@@ -243,6 +243,47 @@ impl CharSet for IA5CharSet {
     }
 }
 
+// === VisibleString === //
+
+/// Printable ASCII characters and SPACE (values from `0x20` to `0x7E`)
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VisibleCharSet;
+pub type VisibleString = RestrictedString<VisibleCharSet>;
+
+impl CharSet for VisibleCharSet {
+    fn check(data: &[u8]) -> bool {
+        for &c in data {
+            if c != b' ' && !c.is_ascii_graphic() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// === BMPString === //
+
+/// Basic Multilingual Plane characters.
+/// The underlying bytes are the big-endian UCS-2 encoding of the string (surrogates are not allowed).
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BMPCharSet;
+pub type BMPString = RestrictedString<BMPCharSet>;
+
+impl CharSet for BMPCharSet {
+    fn check(data: &[u8]) -> bool {
+        let pairs = data.chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return false;
+        }
+        for pair in pairs {
+            if (0xD8..=0xDF).contains(&pair[0]) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,6 +318,28 @@ mod tests {
         assert!(IA5String::from_str("BUéisuteurnt").is_err());
     }
 
+    #[test]
+    fn valid_visible_string() {
+        VisibleString::from_str("BUeisuteurnt ~").expect("invalid string");
+    }
+
+    #[test]
+    fn invalid_visible_string() {
+        assert!(VisibleString::from_str("BUeisuteurnt\n").is_err());
+        assert!(VisibleString::from_str("BUéisuteurnt").is_err());
+    }
+
+    #[test]
+    fn valid_bmp_string() {
+        BMPString::new(vec![0x00, 0x42, 0x00, 0xE9]).expect("invalid string");
+    }
+
+    #[test]
+    fn invalid_bmp_string() {
+        assert!(BMPString::new(vec![0x00, 0x42, 0x00]).is_err());
+        assert!(BMPString::new(vec![0xD8, 0x3D, 0xDE, 0x00]).is_err());
+    }
+
     #[test]
     fn valid_utf8_string() {
         Utf8String::from_str("1224na÷日本語はむずかちー−×—«BUeisuteurnt").expect("invalid string");
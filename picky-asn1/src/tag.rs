@@ -13,6 +13,7 @@ impl Tag {
     pub const NULL: Self = Tag(0x05);
     pub const OID: Self = Tag(0x06);
     pub const REAL: Self = Tag(0x09);
+    pub const ENUMERATED: Self = Tag(0x0A);
     pub const UTF8_STRING: Self = Tag(0x0C);
     pub const RELATIVE_OID: Self = Tag(0xD);
     pub const NUMERIC_STRING: Self = Tag(0x12);
@@ -22,6 +23,8 @@ impl Tag {
     pub const IA5_STRING: Self = Tag(0x16);
     pub const UTC_TIME: Self = Tag(0x17);
     pub const GENERALIZED_TIME: Self = Tag(0x18);
+    pub const VISIBLE_STRING: Self = Tag(0x1A);
+    pub const BMP_STRING: Self = Tag(0x1E);
     pub const SEQUENCE: Self = Tag(0x30);
     pub const SET: Self = Tag(0x31);
     pub const APP_0: Self = Tag::application(0);
@@ -100,6 +103,7 @@ impl fmt::Display for Tag {
             Tag::NULL => write!(f, "NULL"),
             Tag::OID => write!(f, "OBJECT IDENTIFIER"),
             Tag::REAL => write!(f, "REAL"),
+            Tag::ENUMERATED => write!(f, "ENUMERATED"),
             Tag::UTF8_STRING => write!(f, "UTF8String"),
             Tag::RELATIVE_OID => write!(f, "RELATIVE-OID"),
             Tag::NUMERIC_STRING => write!(f, "NumericString"),
@@ -109,6 +113,8 @@ impl fmt::Display for Tag {
             Tag::IA5_STRING => write!(f, "IA5String"),
             Tag::UTC_TIME => write!(f, "UTCTime"),
             Tag::GENERALIZED_TIME => write!(f, "GeneralizedTime"),
+            Tag::VISIBLE_STRING => write!(f, "VisibleString"),
+            Tag::BMP_STRING => write!(f, "BMPString"),
             Tag::SEQUENCE => write!(f, "SEQUENCE"),
             Tag::SET => write!(f, "SET"),
             Tag::APP_0 => write!(f, "ApplicationTag0"),
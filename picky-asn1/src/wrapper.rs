@@ -1,7 +1,7 @@
 use crate::{
     bit_string::BitString,
     date::{GeneralizedTime, UTCTime},
-    restricted_string::{IA5String, NumericString, PrintableString, Utf8String},
+    restricted_string::{BMPString, IA5String, NumericString, PrintableString, Utf8String, VisibleString},
     tag::Tag,
     Asn1Type,
 };
@@ -195,6 +195,8 @@ asn1_wrapper! { auto struct Utf8StringAsn1(Utf8String),             Tag::UTF8_ST
 asn1_wrapper! { auto struct NumericStringAsn1(NumericString),       Tag::NUMERIC_STRING }
 asn1_wrapper! { auto struct PrintableStringAsn1(PrintableString),   Tag::PRINTABLE_STRING }
 asn1_wrapper! { auto struct IA5StringAsn1(IA5String),               Tag::IA5_STRING }
+asn1_wrapper! { auto struct VisibleStringAsn1(VisibleString),       Tag::VISIBLE_STRING }
+asn1_wrapper! { auto struct BMPStringAsn1(BMPString),               Tag::BMP_STRING }
 asn1_wrapper! { auto struct UTCTimeAsn1(UTCTime),                   Tag::UTC_TIME }
 asn1_wrapper! { auto struct GeneralizedTimeAsn1(GeneralizedTime),   Tag::GENERALIZED_TIME }
 
@@ -236,6 +238,28 @@ define_special_tag! {
     ContextTag15     => Tag::CTX_15,
 }
 
+// Implicitly tagged constructed types: the context-specific tag replaces the SEQUENCE (or SET) tag
+// of the wrapped value instead of encapsulating it, e.g. `[1] IMPLICIT SEQUENCE { ... }` is encoded
+// as `0xA1 <length> <sequence content>`.
+define_special_tag! {
+    ImplicitContextTag0  => Tag::APP_0,
+    ImplicitContextTag1  => Tag::APP_1,
+    ImplicitContextTag2  => Tag::APP_2,
+    ImplicitContextTag3  => Tag::APP_3,
+    ImplicitContextTag4  => Tag::APP_4,
+    ImplicitContextTag5  => Tag::APP_5,
+    ImplicitContextTag6  => Tag::APP_6,
+    ImplicitContextTag7  => Tag::APP_7,
+    ImplicitContextTag8  => Tag::APP_8,
+    ImplicitContextTag9  => Tag::APP_9,
+    ImplicitContextTag10 => Tag::APP_10,
+    ImplicitContextTag11 => Tag::APP_11,
+    ImplicitContextTag12 => Tag::APP_12,
+    ImplicitContextTag13 => Tag::APP_13,
+    ImplicitContextTag14 => Tag::APP_14,
+    ImplicitContextTag15 => Tag::APP_15,
+}
+
 fn serialize_vec<S, T>(elems: &[T], serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
 where
     S: ser::Serializer,
@@ -289,11 +313,23 @@ pub struct OctetStringAsn1(#[serde(with = "serde_bytes")] pub Vec<u8>);
 type VecU8 = Vec<u8>;
 impls! { OctetStringAsn1(VecU8), Tag::OCTET_STRING }
 
-
+/// A Vec<u8> wrapper holding an already DER-encoded element (tag, length and value).
+///
+/// The bytes are written and read as is, which is useful to keep the exact encoding of a signed
+/// structure.
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Hash, Clone)]
 pub struct RawDerAsn1(#[serde(with = "serde_bytes")] pub Vec<u8>);
 
 impls! { RawDerAsn1(VecU8), Tag::NO_TAG }
 
+/// A Vec<u8> wrapper for Asn1 encoding as Enumerated.
+///
+/// Like `IntegerAsn1`, the value is stored as big-endian two's complement bytes.
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Hash, Clone)]
+pub struct EnumeratedAsn1(#[serde(with = "serde_bytes")] pub Vec<u8>);
+
+impls! { EnumeratedAsn1(VecU8), Tag::ENUMERATED }
+
 /// A BigInt wrapper for Asn1 encoding.
 ///
 /// Simply use primitive integer types if you don't need big integer.
@@ -15,6 +15,7 @@ const PICKY_INTERMEDIATE_CERT_ENV: &str = "PICKY_INTERMEDIATE_CERT";
 const PICKY_INTERMEDIATE_KEY_ENV: &str = "PICKY_INTERMEDIATE_KEY";
 const PICKY_SAVE_CERTIFICATE_ENV: &str = "PICKY_SAVE_CERTIFICATE";
 const PICKY_BACKEND_FILE_PATH_ENV: &str = "PICKY_BACKEND_FILE_PATH";
+const PICKY_SIGNATURE_ALGORITHM_ENV: &str = "PICKY_SIGNATURE_ALGORITHM";
 
 #[derive(PartialEq, Clone)]
 pub enum BackendType {
@@ -143,6 +144,13 @@ impl ServerConfig {
             self.save_file_path = val;
         }
 
+        if let Ok(val) = env::var(PICKY_SIGNATURE_ALGORITHM_ENV) {
+            // JOSE algorithm names (RS256, ES256, ...)
+            if let Ok(key_config) = serde_json::from_value(serde_json::Value::String(val)) {
+                self.key_config = key_config;
+            }
+        }
+
         if let Ok(val) = env::var(PICKY_SAVE_CERTIFICATE_ENV) {
             if let Ok(save_certificate) = val.parse::<bool>() {
                 self.save_certificate = save_certificate;
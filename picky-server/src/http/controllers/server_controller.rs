@@ -7,13 +7,15 @@ use crate::{
 };
 use base64::URL_SAFE_NO_PAD;
 use picky::{
-    pem::{parse_pem, to_pem, Pem},
+    key::{PrivateKey, PublicKey},
+    loader::{Encoding, LoadedObject},
+    pem::{to_pem, Pem},
+    ssh::{SshCertType, SshCertificate},
     x509::{Cert, Csr},
 };
 use saphir::{Controller, ControllerDispatch, Method, StatusCode, SyncRequest, SyncResponse};
-use serde::export::{fmt::Error, Formatter};
 use serde_json::{self, Value};
-use std::fmt::Display;
+use std::fmt::{Display, Error, Formatter};
 
 struct ControllerData {
     pub storage: BoxedPickyStorage,
@@ -61,6 +63,8 @@ impl ServerController {
         dispatch.add(Method::GET, "/cert/<format>/<multihash>", get_cert); // FIXME: deprecated
         dispatch.add(Method::GET, "/cert/<multihash>", get_cert);
         dispatch.add(Method::POST, "/cert", post_cert);
+        dispatch.add(Method::POST, "/ssh/sign", ssh_signature_request);
+        dispatch.add(Method::GET, "/ssh/ca", get_ssh_ca);
 
         ServerController { dispatch }
     }
@@ -93,6 +97,17 @@ macro_rules! saphir_try {
     };
 }
 
+macro_rules! check_format {
+    ( $res:expr , $check:expr , $context:literal $(,)? ) => {
+        if let Err(e) = $check {
+            let message = format!(concat!($context, ": {}"), e);
+            error!("{}", message);
+            $res.body(message);
+            return;
+        }
+    };
+}
+
 macro_rules! unwrap_opt {
     ( $opt:expr , $error:literal $(,)? ) => {
         match $opt {
@@ -181,6 +196,23 @@ impl Format {
             (unsupported, _) => Err(format!("unsupported format: {}", unsupported)),
         }
     }
+
+    /// Checks the declared format against the encoding of `body`.
+    ///
+    /// `der_format` and `base64_format` are the formats expected for DER and base64 encoded DER bodies.
+    fn check_body(self, body: &[u8], der_format: Self, base64_format: Self) -> Result<(), String> {
+        let detected = match Encoding::detect(body) {
+            Encoding::Pem => Self::PemFile,
+            Encoding::Der => der_format,
+            Encoding::Base64Der => base64_format,
+        };
+
+        if self == detected {
+            Ok(())
+        } else {
+            Err(format!("declared format is {} but body is {}", self, detected))
+        }
+    }
 }
 
 // === health === //
@@ -199,36 +231,40 @@ fn post_cert(controller_data: &ControllerData, req: &SyncRequest, res: &mut Sync
     res.status(StatusCode::BAD_REQUEST);
 
     let request_format = saphir_try!(Format::request_format(req));
-    let cert = match request_format {
-        Format::PemFile => {
-            let pem = saphir_try!(parse_pem(req.body()), "couldn't parse pem");
-            saphir_try!(Cert::from_der(pem.data()), "(pem) couldn't deserialize certificate")
-        }
+    let loaded = match request_format {
         Format::Json => {
             let json = saphir_try!(
                 serde_json::from_slice::<Value>(req.body()),
                 "(json) couldn't parse json"
             );
-            let pem = saphir_try!(
-                json["certificate"]
-                    .to_string()
-                    .trim_matches('"')
-                    .replace("\\n", "\n")
-                    .parse::<Pem>(),
-                "(json) couldn't parse pem",
+            let pem = json["certificate"].to_string().trim_matches('"').replace("\\n", "\n");
+            check_format!(
+                res,
+                Format::PemFile.check_body(pem.as_bytes(), Format::PkixCertBinary, Format::PkixCertBase64),
+                "(json) invalid certificate format",
             );
-            saphir_try!(Cert::from_der(pem.data()), "(json) couldn't deserialize certificate")
+            saphir_try!(LoadedObject::from_bytes(&pem), "(json) couldn't load certificate")
         }
-        Format::PkixCertBinary => saphir_try!(Cert::from_der(req.body()), "(binary) couldn't deserialize certificate"),
-        Format::PkixCertBase64 => {
-            let der = saphir_try!(base64::decode(&req.body()), "couldn't decode base64 body");
-            saphir_try!(Cert::from_der(&der), "(base64) couldn't deserialize certificate")
+        Format::PemFile | Format::PkixCertBinary | Format::PkixCertBase64 => {
+            check_format!(
+                res,
+                request_format.check_body(req.body(), Format::PkixCertBinary, Format::PkixCertBase64),
+                "invalid certificate format",
+            );
+            saphir_try!(LoadedObject::from_bytes(req.body()), "couldn't load certificate")
         }
         unexpected => {
             error!("unexpected request format: {}", unexpected);
             return;
         }
     };
+    let cert = match loaded {
+        LoadedObject::Cert(cert) => cert,
+        other => {
+            error!("expected a certificate, got a {}", other.kind());
+            return;
+        }
+    };
 
     let ski = hex::encode(saphir_try!(cert.subject_key_identifier(), "couldn't fetch SKI"));
 
@@ -269,11 +305,7 @@ fn cert_signature_request(controller_data: &ControllerData, req: &SyncRequest, r
 
     let request_format = saphir_try!(Format::request_format(req));
     let mut ca_name = format!("{} Authority", &controller_data.config.realm);
-    let csr = match request_format {
-        Format::PemFile => {
-            let pem = saphir_try!(parse_pem(req.body()), "couldn't parse pem");
-            saphir_try!(Csr::from_der(pem.data()), "(pem) couldn't deserialize csr")
-        }
+    let loaded = match request_format {
         Format::Json => {
             let json = saphir_try!(
                 serde_json::from_slice::<Value>(req.body()),
@@ -284,26 +316,34 @@ fn cert_signature_request(controller_data: &ControllerData, req: &SyncRequest, r
                 ca_name = ca.trim_matches('"').to_owned();
             }
 
-            let pem = saphir_try!(
-                json["csr"]
-                    .to_string()
-                    .trim_matches('"')
-                    .replace("\\n", "\n")
-                    .parse::<Pem>(),
-                "(json) couldn't parse pem",
+            let pem = json["csr"].to_string().trim_matches('"').replace("\\n", "\n");
+            check_format!(
+                res,
+                Format::PemFile.check_body(pem.as_bytes(), Format::Pkcs10Binary, Format::Pkcs10Base64),
+                "(json) invalid csr format",
             );
-            saphir_try!(Csr::from_der(pem.data()), "(json) couldn't deserialize csr")
+            saphir_try!(LoadedObject::from_bytes(&pem), "(json) couldn't load csr")
         }
-        Format::Pkcs10Binary => saphir_try!(Csr::from_der(req.body()), "(binary) couldn't deserialize csr"),
-        Format::Pkcs10Base64 => {
-            let der = saphir_try!(base64::decode(&req.body()), "couldn't decode base64 body");
-            saphir_try!(Csr::from_der(&der), "(base64) couldn't deserialize csr")
+        Format::PemFile | Format::Pkcs10Binary | Format::Pkcs10Base64 => {
+            check_format!(
+                res,
+                request_format.check_body(req.body(), Format::Pkcs10Binary, Format::Pkcs10Base64),
+                "invalid csr format",
+            );
+            saphir_try!(LoadedObject::from_bytes(req.body()), "couldn't load csr")
         }
         unexpected => {
             error!("unexpected request format: {}", unexpected);
             return;
         }
     };
+    let csr = match loaded {
+        LoadedObject::Csr(csr) => csr,
+        other => {
+            error!("expected a certification request, got a {}", other.kind());
+            return;
+        }
+    };
 
     // Sign CSR
     let signed_cert = saphir_try!(sign_certificate(
@@ -350,10 +390,7 @@ fn sign_certificate(
         .map_err(|e| format!("couldn't get CA cert der: {}", e))?;
     let ca_cert = Cert::from_der(&ca_cert_der).map_err(|e| format!("couldn't deserialize CA cert: {}", e))?;
 
-    let ca_pk_der = storage
-        .get_key_by_hash(&ca_hash)
-        .map_err(|e| format!("couldn't fetch CA private key: {}", e))?;
-    let ca_pk = Picky::parse_pk_from_magic_der(&ca_pk_der).map_err(|e| e.to_string())?;
+    let ca_pk = get_ca_private_key(storage, &ca_hash)?;
 
     let dns_name = csr
         .subject_name()
@@ -387,6 +424,113 @@ fn sign_certificate(
     Ok(signed_cert)
 }
 
+fn get_ca_private_key(storage: &dyn PickyStorage, ca_hash: &str) -> Result<PrivateKey, String> {
+    let ca_pk_der = storage
+        .get_key_by_hash(ca_hash)
+        .map_err(|e| format!("couldn't fetch CA private key: {}", e))?;
+    Picky::load_private_key(&ca_pk_der).map_err(|e| e.to_string())
+}
+
+// === ssh === //
+
+fn ssh_signature_request(controller_data: &ControllerData, req: &SyncRequest, res: &mut SyncResponse) {
+    res.status(StatusCode::BAD_REQUEST);
+
+    let json = saphir_try!(
+        serde_json::from_slice::<Value>(req.body()),
+        "(json) couldn't parse json"
+    );
+
+    // SSH certificates are always signed by the intermediate CA
+    let ca_name = format!("{} Authority", &controller_data.config.realm);
+
+    let public_key = unwrap_opt!(json["public_key"].as_str(), "(json) public key is missing");
+    let public_key = saphir_try!(
+        PublicKey::from_openssh(public_key),
+        "(json) couldn't parse OpenSSH public key"
+    );
+
+    let cert_type = match json["cert_type"].as_str() {
+        None | Some("user") => SshCertType::User,
+        Some("host") => SshCertType::Host,
+        Some(unexpected) => {
+            error!("unexpected SSH certificate type: {}", unexpected);
+            return;
+        }
+    };
+
+    let key_id = json["key_id"].as_str().unwrap_or_default();
+
+    // a certificate without principals is valid for any user or host
+    let principals = unwrap_opt!(json["principals"].as_array(), "(json) principals are missing");
+    let principals = unwrap_opt!(
+        principals
+            .iter()
+            .map(|principal| principal.as_str().filter(|p| !p.is_empty()).map(|p| p.to_owned()))
+            .collect::<Option<Vec<String>>>(),
+        "(json) principals must be non-empty strings"
+    );
+    if principals.is_empty() {
+        error!("(json) at least one principal is required");
+        return;
+    }
+
+    let signed_cert = saphir_try!(sign_ssh_certificate(
+        &ca_name,
+        public_key,
+        cert_type,
+        key_id,
+        principals,
+        controller_data.storage.as_ref()
+    ));
+
+    res.body(signed_cert.to_openssh(key_id));
+    res.status(StatusCode::OK);
+}
+
+fn sign_ssh_certificate(
+    ca_name: &str,
+    public_key: PublicKey,
+    cert_type: SshCertType,
+    key_id: &str,
+    principals: Vec<String>,
+    storage: &dyn PickyStorage,
+) -> Result<SshCertificate, String> {
+    let ca_hash = storage
+        .get_hash_by_name(ca_name)
+        .map_err(|e| format!("couldn't fetch CA: {}", e))?;
+    let ca_pk = get_ca_private_key(storage, &ca_hash)?;
+
+    Picky::generate_ssh_certificate(public_key, cert_type, key_id, principals, &ca_pk)
+        .map_err(|e| format!("couldn't generate SSH certificate: {}", e))
+}
+
+fn get_ssh_ca(controller_data: &ControllerData, _: &SyncRequest, res: &mut SyncResponse) {
+    res.status(StatusCode::BAD_REQUEST);
+    let ca = format!("{} Authority", &controller_data.config.realm);
+    let ca_public_key = saphir_try!(find_ssh_ca_public_key(controller_data.storage.as_ref(), &ca));
+    res.body(ca_public_key);
+    res.status(StatusCode::OK);
+}
+
+/// Returns the CA public key as an OpenSSH public key line, suitable for `TrustedUserCAKeys`
+/// and `@cert-authority` entries of `known_hosts` files.
+fn find_ssh_ca_public_key(storage: &dyn PickyStorage, ca_name: &str) -> Result<String, String> {
+    let ca_hash = storage
+        .get_hash_by_name(ca_name)
+        .map_err(|e| format!("couldn't fetch CA hash id for {}: {}", ca_name, e))?;
+
+    let ca_cert_der = storage
+        .get_cert_by_hash(&ca_hash)
+        .map_err(|e| format!("couldn't fetch CA certificate der: {}", e))?;
+    let ca_cert = Cert::from_der(&ca_cert_der).map_err(|e| format!("couldn't deserialize CA cert: {}", e))?;
+
+    ca_cert
+        .public_key()
+        .to_openssh(ca_name)
+        .map_err(|e| format!("couldn't format CA public key: {}", e))
+}
+
 // === get_cert === //
 
 fn get_cert(controller_data: &ControllerData, req: &SyncRequest, res: &mut SyncResponse) {
@@ -542,7 +686,8 @@ fn generate_root_ca(config: &ServerConfig, storage: &dyn PickyStorage) -> Result
         }
     }
 
-    let pk = Picky::generate_private_key(4096).map_err(|e| format!("couldn't generate private key: {}", e))?;
+    let pk = Picky::generate_private_key_for(config.key_config, 4096)
+        .map_err(|e| format!("couldn't generate private key: {}", e))?;
     let root = Picky::generate_root(&name, &pk, config.key_config)
         .map_err(|e| format!("couldn't generate root certificate: {}", e))?;
     let ski = root
@@ -596,9 +741,9 @@ fn generate_intermediate_ca(config: &ServerConfig, storage: &dyn PickyStorage) -
         }
     };
 
-    let pk = Picky::generate_private_key(2048).map_err(|e| e.to_string())?;
+    let pk = Picky::generate_private_key_for(config.key_config, 2048).map_err(|e| e.to_string())?;
     let root_cert = Cert::from_der(&root_cert_der).map_err(|e| format!("couldn't parse root cert from der: {}", e))?;
-    let root_key = Picky::parse_pk_from_magic_der(&root_key_der).map_err(|e| e.to_string())?;
+    let root_key = Picky::load_private_key(&root_key_der).map_err(|e| e.to_string())?;
 
     let intermediate_cert = Picky::generate_intermediate(
         &intermediate_name,
@@ -739,6 +884,80 @@ mod tests {
             .expect("couldn't validate ca chain");
     }
 
+    #[test]
+    fn generate_ecdsa_chain_and_verify() {
+        let mut config = config();
+        config.key_config = SignatureHashType::EcdsaSha384;
+        let storage = get_storage(&config);
+
+        let ca_name = format!("{} Authority", config.realm);
+
+        generate_root_ca(&config, storage.as_ref()).expect("couldn't generate root ca");
+        generate_intermediate_ca(&config, storage.as_ref()).expect("couldn't generate intermediate ca");
+
+        let pk = Picky::generate_private_key_for(SignatureHashType::EcdsaSha256, 2048)
+            .expect("couldn't generate private key");
+        let csr = Csr::generate(
+            DirectoryName::new_common_name("Mister Elliptic"),
+            &pk,
+            SignatureHashType::EcdsaSha256,
+        )
+        .expect("couldn't generate csr");
+
+        let signed_cert =
+            sign_certificate(&ca_name, csr, &config, storage.as_ref()).expect("couldn't sign certificate");
+
+        let issuer_name = signed_cert.issuer_name().find_common_name().unwrap().to_string();
+        let chain = find_ca_chain(storage.as_ref(), &issuer_name)
+            .expect("couldn't fetch CA chain")
+            .iter()
+            .map(|cert_pem| {
+                let pem = cert_pem.parse::<Pem>().expect("couldn't parse cert pem");
+                Cert::from_der(pem.data()).expect("couldn't parse cert from der")
+            })
+            .collect::<Vec<Cert>>();
+
+        signed_cert
+            .verify_chain(chain.iter(), &UTCDate::now())
+            .expect("couldn't validate ca chain");
+    }
+
+    #[test]
+    fn generate_ssh_certificate_and_verify() {
+        let config = config();
+        let storage = get_storage(&config);
+
+        let ca_name = format!("{} Authority", config.realm);
+
+        generate_root_ca(&config, storage.as_ref()).expect("couldn't generate root ca");
+        generate_intermediate_ca(&config, storage.as_ref()).expect("couldn't generate intermediate ca");
+
+        let pk =
+            Picky::generate_private_key_for(SignatureHashType::Ed25519, 2048).expect("couldn't generate private key");
+
+        let signed_cert = sign_ssh_certificate(
+            &ca_name,
+            pk.to_public_key(),
+            SshCertType::User,
+            "mister.bushido",
+            vec!["bushido".to_owned()],
+            storage.as_ref(),
+        )
+        .expect("couldn't sign SSH certificate");
+
+        let ca_public_key = find_ssh_ca_public_key(storage.as_ref(), &ca_name).expect("couldn't fetch SSH CA key");
+        let ca_public_key = PublicKey::from_openssh(&ca_public_key).expect("couldn't parse SSH CA key");
+
+        let cert = SshCertificate::from_openssh(&signed_cert.to_openssh("mister.bushido"))
+            .expect("couldn't parse SSH certificate");
+        assert_eq!(cert.valid_principals(), &["bushido".to_owned()]);
+        assert!(cert.extensions().iter().any(|(name, _)| name == "permit-pty"));
+
+        let now = chrono::offset::Utc::now().timestamp() as u64;
+        cert.verify_for_principal(&ca_public_key, "bushido", now)
+            .expect("couldn't verify SSH certificate");
+    }
+
     fn new_saphir_request(headers: Vec<(&str, &str)>) -> SyncRequest {
         use saphir::Request;
 
@@ -802,6 +1021,46 @@ mod tests {
         assert_eq!(err, "unsupported encoding format for pkcs10: unknown");
     }
 
+    #[test]
+    fn request_body_format() {
+        let pk =
+            Picky::generate_private_key_for(SignatureHashType::Ed25519, 2048).expect("couldn't generate private key");
+        let csr = Csr::generate(
+            DirectoryName::new_common_name("Mister Bushido"),
+            &pk,
+            SignatureHashType::Ed25519,
+        )
+        .expect("couldn't generate csr");
+        let csr_pem = csr.to_pem().expect("csr pem").to_string();
+        let csr_der = csr.to_der().expect("csr der");
+        let csr_base64 = base64::encode(&csr_der);
+
+        for (format, body) in vec![
+            (Format::PemFile, csr_pem.as_bytes()),
+            (Format::Pkcs10Binary, csr_der.as_slice()),
+            (Format::Pkcs10Base64, csr_base64.as_bytes()),
+        ] {
+            format
+                .check_body(body, Format::Pkcs10Binary, Format::Pkcs10Base64)
+                .expect("matching format");
+        }
+
+        let err = Format::Pkcs10Base64
+            .check_body(&csr_der, Format::Pkcs10Binary, Format::Pkcs10Base64)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            "declared format is base64-encoded pkcs10 but body is binary-encoded pkcs10"
+        );
+
+        let err = Format::PkixCertBinary
+            .check_body(csr_pem.as_bytes(), Format::PkixCertBinary, Format::PkixCertBase64)
+            .err()
+            .unwrap();
+        assert_eq!(err, "declared format is binary-encoded pkix-cert but body is pem file");
+    }
+
     #[test]
     fn response_format() {
         let format = Format::response_format(&new_saphir_request(vec![("Accept", "application/x-pem-file")])).unwrap();
@@ -2,9 +2,9 @@ use crate::oids;
 use oid::ObjectIdentifier;
 use picky_asn1::{
     tag::{Tag, TagPeeker},
-    wrapper::ObjectIdentifierAsn1,
+    wrapper::{ApplicationTag0, ApplicationTag1, ApplicationTag2, ApplicationTag3, ObjectIdentifierAsn1},
 };
-use serde::{de, ser};
+use serde::{de, ser, Deserialize, Serialize};
 use std::fmt;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -68,6 +68,13 @@ impl AlgorithmIdentifier {
         }
     }
 
+    pub fn new_ecdsa_with_sha512() -> Self {
+        Self {
+            algorithm: oids::ecdsa_with_sha512().into(),
+            parameters: AlgorithmIdentifierParameters::None,
+        }
+    }
+
     pub fn new_ecdsa_with_sha384() -> Self {
         Self {
             algorithm: oids::ecdsa_with_sha384().into(),
@@ -82,6 +89,34 @@ impl AlgorithmIdentifier {
         }
     }
 
+    pub fn new_rsassa_pss(parameters: RsassaPssParameters) -> Self {
+        Self {
+            algorithm: oids::rsassa_pss().into(),
+            parameters: AlgorithmIdentifierParameters::RsassaPss(Box::new(parameters)),
+        }
+    }
+
+    pub fn new_sha(hash: HashAlgorithm) -> Self {
+        Self {
+            algorithm: hash.oid().into(),
+            parameters: AlgorithmIdentifierParameters::Null,
+        }
+    }
+
+    pub fn new_ed25519() -> Self {
+        Self {
+            algorithm: oids::ed25519().into(),
+            parameters: AlgorithmIdentifierParameters::None,
+        }
+    }
+
+    pub fn new_ed448() -> Self {
+        Self {
+            algorithm: oids::ed448().into(),
+            parameters: AlgorithmIdentifierParameters::None,
+        }
+    }
+
     pub fn new_elliptic_curve<P: Into<ECParameters>>(ec_params: P) -> Self {
         Self {
             algorithm: oids::ec_public_key().into(),
@@ -106,6 +141,9 @@ impl ser::Serialize for AlgorithmIdentifier {
             AlgorithmIdentifierParameters::EC(ec_params) => {
                 seq.serialize_element(ec_params)?;
             }
+            AlgorithmIdentifierParameters::RsassaPss(pss_params) => {
+                seq.serialize_element(pss_params)?;
+            }
         }
         seq.end()
     }
@@ -141,7 +179,23 @@ impl<'de> de::Deserialize<'de> for AlgorithmIdentifier {
                         seq_next_element!(seq, AlgorithmIdentifier, "algorithm identifier parameters (null)");
                         AlgorithmIdentifierParameters::Null
                     }
-                    oids::ECDSA_WITH_SHA384 | oids::ECDSA_WITH_SHA256 => AlgorithmIdentifierParameters::None,
+                    oids::RSASSA_PSS => AlgorithmIdentifierParameters::RsassaPss(seq_next_element!(
+                        seq,
+                        AlgorithmIdentifier,
+                        "RSASSA-PSS parameters"
+                    )),
+                    // RFC 4055: parameters may be either absent or null
+                    oids::SHA1 | oids::SHA224 | oids::SHA256 | oids::SHA384 | oids::SHA512 => {
+                        match seq.next_element::<()>()? {
+                            Some(()) => AlgorithmIdentifierParameters::Null,
+                            None => AlgorithmIdentifierParameters::None,
+                        }
+                    }
+                    oids::ECDSA_WITH_SHA512 | oids::ECDSA_WITH_SHA384 | oids::ECDSA_WITH_SHA256 => {
+                        AlgorithmIdentifierParameters::None
+                    }
+                    // RFC 8410: parameters must be absent
+                    oids::ED25519 | oids::ED448 => AlgorithmIdentifierParameters::None,
                     oids::EC_PUBLIC_KEY => AlgorithmIdentifierParameters::EC(seq_next_element!(
                         seq,
                         AlgorithmIdentifier,
@@ -172,6 +226,221 @@ pub enum AlgorithmIdentifierParameters {
     None,
     Null,
     EC(ECParameters),
+    RsassaPss(Box<RsassaPssParameters>),
+}
+
+/// Hash algorithms usable with RSASSA-PSS and for fingerprints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    pub fn oid(self) -> ObjectIdentifier {
+        match self {
+            HashAlgorithm::Sha1 => oids::sha1(),
+            HashAlgorithm::Sha224 => oids::sha224(),
+            HashAlgorithm::Sha256 => oids::sha256(),
+            HashAlgorithm::Sha384 => oids::sha384(),
+            HashAlgorithm::Sha512 => oids::sha512(),
+        }
+    }
+
+    pub fn from_oid(oid: &ObjectIdentifier) -> Option<Self> {
+        match Into::<String>::into(oid).as_str() {
+            oids::SHA1 => Some(HashAlgorithm::Sha1),
+            oids::SHA224 => Some(HashAlgorithm::Sha224),
+            oids::SHA256 => Some(HashAlgorithm::Sha256),
+            oids::SHA384 => Some(HashAlgorithm::Sha384),
+            oids::SHA512 => Some(HashAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    /// Digest size in bytes
+    pub fn output_size(self) -> usize {
+        match self {
+            HashAlgorithm::Sha1 => 20,
+            HashAlgorithm::Sha224 => 28,
+            HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Sha384 => 48,
+            HashAlgorithm::Sha512 => 64,
+        }
+    }
+
+    /// Hashes the message
+    pub fn digest(self, msg: &[u8]) -> Vec<u8> {
+        use sha1::{Digest, Sha1};
+        use sha2::{Sha224, Sha256, Sha384, Sha512};
+
+        match self {
+            HashAlgorithm::Sha1 => Sha1::digest(msg).to_vec(),
+            HashAlgorithm::Sha224 => Sha224::digest(msg).to_vec(),
+            HashAlgorithm::Sha256 => Sha256::digest(msg).to_vec(),
+            HashAlgorithm::Sha384 => Sha384::digest(msg).to_vec(),
+            HashAlgorithm::Sha512 => Sha512::digest(msg).to_vec(),
+        }
+    }
+}
+
+// RSASSA-PSS parameters:
+// https://tools.ietf.org/html/rfc4055#section-3.1
+//
+// RSASSA-PSS-params  ::=  SEQUENCE  {
+//     hashAlgorithm      [0] HashAlgorithm DEFAULT sha1Identifier,
+//     maskGenAlgorithm   [1] MaskGenAlgorithm DEFAULT mgf1SHA1Identifier,
+//     saltLength         [2] INTEGER DEFAULT 20,
+//     trailerField       [3] INTEGER DEFAULT 1  }
+//
+// Fields holding their default value are omitted when serializing (DER).
+// Only the trailerField value 1 is defined.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RsassaPssParameters {
+    hash_algorithm: AlgorithmIdentifier,
+    mask_gen_algorithm: MaskGenAlgorithm,
+    salt_length: u16,
+}
+
+impl Default for RsassaPssParameters {
+    fn default() -> Self {
+        Self {
+            hash_algorithm: AlgorithmIdentifier::new_sha(HashAlgorithm::Sha1),
+            mask_gen_algorithm: MaskGenAlgorithm::new_mgf1(HashAlgorithm::Sha1),
+            salt_length: 20,
+        }
+    }
+}
+
+impl RsassaPssParameters {
+    /// Usual parameters: MGF1 with the same hash algorithm and a salt as long as the digest.
+    pub fn new(hash: HashAlgorithm) -> Self {
+        Self {
+            hash_algorithm: AlgorithmIdentifier::new_sha(hash),
+            mask_gen_algorithm: MaskGenAlgorithm::new_mgf1(hash),
+            salt_length: hash.output_size() as u16,
+        }
+    }
+
+    pub fn hash_algorithm(&self) -> &AlgorithmIdentifier {
+        &self.hash_algorithm
+    }
+
+    pub fn mask_gen_algorithm(&self) -> &MaskGenAlgorithm {
+        &self.mask_gen_algorithm
+    }
+
+    pub fn salt_length(&self) -> u16 {
+        self.salt_length
+    }
+}
+
+impl ser::Serialize for RsassaPssParameters {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let default = Self::default();
+        let mut seq = serializer.serialize_seq(Some(3))?;
+        if self.hash_algorithm.oid() != default.hash_algorithm.oid() {
+            seq.serialize_element(&ApplicationTag0(self.hash_algorithm.clone()))?;
+        }
+        if self.mask_gen_algorithm != default.mask_gen_algorithm {
+            seq.serialize_element(&ApplicationTag1(self.mask_gen_algorithm.clone()))?;
+        }
+        if self.salt_length != default.salt_length {
+            seq.serialize_element(&ApplicationTag2(self.salt_length))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for RsassaPssParameters {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = RsassaPssParameters;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded RSASSA-PSS-params")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut params = RsassaPssParameters::default();
+
+                if let Some(TagPeeker { next_tag: Tag::APP_0 }) = seq.next_element()? {
+                    let hash_algorithm: ApplicationTag0<AlgorithmIdentifier> =
+                        seq_next_element!(seq, RsassaPssParameters, "hash algorithm");
+                    params.hash_algorithm = hash_algorithm.0;
+                }
+
+                if let Some(TagPeeker { next_tag: Tag::APP_1 }) = seq.next_element()? {
+                    let mask_gen_algorithm: ApplicationTag1<MaskGenAlgorithm> =
+                        seq_next_element!(seq, RsassaPssParameters, "mask generation algorithm");
+                    params.mask_gen_algorithm = mask_gen_algorithm.0;
+                }
+
+                if let Some(TagPeeker { next_tag: Tag::APP_2 }) = seq.next_element()? {
+                    let salt_length: ApplicationTag2<u16> = seq_next_element!(seq, RsassaPssParameters, "salt length");
+                    params.salt_length = salt_length.0;
+                }
+
+                if let Some(TagPeeker { next_tag: Tag::APP_3 }) = seq.next_element()? {
+                    let trailer_field: ApplicationTag3<u8> =
+                        seq_next_element!(seq, RsassaPssParameters, "trailer field");
+                    if trailer_field.0 != 1 {
+                        return Err(serde_invalid_value!(
+                            RsassaPssParameters,
+                            "unsupported trailer field",
+                            "trailer field 1 (trailerFieldBC)"
+                        ));
+                    }
+                }
+
+                Ok(params)
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// MaskGenAlgorithm ::= AlgorithmIdentifier { {PKCS1MGFAlgorithms} }
+//
+// Only MGF1 is defined, with the hash algorithm as parameter.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct MaskGenAlgorithm {
+    algorithm: ObjectIdentifierAsn1,
+    hash_algorithm: AlgorithmIdentifier,
+}
+
+impl MaskGenAlgorithm {
+    pub fn new_mgf1(hash: HashAlgorithm) -> Self {
+        Self {
+            algorithm: oids::id_mgf1().into(),
+            hash_algorithm: AlgorithmIdentifier::new_sha(hash),
+        }
+    }
+
+    pub fn oid(&self) -> &ObjectIdentifier {
+        &self.algorithm.0
+    }
+
+    pub fn hash_algorithm(&self) -> &AlgorithmIdentifier {
+        &self.hash_algorithm
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -252,3 +521,45 @@ impl<'de> de::Deserialize<'de> for ECParameters {
         deserializer.deserialize_enum("DirectoryString", &["NamedCurve", "ImplicitCurve"], Visitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rsassa_pss_sha512() {
+        let encoded = base64::decode(
+            "MEEGCSqGSIb3DQEBCjA0oA8wDQYJYIZIAWUDBAIDBQChHDAaBgkqhkiG9w0BAQgwDQYJYIZIAWUDBAIDBQCiAwIBQA==",
+        )
+        .expect("invalid base64");
+
+        let hash_algorithm = AlgorithmIdentifier::new_sha(HashAlgorithm::Sha512);
+        check_serde!(hash_algorithm: AlgorithmIdentifier in encoded[17..32]);
+
+        let mask_gen_algorithm = MaskGenAlgorithm::new_mgf1(HashAlgorithm::Sha512);
+        check_serde!(mask_gen_algorithm: MaskGenAlgorithm in encoded[34..62]);
+
+        let algorithm = AlgorithmIdentifier::new_rsassa_pss(RsassaPssParameters::new(HashAlgorithm::Sha512));
+        check_serde!(algorithm: AlgorithmIdentifier in encoded);
+    }
+
+    #[test]
+    fn rsassa_pss_default_parameters() {
+        // all fields hold their default value: sha1, mgf1 with sha1 and a 20 bytes salt
+        let encoded = [
+            0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0A, 0x30, 0x00,
+        ];
+        let algorithm = AlgorithmIdentifier::new_rsassa_pss(RsassaPssParameters::new(HashAlgorithm::Sha1));
+        check_serde!(algorithm: AlgorithmIdentifier in encoded);
+    }
+
+    #[test]
+    fn sha256_without_null_parameters() {
+        let encoded = [
+            0x30, 0x0B, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
+        ];
+        let algorithm: AlgorithmIdentifier = picky_asn1_der::from_bytes(&encoded).expect("deserialization");
+        assert_eq!(algorithm.oid(), &oids::sha256());
+        assert_eq!(algorithm.parameters(), &AlgorithmIdentifierParameters::None);
+    }
+}
@@ -162,6 +162,9 @@ impl Jwk {
             SerdePublicKey::EC(_) => Err(JwkError::UnsupportedAlgorithm {
                 algorithm: "elliptic curves",
             }),
+            SerdePublicKey::ED(_) => Err(JwkError::UnsupportedAlgorithm {
+                algorithm: "edwards curves",
+            }),
         }
     }
 
@@ -1,6 +1,6 @@
 use crate::{
-    key::{PrivateKey, PublicKey},
-    signature::{SignatureError, SignatureHashType},
+    key::{EdAlgorithm, PublicKey},
+    signature::{SignatureError, SignatureHashType, Signer},
 };
 use base64::DecodeError;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -296,21 +296,25 @@ impl<'a, C> Jwt<'a, C> {
         }
 
         let signature = base64::decode_config(&encoded_token[last_dot_idx + 1..], base64::URL_SAFE_NO_PAD)?;
+        let signature = jws_signature_to_der(self.header.alg, signature)?;
 
-        self.header
-            .alg
-            .verify(public_key, &encoded_token[..last_dot_idx].as_bytes(), &signature)?;
+        jws_verification_alg(self.header.alg, public_key).verify(
+            public_key,
+            &encoded_token[..last_dot_idx].as_bytes(),
+            &signature,
+        )?;
 
         Ok(())
     }
 }
 
 impl<'a, C: Serialize> Jwt<'a, C> {
-    pub fn encode(&self, private_key: &PrivateKey) -> Result<String, JwtError> {
+    pub fn encode(&self, signer: &dyn Signer) -> Result<String, JwtError> {
         let header_base64 = base64::encode_config(&serde_json::to_vec(&self.header)?, base64::URL_SAFE_NO_PAD);
         let claims_base64 = base64::encode_config(&serde_json::to_vec(&self.claims)?, base64::URL_SAFE_NO_PAD);
         let header_claims = [header_base64, claims_base64].join(".");
-        let signature = self.header.alg.sign(header_claims.as_bytes(), private_key)?;
+        let signature = signer.sign(header_claims.as_bytes(), self.header.alg)?;
+        let signature = der_signature_to_jws(self.header.alg, signature)?;
         let signature_base64 = base64::encode_config(&signature, base64::URL_SAFE_NO_PAD);
         Ok([header_claims, signature_base64].join("."))
     }
@@ -342,10 +346,13 @@ impl<'a, C: DeserializeOwned> Jwt<'a, C> {
 
         if let Some(public_key) = &validator.public_key {
             let signature = base64::decode_config(&encoded_token[last_dot_idx + 1..], base64::URL_SAFE_NO_PAD)?;
+            let signature = jws_signature_to_der(header.alg, signature)?;
 
-            header
-                .alg
-                .verify(public_key, &encoded_token[..last_dot_idx].as_bytes(), &signature)?;
+            jws_verification_alg(header.alg, public_key).verify(
+                public_key,
+                &encoded_token[..last_dot_idx].as_bytes(),
+                &signature,
+            )?;
         }
 
         let claims_json =
@@ -420,10 +427,52 @@ impl<'a, C: DeserializeOwned> Jwt<'a, C> {
     }
 }
 
+// JWS ECDSA signatures are the raw concatenation of R and S (RFC 7518 section 3.4)
+// while `SignatureHashType` deals with DER-encoded ECDSA-Sig-Value structures.
+
+fn der_signature_to_jws(alg: SignatureHashType, signature: Vec<u8>) -> Result<Vec<u8>, SignatureError> {
+    match alg {
+        SignatureHashType::EcdsaSha256 => p256::ecdsa::Signature::from_der(&signature)
+            .map(|s| s.to_bytes().to_vec())
+            .map_err(|_| SignatureError::BadSignature),
+        SignatureHashType::EcdsaSha384 => p384::ecdsa::Signature::from_der(&signature)
+            .map(|s| s.to_bytes().to_vec())
+            .map_err(|_| SignatureError::BadSignature),
+        SignatureHashType::EcdsaSha512 => p521::ecdsa::Signature::from_der(&signature)
+            .map(|s| s.to_bytes().to_vec())
+            .map_err(|_| SignatureError::BadSignature),
+        _ => Ok(signature),
+    }
+}
+
+/// JWS uses `EdDSA` for both Edwards curves (always deserialized as `Ed25519`): the actual
+/// algorithm is given by the verifying key.
+fn jws_verification_alg(alg: SignatureHashType, public_key: &PublicKey) -> SignatureHashType {
+    match (alg, public_key.ed_algorithm()) {
+        (SignatureHashType::Ed25519, Some(EdAlgorithm::Ed448)) => SignatureHashType::Ed448,
+        _ => alg,
+    }
+}
+
+fn jws_signature_to_der(alg: SignatureHashType, signature: Vec<u8>) -> Result<Vec<u8>, SignatureError> {
+    match alg {
+        SignatureHashType::EcdsaSha256 => p256::ecdsa::Signature::from_slice(&signature)
+            .map(|s| s.to_der().as_bytes().to_vec())
+            .map_err(|_| SignatureError::BadSignature),
+        SignatureHashType::EcdsaSha384 => p384::ecdsa::Signature::from_slice(&signature)
+            .map(|s| s.to_der().as_bytes().to_vec())
+            .map_err(|_| SignatureError::BadSignature),
+        SignatureHashType::EcdsaSha512 => p521::ecdsa::Signature::from_slice(&signature)
+            .map(|s| s.to_der().as_bytes().to_vec())
+            .map_err(|_| SignatureError::BadSignature),
+        _ => Ok(signature),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pem::Pem;
+    use crate::{key::PrivateKey, pem::Pem};
 
     #[derive(Serialize, Deserialize, Debug, PartialEq)]
     struct MyClaims {
@@ -460,6 +509,29 @@ mod tests {
         assert_eq!(encoded, crate::test_files::JOSE_JWT_EXAMPLE);
     }
 
+    #[test]
+    fn encode_with_custom_signer() {
+        struct RemoteSigner(PrivateKey);
+
+        impl Signer for RemoteSigner {
+            fn public_key(&self) -> PublicKey {
+                self.0.to_public_key()
+            }
+
+            fn signature_hash_type(&self) -> SignatureHashType {
+                SignatureHashType::RsaSha256
+            }
+
+            fn sign(&self, msg: &[u8], signature_hash_type: SignatureHashType) -> Result<Vec<u8>, SignatureError> {
+                signature_hash_type.sign(msg, &self.0)
+            }
+        }
+
+        let jwt = Jwt::new(SignatureHashType::RsaSha256, get_strongly_typed_claims());
+        let encoded = jwt.encode(&RemoteSigner(get_private_key_1())).unwrap();
+        assert_eq!(encoded, crate::test_files::JOSE_JWT_EXAMPLE);
+    }
+
     #[test]
     fn decode_rsa_sha256() {
         let public_key = get_private_key_1().to_public_key();
@@ -477,6 +549,50 @@ mod tests {
         Jwt::<MyClaims>::decode(crate::test_files::JOSE_JWT_EXAMPLE, &validator).unwrap();
     }
 
+    const EC_P256_PUBLIC_KEY: &str = "-----BEGIN PUBLIC KEY-----\n\
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEkjQapUBEr5D2VpuKso3CMY+FZm3q\n\
+J9YFJVH8l1Foai1PFI09iCV/dLfuoJ6BnG8Y+h+9VIin3zEMtPjjx5JdAA==\n\
+-----END PUBLIC KEY-----";
+
+    #[test]
+    fn decode_ecdsa_sha256() {
+        let public_key = PublicKey::from_pem(&EC_P256_PUBLIC_KEY.parse::<Pem>().unwrap()).unwrap();
+        let validator = JwtValidator::signature_only(&public_key);
+        let jwt = Jwt::<MyClaims>::decode(crate::test_files::JOSE_JWT_ES256_EXAMPLE, &validator).unwrap();
+        assert_eq!(jwt.into_claims(), get_strongly_typed_claims());
+
+        let rsa_public_key = get_private_key_1().to_public_key();
+        let err = Jwt::<MyClaims>::decode(
+            crate::test_files::JOSE_JWT_ES256_EXAMPLE,
+            &JwtValidator::signature_only(&rsa_public_key),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "signature error: EcdsaSha256 can't be used with RSA key"
+        );
+    }
+
+    #[test]
+    fn eddsa_roundtrip() {
+        // JWS uses `EdDSA` for both Ed25519 and Ed448
+        for pem_str in &[crate::test_files::ED25519_PK_1, crate::test_files::ED448_PK_1] {
+            let private_key = PrivateKey::from_pem(&pem_str.parse::<Pem>().unwrap()).unwrap();
+            let jwt = Jwt::new(private_key.signature_hash_type(), get_strongly_typed_claims());
+            let encoded = jwt.encode(&private_key).unwrap();
+            assert!(encoded.starts_with(&base64::encode_config(
+                r#"{"alg":"EdDSA","typ":"JWT"}"#,
+                base64::URL_SAFE_NO_PAD
+            )));
+
+            let public_key = private_key.to_public_key();
+            let validator = JwtValidator::signature_only(&public_key);
+            let jwt = Jwt::<MyClaims>::decode(&encoded, &validator).unwrap();
+            assert_eq!(jwt.into_claims(), get_strongly_typed_claims());
+        }
+    }
+
     #[test]
     fn decode_invalid_validator_err() {
         let public_key = get_private_key_1().to_public_key();
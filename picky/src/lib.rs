@@ -8,6 +8,15 @@ pub mod jose;
 #[cfg(feature = "x509")]
 pub mod x509;
 
+#[cfg(feature = "x509")]
+pub mod pkcs12;
+
+#[cfg(feature = "x509")]
+pub mod loader;
+
+#[cfg(feature = "ssh")]
+pub mod ssh;
+
 pub mod algorithm_identifier;
 pub mod key;
 pub mod oids;
@@ -27,11 +36,91 @@ mod test_files {
             include_str!("../../test_assets/private_keys/rsa-2048-pk_3.key");
         pub const RSA_2048_PK_4: &str =
             include_str!("../../test_assets/private_keys/rsa-2048-pk_4.key");
+        pub const RSA_2048_PK_1_ENCRYPTED: &str =
+            include_str!("../../test_assets/private_keys/rsa-2048-pk_1-encrypted.key");
+        pub const RSA_2048_PK_1_LEGACY_AES256: &str =
+            include_str!("../../test_assets/private_keys/rsa-2048-pk_1-legacy-aes256.key");
+        pub const RSA_2048_PK_1_LEGACY_DES3: &str =
+            include_str!("../../test_assets/private_keys/rsa-2048-pk_1-legacy-des3.key");
+        pub const EC_P256_PK_1: &str =
+            include_str!("../../test_assets/private_keys/ec-p256-pk_1.key");
+        pub const EC_P256_PK_2: &str =
+            include_str!("../../test_assets/private_keys/ec-p256-pk_2.key");
+        pub const EC_P256_PK_1_ENCRYPTED: &str =
+            include_str!("../../test_assets/private_keys/ec-p256-pk_1-encrypted.key");
+        pub const EC_P256_PK_1_LEGACY_AES128: &str =
+            include_str!("../../test_assets/private_keys/ec-p256-pk_1-legacy-aes128.key");
+        pub const EC_P384_PK_1: &str =
+            include_str!("../../test_assets/private_keys/ec-p384-pk_1.key");
+        pub const EC_P521_PK_1: &str =
+            include_str!("../../test_assets/private_keys/ec-p521-pk_1.key");
+        pub const ED25519_PK_1: &str =
+            include_str!("../../test_assets/private_keys/ed25519-pk_1.key");
+        pub const ED25519_PK_2: &str =
+            include_str!("../../test_assets/private_keys/ed25519-pk_2.key");
+        pub const ED448_PK_1: &str =
+            include_str!("../../test_assets/private_keys/ed448-pk_1.key");
+
+        pub const SSH_RSA_2048_PK_1: &str =
+            include_str!("../../test_assets/ssh/rsa-2048-pk_1.key");
+        pub const SSH_RSA_2048_PK_1_ENCRYPTED: &str =
+            include_str!("../../test_assets/ssh/rsa-2048-pk_1-encrypted.key");
+        pub const SSH_EC_P256_PK_1: &str =
+            include_str!("../../test_assets/ssh/ec-p256-pk_1.key");
+        pub const SSH_EC_P256_PK_1_ENCRYPTED: &str =
+            include_str!("../../test_assets/ssh/ec-p256-pk_1-encrypted.key");
+        pub const SSH_EC_P384_PK_1: &str =
+            include_str!("../../test_assets/ssh/ec-p384-pk_1.key");
+        pub const SSH_EC_P521_PK_1: &str =
+            include_str!("../../test_assets/ssh/ec-p521-pk_1.key");
+        pub const SSH_ED25519_PK_1: &str =
+            include_str!("../../test_assets/ssh/ed25519-pk_1.key");
+        pub const SSH_RSA_2048_PK_1_PUB: &str =
+            include_str!("../../test_assets/ssh/rsa-2048-pk_1.pub");
+        pub const SSH_EC_P256_PK_1_PUB: &str =
+            include_str!("../../test_assets/ssh/ec-p256-pk_1.pub");
+        pub const SSH_EC_P384_PK_1_PUB: &str =
+            include_str!("../../test_assets/ssh/ec-p384-pk_1.pub");
+        pub const SSH_EC_P521_PK_1_PUB: &str =
+            include_str!("../../test_assets/ssh/ec-p521-pk_1.pub");
+        pub const SSH_ED25519_PK_1_PUB: &str =
+            include_str!("../../test_assets/ssh/ed25519-pk_1.pub");
 
         pub const CSR: &str = include_str!("../../test_assets/certification_request.csr");
+        pub const RSA_PSS_CSR: &str = include_str!("../../test_assets/rsa_pss_certification_request.csr");
 
         pub const INTERMEDIATE_CA: &str = include_str!("../../test_assets/intermediate_ca.crt");
         pub const ROOT_CA: &str = include_str!("../../test_assets/root_ca.crt");
+
+        pub const EC_LEAF: &str = include_str!("../../test_assets/ec_leaf.crt");
+        pub const EC_INTERMEDIATE_CA: &str = include_str!("../../test_assets/ec_intermediate_ca.crt");
+        pub const EC_ROOT_CA: &str = include_str!("../../test_assets/ec_root_ca.crt");
+        pub const EC_ROOT_CA_NO_SKI: &str = include_str!("../../test_assets/ec_root_ca_no_ski.crt");
+
+        pub const ED25519_LEAF: &str = include_str!("../../test_assets/ed25519_leaf.crt");
+        pub const ED448_INTERMEDIATE_CA: &str = include_str!("../../test_assets/ed448_intermediate_ca.crt");
+        pub const ED25519_ROOT_CA: &str = include_str!("../../test_assets/ed25519_root_ca.crt");
+
+        pub const RSA_PSS_LEAF: &str = include_str!("../../test_assets/rsa_pss_leaf.crt");
+        pub const RSA_PSS_ROOT_CA: &str = include_str!("../../test_assets/rsa_pss_root_ca.crt");
+
+        pub const EC_LEAF_PFX: &[u8] = include_bytes!("../../test_assets/ec_leaf.pfx");
+        pub const EC_LEAF_LEGACY_PFX: &[u8] = include_bytes!("../../test_assets/ec_leaf_legacy.pfx");
+
+        pub const CRL_CA: &str = include_str!("../../test_assets/crl/ca.crt");
+        pub const CRL: &str = include_str!("../../test_assets/crl/ca.crl");
+        pub const CRL_REVOKED_LEAF: &str = include_str!("../../test_assets/crl/revoked_leaf.crt");
+        pub const CRL_VALID_LEAF: &str = include_str!("../../test_assets/crl/valid_leaf.crt");
+
+        pub const OCSP_REQUEST: &[u8] = include_bytes!("../../test_assets/ocsp/request.der");
+        pub const OCSP_RESPONSE: &[u8] = include_bytes!("../../test_assets/ocsp/response.der");
+    }}
+
+    cfg_if::cfg_if! { if #[cfg(feature = "ssh")] {
+        pub const SSH_ED25519_PK_1_USER_CERT: &str =
+            include_str!("../../test_assets/ssh/ed25519-pk_1-user-cert.pub");
+        pub const SSH_EC_P384_PK_1_HOST_CERT: &str =
+            include_str!("../../test_assets/ssh/ec-p384-pk_1-host-cert.pub");
     }}
 
     cfg_if::cfg_if! { if #[cfg(feature = "jose")] {
@@ -39,6 +128,8 @@ mod test_files {
             include_str!("../../test_assets/jose/jwt_example.txt");
         pub const JOSE_JWT_WITH_EXP: &str =
             include_str!("../../test_assets/jose/jwt_with_exp.txt");
+        pub const JOSE_JWT_ES256_EXAMPLE: &str =
+            include_str!("../../test_assets/jose/jwt_es256_example.txt");
         pub const JOSE_JWK_SET: &str =
             include_str!("../../test_assets/jose/jwk_set.json");
     }}
@@ -0,0 +1,342 @@
+//! Loading of keys, certificates and certification requests without knowing their type beforehand.
+//!
+//! Input can be PEM (the label is used to pick the structure), raw DER or base64 encoded DER
+//! (the structure is detected from its ASN.1 shape).
+
+use crate::{
+    key::{KeyError, PrivateKey, PublicKey},
+    pem::{parse_pem, Pem, PemError},
+    x509::{
+        certificate::{Cert, CertError},
+        csr::{Csr, CsrError},
+    },
+};
+use picky_asn1::wrapper::{Asn1SequenceOf, RawDerAsn1};
+use snafu::{ResultExt, Snafu};
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_CONTEXT_0: u8 = 0xA0;
+
+#[derive(Debug, Snafu)]
+pub enum LoaderError {
+    /// PEM error
+    #[snafu(display("PEM error: {}", source))]
+    InvalidPem { source: PemError },
+
+    /// certificate error
+    #[snafu(display("certificate error: {}", source))]
+    Certificate { source: CertError },
+
+    /// CSR error
+    #[snafu(display("CSR error: {}", source))]
+    CertificationRequest { source: CsrError },
+
+    /// key error
+    #[snafu(display("key error: {}", source))]
+    Key { source: KeyError },
+
+    /// unsupported PEM label
+    #[snafu(display("unsupported PEM label: {}", label))]
+    UnsupportedPemLabel { label: String },
+
+    /// structure couldn't be detected
+    #[snafu(display("unknown structure (neither PEM, DER nor base64 encoded DER)"))]
+    UnknownStructure,
+}
+
+/// Encoding of the data given to `LoadedObject::from_bytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Pem,
+    Der,
+    Base64Der,
+}
+
+impl Encoding {
+    /// Detects the encoding the same way `LoadedObject::from_bytes` does.
+    ///
+    /// The data isn't validated: `Base64Der` is returned for anything that is neither PEM nor DER.
+    pub fn detect<T: ?Sized + AsRef<[u8]>>(data: &T) -> Self {
+        let data = data.as_ref();
+        if data.windows(10).any(|window| window == b"-----BEGIN") {
+            Encoding::Pem
+        } else if data.first() == Some(&TAG_SEQUENCE) {
+            Encoding::Der
+        } else {
+            Encoding::Base64Der
+        }
+    }
+}
+
+/// A structure loaded by `LoadedObject::from_bytes`.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum LoadedObject {
+    Cert(Cert),
+    Csr(Csr),
+    PrivateKey(PrivateKey),
+    PublicKey(PublicKey),
+}
+
+impl LoadedObject {
+    /// Loads a PEM, DER or base64 encoded DER structure.
+    pub fn from_bytes<T: ?Sized + AsRef<[u8]>>(data: &T) -> Result<Self, LoaderError> {
+        let data = data.as_ref();
+
+        match Encoding::detect(data) {
+            Encoding::Pem => {
+                let pem = parse_pem(data).context(InvalidPem)?;
+                Self::from_pem(&pem)
+            }
+            Encoding::Der => Self::from_der(data),
+            Encoding::Base64Der => {
+                let base64_data: Vec<u8> = data
+                    .iter()
+                    .copied()
+                    .filter(|byte| !byte.is_ascii_whitespace())
+                    .collect();
+                match base64::decode(&base64_data) {
+                    Ok(der) if !der.is_empty() => Self::from_der(&der),
+                    _ => Err(LoaderError::UnknownStructure),
+                }
+            }
+        }
+    }
+
+    /// Loads a structure based on the PEM label.
+    pub fn from_pem(pem: &Pem) -> Result<Self, LoaderError> {
+        match pem.label() {
+            "CERTIFICATE" | "X509 CERTIFICATE" => {
+                Cert::from_der(pem.data()).map(LoadedObject::Cert).context(Certificate)
+            }
+            "CERTIFICATE REQUEST" | "NEW CERTIFICATE REQUEST" => Csr::from_der(pem.data())
+                .map(LoadedObject::Csr)
+                .context(CertificationRequest),
+            "PUBLIC KEY" | "RSA PUBLIC KEY" => PublicKey::from_pem(pem).map(LoadedObject::PublicKey).context(Key),
+            "PRIVATE KEY" | "ENCRYPTED PRIVATE KEY" | "RSA PRIVATE KEY" | "EC PRIVATE KEY" | "OPENSSH PRIVATE KEY" => {
+                PrivateKey::from_pem(pem).map(LoadedObject::PrivateKey).context(Key)
+            }
+            unsupported => Err(LoaderError::UnsupportedPemLabel {
+                label: unsupported.to_owned(),
+            }),
+        }
+    }
+
+    /// Loads a DER structure based on its ASN.1 shape.
+    pub fn from_der<T: ?Sized + AsRef<[u8]>>(der: &T) -> Result<Self, LoaderError> {
+        let der = der.as_ref();
+
+        let elements = sequence_elements(der).ok_or(LoaderError::UnknownStructure)?;
+
+        match elements.as_slice() {
+            // Certificate and CertificationRequest: { info, signatureAlgorithm, signature }
+            [(TAG_SEQUENCE, info), (TAG_SEQUENCE, _), (TAG_BIT_STRING, _)] => {
+                let info_elements = sequence_elements(info).ok_or(LoaderError::UnknownStructure)?;
+                match info_elements.as_slice() {
+                    // CertificationRequestInfo: { version, subject, subjectPKInfo, [0] attributes }
+                    [(TAG_INTEGER, _), (TAG_SEQUENCE, _), (TAG_SEQUENCE, _), (TAG_CONTEXT_0, _)] => {
+                        Csr::from_der(der).map(LoadedObject::Csr).context(CertificationRequest)
+                    }
+                    _ => Cert::from_der(der).map(LoadedObject::Cert).context(Certificate),
+                }
+            }
+            // SubjectPublicKeyInfo: { algorithm, subjectPublicKey }
+            [(TAG_SEQUENCE, _), (TAG_BIT_STRING, _)] => {
+                PublicKey::from_der(der).map(LoadedObject::PublicKey).context(Key)
+            }
+            // EncryptedPrivateKeyInfo: { encryptionAlgorithm, encryptedData }
+            [(TAG_SEQUENCE, _), (TAG_OCTET_STRING, _)] => Err(LoaderError::Key {
+                source: KeyError::Encryption {
+                    context: "private key is encrypted, use `PrivateKey::from_encrypted_pkcs8` instead".to_owned(),
+                },
+            }),
+            // PrivateKeyInfo (PKCS#8): { version, privateKeyAlgorithm, privateKey, ... }
+            [(TAG_INTEGER, _), (TAG_SEQUENCE, _), (TAG_OCTET_STRING, _), ..] => {
+                PrivateKey::from_pkcs8(der).map(LoadedObject::PrivateKey).context(Key)
+            }
+            // ECPrivateKey (SEC1): { version, privateKey, [0] parameters, [1] publicKey }
+            [(TAG_INTEGER, _), (TAG_OCTET_STRING, _), ..] => {
+                PrivateKey::from_ec_der(der).map(LoadedObject::PrivateKey).context(Key)
+            }
+            // RSAPublicKey (PKCS#1): { modulus, publicExponent }
+            [(TAG_INTEGER, _), (TAG_INTEGER, _)] => {
+                PublicKey::from_rsa_der(der).map(LoadedObject::PublicKey).context(Key)
+            }
+            // RSAPrivateKey (PKCS#1): { version (0), modulus, publicExponent, privateExponent,
+            //                          prime1, prime2, exponent1, exponent2, coefficient }
+            [(TAG_INTEGER, version), ..]
+                if version.as_slice() == [TAG_INTEGER, 0x01, 0x00]
+                    && elements.len() == 9
+                    && elements.iter().all(|(tag, _)| *tag == TAG_INTEGER) =>
+            {
+                PrivateKey::from_rsa_der(der).map(LoadedObject::PrivateKey).context(Key)
+            }
+            _ => Err(LoaderError::UnknownStructure),
+        }
+    }
+
+    /// Human-readable name of the loaded structure.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LoadedObject::Cert(_) => "certificate",
+            LoadedObject::Csr(_) => "certification request",
+            LoadedObject::PrivateKey(_) => "private key",
+            LoadedObject::PublicKey(_) => "public key",
+        }
+    }
+}
+
+/// Tag and encoding of each element of `der`, which must be a single SEQUENCE.
+fn sequence_elements(der: &[u8]) -> Option<Vec<(u8, Vec<u8>)>> {
+    let sequence: RawDerAsn1 = picky_asn1_der::from_bytes(der).ok()?;
+    if sequence.0.len() != der.len() || der[0] != TAG_SEQUENCE {
+        return None;
+    }
+
+    let elements: Asn1SequenceOf<RawDerAsn1> = picky_asn1_der::from_bytes(der).ok()?;
+    Some(
+        elements
+            .0
+            .into_iter()
+            .map(|RawDerAsn1(element)| (element[0], element))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{key::Pkcs8EncryptionParams, test_files};
+
+    fn pem_data(pem_str: &str) -> Vec<u8> {
+        pem_str.parse::<Pem>().expect("pem").into_data().into_owned()
+    }
+
+    fn private_key(pem_str: &str) -> PrivateKey {
+        PrivateKey::from_pem(&pem_str.parse::<Pem>().expect("pem")).expect("private key")
+    }
+
+    #[test]
+    fn load_pem() {
+        for &(pem_str, kind) in &[
+            (test_files::INTERMEDIATE_CA, "certificate"),
+            (test_files::CSR, "certification request"),
+            (test_files::RSA_2048_PK_1, "private key"),
+            (test_files::EC_P256_PK_1, "private key"),
+            (test_files::SSH_ED25519_PK_1, "private key"),
+        ] {
+            let loaded = LoadedObject::from_bytes(pem_str).expect("load pem");
+            assert_eq!(loaded.kind(), kind);
+        }
+
+        let public_key_pem = private_key(test_files::EC_P256_PK_1)
+            .to_public_key()
+            .to_pem()
+            .expect("public key pem");
+        let loaded = LoadedObject::from_bytes(&public_key_pem).expect("load public key pem");
+        assert_eq!(loaded.kind(), "public key");
+    }
+
+    #[test]
+    fn load_der() {
+        let cert_der = pem_data(test_files::INTERMEDIATE_CA);
+        assert_eq!(
+            LoadedObject::from_bytes(&cert_der).expect("load certificate"),
+            LoadedObject::Cert(Cert::from_der(&cert_der).expect("certificate"))
+        );
+
+        let csr_der = pem_data(test_files::CSR);
+        assert_eq!(
+            LoadedObject::from_bytes(&csr_der).expect("load csr"),
+            LoadedObject::Csr(Csr::from_der(&csr_der).expect("csr"))
+        );
+
+        let rsa_key = private_key(test_files::RSA_2048_PK_1);
+        let ec_key = private_key(test_files::EC_P256_PK_1);
+        for (der, expected) in [
+            (
+                rsa_key.to_pkcs8().expect("pkcs8"),
+                LoadedObject::PrivateKey(rsa_key.clone()),
+            ),
+            (
+                rsa_key.to_rsa_der().expect("pkcs1"),
+                LoadedObject::PrivateKey(rsa_key.clone()),
+            ),
+            (
+                ec_key.to_ec_der().expect("sec1"),
+                LoadedObject::PrivateKey(ec_key.clone()),
+            ),
+            (
+                rsa_key.to_public_key().to_der().expect("spki"),
+                LoadedObject::PublicKey(rsa_key.to_public_key()),
+            ),
+            (
+                rsa_key.to_public_key().to_rsa_der().expect("pkcs1"),
+                LoadedObject::PublicKey(rsa_key.to_public_key()),
+            ),
+        ] {
+            assert_eq!(LoadedObject::from_bytes(&der).expect("load der"), expected);
+        }
+    }
+
+    #[test]
+    fn load_base64_der() {
+        let cert_der = pem_data(test_files::ROOT_CA);
+        let base64_der = base64::encode(&cert_der);
+        let loaded = LoadedObject::from_bytes(&base64_der).expect("load base64 certificate");
+        assert_eq!(
+            loaded,
+            LoadedObject::Cert(Cert::from_der(&cert_der).expect("certificate"))
+        );
+    }
+
+    #[test]
+    fn detect_encoding() {
+        let cert_der = pem_data(test_files::ROOT_CA);
+        assert_eq!(Encoding::detect(test_files::ROOT_CA), Encoding::Pem);
+        assert_eq!(Encoding::detect(&cert_der), Encoding::Der);
+        assert_eq!(Encoding::detect(&base64::encode(&cert_der)), Encoding::Base64Der);
+    }
+
+    #[test]
+    fn load_errors() {
+        let err = LoadedObject::from_bytes("garbage !").expect_err("error");
+        assert_eq!(
+            err.to_string(),
+            "unknown structure (neither PEM, DER nor base64 encoded DER)"
+        );
+
+        let err = LoadedObject::from_bytes("-----BEGIN PKCS7-----MAA=-----END PKCS7-----").expect_err("error");
+        assert_eq!(err.to_string(), "unsupported PEM label: PKCS7");
+
+        let encrypted = private_key(test_files::EC_P256_PK_1)
+            .to_encrypted_pkcs8(b"picky", &Pkcs8EncryptionParams::default())
+            .expect("encrypted pkcs8");
+        let err = LoadedObject::from_bytes(&encrypted).expect_err("error");
+        assert_eq!(
+            err.to_string(),
+            "key error: private key encryption error: private key is encrypted, \
+             use `PrivateKey::from_encrypted_pkcs8` instead"
+        );
+
+        // SEQUENCE { INTEGER 0, INTEGER 1, INTEGER 3 } is not an RSAPrivateKey
+        let three_integers = [0x30, 0x09, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01, 0x02, 0x01, 0x03];
+        let err = LoadedObject::from_der(&three_integers[..]).expect_err("error");
+        assert_eq!(
+            err.to_string(),
+            "unknown structure (neither PEM, DER nor base64 encoded DER)"
+        );
+
+        // trailing data after the outer SEQUENCE
+        let mut trailing = three_integers.to_vec();
+        trailing.push(0x00);
+        let err = LoadedObject::from_der(&trailing).expect_err("error");
+        assert_eq!(
+            err.to_string(),
+            "unknown structure (neither PEM, DER nor base64 encoded DER)"
+        );
+    }
+}
@@ -42,20 +42,49 @@ macro_rules! define_oid {
 define_oid! {
     // ANSI-X962
     EC_PUBLIC_KEY => ec_public_key => "1.2.840.10045.2.1",
+    SECP256R1 => secp256r1 => "1.2.840.10045.3.1.7",
     ECDSA_WITH_SHA256 => ecdsa_with_sha256 => "1.2.840.10045.4.3.2",
     ECDSA_WITH_SHA384 => ecdsa_with_sha384 => "1.2.840.10045.4.3.3",
+    ECDSA_WITH_SHA512 => ecdsa_with_sha512 => "1.2.840.10045.4.3.4",
 
     // RSADSI
     RSA_ENCRYPTION => rsa_encryption => "1.2.840.113549.1.1.1",
     SHA1_WITH_RSA_ENCRYPTION => sha1_with_rsa_encryption => "1.2.840.113549.1.1.5",
+    ID_MGF1 => id_mgf1 => "1.2.840.113549.1.1.8",
+    RSASSA_PSS => rsassa_pss => "1.2.840.113549.1.1.10",
     SHA256_WITH_RSA_ENCRYPTION => sha256_with_rsa_encryption => "1.2.840.113549.1.1.11",
     SHA384_WITH_RSA_ENCRYPTION => sha384_with_rsa_encryption => "1.2.840.113549.1.1.12",
     SHA512_WITH_RSA_ENCRYPTION => sha512_with_rsa_encryption => "1.2.840.113549.1.1.13",
     SHA224_WITH_RSA_ENCRYPTION => sha224_with_rsa_encryption => "1.2.840.113549.1.1.14",
+    PBKDF2 => pbkdf2 => "1.2.840.113549.1.5.12",
+    PBES2 => pbes2 => "1.2.840.113549.1.5.13",
+    PKCS7_DATA => pkcs7_data => "1.2.840.113549.1.7.1",
+    PKCS7_ENCRYPTED_DATA => pkcs7_encrypted_data => "1.2.840.113549.1.7.6",
     EMAIL_ADDRESS => email_address => "1.2.840.113549.1.9.1", // deprecated
+    LOCAL_KEY_ID => local_key_id => "1.2.840.113549.1.9.21",
+    X509_CERTIFICATE => x509_certificate => "1.2.840.113549.1.9.22.1",
+    PBE_WITH_SHA_AND_3_KEY_TRIPLE_DES_CBC => pbe_with_sha_and_3_key_triple_des_cbc => "1.2.840.113549.1.12.1.3",
+    PBE_WITH_SHA_AND_128_BIT_RC2_CBC => pbe_with_sha_and_128_bit_rc2_cbc => "1.2.840.113549.1.12.1.5",
+    PBE_WITH_SHA_AND_40_BIT_RC2_CBC => pbe_with_sha_and_40_bit_rc2_cbc => "1.2.840.113549.1.12.1.6",
+    KEY_BAG => key_bag => "1.2.840.113549.1.12.10.1.1",
+    PKCS8_SHROUDED_KEY_BAG => pkcs8_shrouded_key_bag => "1.2.840.113549.1.12.10.1.2",
+    CERT_BAG => cert_bag => "1.2.840.113549.1.12.10.1.3",
+    HMAC_WITH_SHA1 => hmac_with_sha1 => "1.2.840.113549.2.7",
+    HMAC_WITH_SHA256 => hmac_with_sha256 => "1.2.840.113549.2.9",
+
+    // OIW
+    SHA1 => sha1 => "1.3.14.3.2.26",
+
+    // RFC 8410
+    ED25519 => ed25519 => "1.3.101.112",
+    ED448 => ed448 => "1.3.101.113",
 
     // Certicom Object Identifiers
     SECP384R1 => secp384r1 => "1.3.132.0.34",
+    SECP521R1 => secp521r1 => "1.3.132.0.35",
+
+    // PKIX private extensions
+    AUTHORITY_INFO_ACCESS => authority_info_access => "1.3.6.1.5.5.7.1.1",
 
     // Extended key purpose OIDS
     KP_SERVER_AUTH => kp_server_auth => "1.3.6.1.5.5.7.3.1",
@@ -69,6 +98,16 @@ define_oid! {
     KP_OCSP_SIGNING => kp_ocsp_signing => "1.3.6.1.5.5.7.3.9",
     KP_ANY_EXTENDED_KEY_USAGE => kp_any_extended_key_usage => "2.5.29.37.0",
 
+    // policy qualifiers
+    QT_CPS => qt_cps => "1.3.6.1.5.5.7.2.1",
+    QT_UNOTICE => qt_unotice => "1.3.6.1.5.5.7.2.2",
+
+    // access descriptors and OCSP
+    AD_OCSP => ad_ocsp => "1.3.6.1.5.5.7.48.1",
+    OCSP_BASIC => ocsp_basic => "1.3.6.1.5.5.7.48.1.1",
+    OCSP_NONCE => ocsp_nonce => "1.3.6.1.5.5.7.48.1.2",
+    AD_CA_ISSUERS => ad_ca_issuers => "1.3.6.1.5.5.7.48.2",
+
     // attribute types
     AT_COMMON_NAME => at_common_name => "2.5.4.3",
     AT_SURNAME => at_surname => "2.5.4.4",
@@ -86,6 +125,26 @@ define_oid! {
     SUBJECT_ALTERNATIVE_NAME => subject_alternative_name => "2.5.29.17",
     ISSUER_ALTERNATIVE_NAME => issuer_alternative_name => "2.5.29.18",
     BASIC_CONSTRAINTS => basic_constraints => "2.5.29.19",
+    CRL_NUMBER => crl_number => "2.5.29.20",
+    REASON_CODE => reason_code => "2.5.29.21",
+    NAME_CONSTRAINTS => name_constraints => "2.5.29.30",
+    CRL_DISTRIBUTION_POINTS => crl_distribution_points => "2.5.29.31",
+    CERTIFICATE_POLICIES => certificate_policies => "2.5.29.32",
+    ANY_POLICY => any_policy => "2.5.29.32.0",
+    POLICY_MAPPINGS => policy_mappings => "2.5.29.33",
     AUTHORITY_KEY_IDENTIFIER => authority_key_identifier => "2.5.29.35",
+    POLICY_CONSTRAINTS => policy_constraints => "2.5.29.36",
     EXTENDED_KEY_USAGE => extended_key_usage => "2.5.29.37",
+    FRESHEST_CRL => freshest_crl => "2.5.29.46",
+    INHIBIT_ANY_POLICY => inhibit_any_policy => "2.5.29.54",
+
+    // NIST encryption algorithms
+    AES128_CBC => aes128_cbc => "2.16.840.1.101.3.4.1.2",
+    AES256_CBC => aes256_cbc => "2.16.840.1.101.3.4.1.42",
+
+    // NIST hash algorithms
+    SHA256 => sha256 => "2.16.840.1.101.3.4.2.1",
+    SHA384 => sha384 => "2.16.840.1.101.3.4.2.2",
+    SHA512 => sha512 => "2.16.840.1.101.3.4.2.3",
+    SHA224 => sha224 => "2.16.840.1.101.3.4.2.4",
 }
@@ -0,0 +1,556 @@
+//! PKCS#12 (PFX) bundles holding a private key and its certificate chain.
+//!
+//! Only the password privacy and integrity modes are supported. Keys and certificates are encrypted
+//! using PBES2 (see `PrivateKey::to_encrypted_pkcs8`). Bundles produced by older tools using the legacy
+//! PKCS#12 PBE algorithms (`pbeWithSHAAnd3-KeyTripleDES-CBC`, `pbeWithSHAAnd128BitRC2-CBC` and
+//! `pbeWithSHAAnd40BitRC2-CBC`) can be read but are never produced.
+
+use crate::{
+    algorithm_identifier::HashAlgorithm,
+    key::{self, KeyError, Pkcs8EncryptionParams, PrivateKey},
+    oids,
+    private::pkcs12::{
+        AuthenticatedSafe, CertBag, DigestInfo, EncryptedData, MacData, Pfx as PfxAsn1, Pkcs12EncryptionAlgorithm,
+        Pkcs12PbeAlgorithmIdentifier, SafeBag, SafeBagValue, SafeContents, SafeContentsInfo,
+    },
+    x509::certificate::{Cert, CertError},
+    AlgorithmIdentifier,
+};
+use picky_asn1::wrapper::{Asn1SequenceOf, OctetStringAsn1};
+use picky_asn1_der::Asn1DerError;
+use sha1::Sha1;
+use sha2::{
+    digest::{core_api::BlockSizeUser, Digest},
+    Sha224, Sha256, Sha384, Sha512,
+};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum Pkcs12Error {
+    /// asn1 serialization error
+    #[snafu(display("(asn1) couldn't serialize {}: {}", element, source))]
+    Asn1Serialization {
+        element: &'static str,
+        source: Asn1DerError,
+    },
+
+    /// asn1 deserialization error
+    #[snafu(display("(asn1) couldn't deserialize {}: {}", element, source))]
+    Asn1Deserialization {
+        element: &'static str,
+        source: Asn1DerError,
+    },
+
+    /// key error
+    #[snafu(display("key error: {}", source))]
+    Key { source: KeyError },
+
+    /// certificate error
+    #[snafu(display("certificate error: {}", source))]
+    Certificate { source: CertError },
+
+    /// MAC is missing
+    #[snafu(display("MAC is missing"))]
+    MissingMac,
+
+    /// MAC verification failed
+    #[snafu(display("MAC verification failed (wrong password or corrupted data)"))]
+    InvalidMac,
+
+    /// unsupported algorithm
+    #[snafu(display("unsupported algorithm: {}", algorithm))]
+    UnsupportedAlgorithm { algorithm: String },
+
+    /// iteration count is too high
+    #[snafu(display("iteration count {} exceeds the maximum of {}", iterations, maximum))]
+    TooManyIterations { iterations: u32, maximum: u32 },
+
+    /// no private key found in the bundle
+    #[snafu(display("no private key found"))]
+    MissingPrivateKey,
+
+    /// more than one private key found in the bundle
+    #[snafu(display("more than one private key found"))]
+    MultiplePrivateKeys,
+}
+
+impl From<KeyError> for Pkcs12Error {
+    fn from(e: KeyError) -> Self {
+        Self::Key { source: e }
+    }
+}
+
+impl From<CertError> for Pkcs12Error {
+    fn from(e: CertError) -> Self {
+        Self::Certificate { source: e }
+    }
+}
+
+const MAC_SALT_LENGTH: usize = 16;
+
+// diversifiers used by the PKCS#12 KDF to derive encryption keys, IVs and MAC keys
+const ENCRYPTION_KEY_ID: u8 = 1;
+const IV_ID: u8 = 2;
+const MAC_KEY_ID: u8 = 3;
+
+// block size of both DES and RC2
+const PBE_IV_LENGTH: usize = 8;
+
+// Iteration counts are read from the bundle before its MAC can be verified: without an upper bound
+// a crafted file could keep the KDF busy for an arbitrary amount of time.
+const MAX_ITERATIONS: u32 = 1_000_000;
+
+/// A private key along with its certificate chain, as found in a PKCS#12 bundle.
+///
+/// The certificate matching the private key (the leaf) always comes first in the chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pfx {
+    private_key: PrivateKey,
+    chain: Vec<Cert>,
+}
+
+impl Pfx {
+    /// `chain` should start with the certificate matching `private_key`.
+    pub fn new(private_key: PrivateKey, chain: Vec<Cert>) -> Self {
+        Self { private_key, chain }
+    }
+
+    pub fn from_der<T: ?Sized + AsRef<[u8]>>(der: &T, password: &str) -> Result<Self, Pkcs12Error> {
+        let pfx: PfxAsn1 = picky_asn1_der::from_bytes(der.as_ref()).context(Asn1Deserialization { element: "pfx" })?;
+
+        let mac_data = pfx.mac_data.as_ref().ok_or(Pkcs12Error::MissingMac)?;
+        let hash = HashAlgorithm::from_oid(mac_data.mac.digest_algorithm.oid()).ok_or_else(|| {
+            Pkcs12Error::UnsupportedAlgorithm {
+                algorithm: Into::<String>::into(mac_data.mac.digest_algorithm.oid()),
+            }
+        })?;
+        check_iterations(mac_data.iterations)?;
+        let bmp_password = to_bmp_password(password);
+        if !verify_mac(
+            hash,
+            &bmp_password,
+            &mac_data.mac_salt.0,
+            mac_data.iterations,
+            &pfx.auth_safe.0,
+            &mac_data.mac.digest.0,
+        ) {
+            return Err(Pkcs12Error::InvalidMac);
+        }
+
+        let auth_safe: AuthenticatedSafe =
+            picky_asn1_der::from_bytes(&pfx.auth_safe.0).context(Asn1Deserialization {
+                element: "authenticated safe",
+            })?;
+
+        let mut bags = Vec::new();
+        for safe_contents_info in auth_safe.0 {
+            match safe_contents_info {
+                SafeContentsInfo::Data(safe_contents) => bags.extend(safe_contents.0),
+                SafeContentsInfo::EncryptedData(encrypted_data) => {
+                    let encrypted_content_info = encrypted_data.encrypted_content_info;
+                    let safe_contents = decrypt(
+                        &encrypted_content_info.content_encryption_algorithm,
+                        password,
+                        &(encrypted_content_info.encrypted_content.0).0,
+                    )?;
+                    let safe_contents: SafeContents =
+                        picky_asn1_der::from_bytes(&safe_contents).context(Asn1Deserialization {
+                            element: "safe contents",
+                        })?;
+                    bags.extend(safe_contents.0);
+                }
+            }
+        }
+
+        let mut private_key = None;
+        let mut chain = Vec::new();
+        for bag in bags {
+            let key = match bag.value {
+                SafeBagValue::Key(key) => {
+                    PrivateKey::from_pkcs8(&picky_asn1_der::to_vec(&key).context(Asn1Serialization {
+                        element: "private key info",
+                    })?)?
+                }
+                SafeBagValue::ShroudedKey(key) => {
+                    let pkcs8 = decrypt(&key.encryption_algorithm, password, &key.encrypted_data.0)?;
+
+                    // padding may be valid by chance when the password is wrong
+                    PrivateKey::from_pkcs8(&pkcs8).map_err(|_| KeyError::Encryption {
+                        context: "wrong password or corrupted data".to_owned(),
+                    })?
+                }
+                SafeBagValue::Cert(cert) => {
+                    if cert.cert_id.0 != oids::x509_certificate() {
+                        return Err(Pkcs12Error::UnsupportedAlgorithm {
+                            algorithm: Into::<String>::into(&cert.cert_id.0),
+                        });
+                    }
+                    chain.push(Cert::from_der(&(cert.cert_value.0).0)?);
+                    continue;
+                }
+                SafeBagValue::Unsupported(_) => continue,
+            };
+
+            if private_key.replace(key).is_some() {
+                return Err(Pkcs12Error::MultiplePrivateKeys);
+            }
+        }
+
+        let private_key = private_key.ok_or(Pkcs12Error::MissingPrivateKey)?;
+
+        let public_key = private_key.to_public_key();
+        if let Some(leaf_idx) = chain.iter().position(|cert| cert.public_key() == &public_key) {
+            let leaf = chain.remove(leaf_idx);
+            chain.insert(0, leaf);
+        }
+
+        Ok(Self { private_key, chain })
+    }
+
+    /// Encrypts the private key and the certificates with PBES2 and protects the bundle with a
+    /// HMAC-SHA256 MAC, as OpenSSL 3 does by default.
+    ///
+    /// The same iteration count is used for the key derivation and the MAC.
+    pub fn to_der(&self, password: &str, params: &Pkcs8EncryptionParams) -> Result<Vec<u8>, Pkcs12Error> {
+        use rand_core::RngCore;
+
+        // localKeyId binds the private key to the leaf certificate
+        let local_key_id = match self.chain.first() {
+            Some(leaf) => Some(OctetStringAsn1::from(Sha1::digest(leaf.to_der()?).to_vec())),
+            None => None,
+        };
+
+        let cert_bags = self
+            .chain
+            .iter()
+            .enumerate()
+            .map(|(idx, cert)| {
+                Ok(SafeBag {
+                    value: SafeBagValue::Cert(CertBag::new_x509(cert.to_der()?)),
+                    local_key_id: if idx == 0 { local_key_id.clone() } else { None },
+                })
+            })
+            .collect::<Result<Vec<_>, Pkcs12Error>>()?;
+        let cert_safe_contents = picky_asn1_der::to_vec(&Asn1SequenceOf(cert_bags)).context(Asn1Serialization {
+            element: "safe contents",
+        })?;
+        let (content_encryption_algorithm, encrypted_content) =
+            key::pbes2_encrypt(password.as_bytes(), params, &cert_safe_contents)?;
+
+        let encrypted_key = self.private_key.to_encrypted_pkcs8(password, params)?;
+        let key_bag = SafeBag {
+            value: SafeBagValue::ShroudedKey(picky_asn1_der::from_bytes(&encrypted_key).context(
+                Asn1Deserialization {
+                    element: "encrypted private key info",
+                },
+            )?),
+            local_key_id,
+        };
+
+        let auth_safe: AuthenticatedSafe = Asn1SequenceOf(vec![
+            SafeContentsInfo::EncryptedData(Box::new(EncryptedData::new(
+                content_encryption_algorithm,
+                encrypted_content,
+            ))),
+            SafeContentsInfo::Data(Asn1SequenceOf(vec![key_bag])),
+        ]);
+        let auth_safe = picky_asn1_der::to_vec(&auth_safe).context(Asn1Serialization {
+            element: "authenticated safe",
+        })?;
+
+        let mut mac_salt = vec![0; MAC_SALT_LENGTH];
+        rand_core::OsRng
+            .try_fill_bytes(&mut mac_salt)
+            .map_err(|e| KeyError::Encryption { context: e.to_string() })?;
+        let hash = HashAlgorithm::Sha256;
+        let mac = compute_mac(
+            hash,
+            &to_bmp_password(password),
+            &mac_salt,
+            params.iterations(),
+            &auth_safe,
+        );
+
+        let pfx = PfxAsn1 {
+            version: 3,
+            auth_safe: auth_safe.into(),
+            mac_data: Some(MacData {
+                mac: DigestInfo {
+                    digest_algorithm: AlgorithmIdentifier::new_sha(hash),
+                    digest: mac.into(),
+                },
+                mac_salt: mac_salt.into(),
+                iterations: params.iterations(),
+            }),
+        };
+        picky_asn1_der::to_vec(&pfx).context(Asn1Serialization { element: "pfx" })
+    }
+
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.private_key
+    }
+
+    pub fn chain(&self) -> &[Cert] {
+        &self.chain
+    }
+
+    pub fn into_parts(self) -> (PrivateKey, Vec<Cert>) {
+        (self.private_key, self.chain)
+    }
+}
+
+fn check_iterations(iterations: u32) -> Result<u32, Pkcs12Error> {
+    if iterations > MAX_ITERATIONS {
+        Err(Pkcs12Error::TooManyIterations {
+            iterations,
+            maximum: MAX_ITERATIONS,
+        })
+    } else {
+        Ok(iterations)
+    }
+}
+
+fn decrypt(algorithm: &Pkcs12EncryptionAlgorithm, password: &str, data: &[u8]) -> Result<Vec<u8>, Pkcs12Error> {
+    match algorithm {
+        Pkcs12EncryptionAlgorithm::Pbes2(algorithm) => Ok(key::pbes2_decrypt(algorithm, password.as_bytes(), data)?),
+        Pkcs12EncryptionAlgorithm::Pbe(algorithm) => pbe_decrypt(algorithm, &to_bmp_password(password), data),
+    }
+}
+
+// Legacy PKCS#12 password based encryption: key and IV are both derived with the PKCS#12 KDF
+// using SHA-1.
+// https://tools.ietf.org/html/rfc7292#appendix-C
+fn pbe_decrypt(algorithm: &Pkcs12PbeAlgorithmIdentifier, password: &[u8], data: &[u8]) -> Result<Vec<u8>, Pkcs12Error> {
+    use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, InnerIvInit, KeyIvInit};
+
+    let salt = &algorithm.parameters.salt.0;
+    let iterations = check_iterations(algorithm.parameters.iterations)?;
+    let derive_key_iv = |key_length: usize| {
+        (
+            pkcs12_kdf::<Sha1>(ENCRYPTION_KEY_ID, password, salt, iterations, key_length),
+            pkcs12_kdf::<Sha1>(IV_ID, password, salt, iterations, PBE_IV_LENGTH),
+        )
+    };
+    let rc2_decrypt = |key_length: usize| {
+        let (key, iv) = derive_key_iv(key_length);
+        let cipher = rc2::Rc2::new_with_eff_key_len(&key, key_length * 8);
+        cbc::Decryptor::<rc2::Rc2>::inner_iv_slice_init(cipher, &iv)
+            .map_err(|e| KeyError::Encryption { context: e.to_string() })
+            .map(|decryptor| decryptor.decrypt_padded_vec_mut::<Pkcs7>(data))
+    };
+
+    let decrypted = match Into::<String>::into(&algorithm.algorithm.0).as_str() {
+        oids::PBE_WITH_SHA_AND_3_KEY_TRIPLE_DES_CBC => {
+            let (key, iv) = derive_key_iv(24);
+            cbc::Decryptor::<des::TdesEde3>::new_from_slices(&key, &iv)
+                .map_err(|e| KeyError::Encryption { context: e.to_string() })?
+                .decrypt_padded_vec_mut::<Pkcs7>(data)
+        }
+        oids::PBE_WITH_SHA_AND_128_BIT_RC2_CBC => rc2_decrypt(16)?,
+        oids::PBE_WITH_SHA_AND_40_BIT_RC2_CBC => rc2_decrypt(5)?,
+        unsupported => {
+            return Err(Pkcs12Error::UnsupportedAlgorithm {
+                algorithm: unsupported.to_owned(),
+            })
+        }
+    };
+
+    decrypted.map_err(|_| {
+        KeyError::Encryption {
+            context: "wrong password or corrupted data".to_owned(),
+        }
+        .into()
+    })
+}
+
+// Passwords are BMPStrings (big-endian UTF-16 with a null terminator) when used with the PKCS#12 KDF:
+// https://tools.ietf.org/html/rfc7292#appendix-B.1
+fn to_bmp_password(password: &str) -> Vec<u8> {
+    password
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .flat_map(|c| c.to_be_bytes().to_vec())
+        .collect()
+}
+
+// PKCS#12 key derivation function:
+// https://tools.ietf.org/html/rfc7292#appendix-B.2
+fn pkcs12_kdf<D: Digest + BlockSizeUser>(
+    id: u8,
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    key_length: usize,
+) -> Vec<u8> {
+    let u = <D as Digest>::output_size();
+    let v = D::block_size();
+
+    let fill = |input: &[u8]| -> Vec<u8> {
+        let length = input.len() + (v - input.len() % v) % v;
+        input.iter().cycle().take(length).copied().collect()
+    };
+
+    let d = vec![id; v];
+    let mut i = fill(salt);
+    i.extend(fill(password));
+
+    let mut key = Vec::with_capacity(key_length + u);
+    while key.len() < key_length {
+        let mut a = D::new().chain_update(&d).chain_update(&i).finalize();
+        for _ in 1..iterations {
+            a = D::digest(&a);
+        }
+        key.extend_from_slice(&a);
+
+        // I_j = (I_j + B + 1) mod 2^(v * 8)
+        let b: Vec<u8> = a.iter().cycle().take(v).copied().collect();
+        for block in i.chunks_mut(v) {
+            let mut carry = 1u16;
+            for (x, y) in block.iter_mut().zip(&b).rev() {
+                let sum = u16::from(*x) + u16::from(*y) + carry;
+                *x = sum as u8;
+                carry = sum >> 8;
+            }
+        }
+    }
+
+    key.truncate(key_length);
+    key
+}
+
+macro_rules! hmac {
+    ($hash:expr, $password:expr, $salt:expr, $iterations:expr, $data:expr, |$mac:ident| $then:expr) => {{
+        use hmac::{Hmac, Mac};
+
+        macro_rules! with_digest {
+            ($digest:ty) => {{
+                let key = pkcs12_kdf::<$digest>(
+                    MAC_KEY_ID,
+                    $password,
+                    $salt,
+                    $iterations,
+                    <$digest as Digest>::output_size(),
+                );
+                let mut $mac = Hmac::<$digest>::new_from_slice(&key).expect("HMAC accepts keys of any size");
+                $mac.update($data);
+                $then
+            }};
+        }
+
+        match $hash {
+            HashAlgorithm::Sha1 => with_digest!(Sha1),
+            HashAlgorithm::Sha224 => with_digest!(Sha224),
+            HashAlgorithm::Sha256 => with_digest!(Sha256),
+            HashAlgorithm::Sha384 => with_digest!(Sha384),
+            HashAlgorithm::Sha512 => with_digest!(Sha512),
+        }
+    }};
+}
+
+fn compute_mac(hash: HashAlgorithm, password: &[u8], salt: &[u8], iterations: u32, data: &[u8]) -> Vec<u8> {
+    hmac!(hash, password, salt, iterations, data, |mac| mac
+        .finalize()
+        .into_bytes()
+        .to_vec())
+}
+
+fn verify_mac(
+    hash: HashAlgorithm,
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    data: &[u8],
+    expected: &[u8],
+) -> bool {
+    hmac!(hash, password, salt, iterations, data, |mac| mac
+        .verify_slice(expected)
+        .is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pem::Pem;
+
+    fn ec_chain() -> (PrivateKey, Vec<Cert>) {
+        let private_key =
+            PrivateKey::from_pem(&crate::test_files::EC_P256_PK_1.parse::<Pem>().expect("pem")).expect("private key");
+        let chain = [
+            crate::test_files::EC_LEAF,
+            crate::test_files::EC_INTERMEDIATE_CA,
+            crate::test_files::EC_ROOT_CA,
+        ]
+        .iter()
+        .map(|pem| Cert::from_pem(&pem.parse::<Pem>().expect("pem")).expect("cert"))
+        .collect();
+        (private_key, chain)
+    }
+
+    #[test]
+    fn pfx_from_openssl() {
+        let (private_key, chain) = ec_chain();
+
+        let pfx = Pfx::from_der(crate::test_files::EC_LEAF_PFX, "picky").expect("pfx");
+        assert_eq!(pfx.private_key(), &private_key);
+        assert_eq!(pfx.chain()[0], chain[0]);
+        assert_eq!(pfx.chain().len(), 3);
+        assert!(chain.iter().all(|cert| pfx.chain().contains(cert)));
+
+        let err = Pfx::from_der(crate::test_files::EC_LEAF_PFX, "wrong password")
+            .err()
+            .expect("pkcs12 error");
+        assert_eq!(
+            err.to_string(),
+            "MAC verification failed (wrong password or corrupted data)"
+        );
+    }
+
+    #[test]
+    fn pfx_legacy_pbe_from_openssl() {
+        // generated with `openssl pkcs12 -export -legacy`: certificates are encrypted with
+        // pbeWithSHAAnd40BitRC2-CBC and the key with pbeWithSHAAnd3-KeyTripleDES-CBC
+        let (private_key, chain) = ec_chain();
+
+        let pfx = Pfx::from_der(crate::test_files::EC_LEAF_LEGACY_PFX, "picky").expect("pfx");
+        assert_eq!(pfx.private_key(), &private_key);
+        assert_eq!(pfx.chain()[0], chain[0]);
+        assert_eq!(pfx.chain().len(), 3);
+        assert!(chain.iter().all(|cert| pfx.chain().contains(cert)));
+    }
+
+    #[test]
+    fn pfx_too_many_iterations() {
+        let mut pfx: PfxAsn1 = picky_asn1_der::from_bytes(crate::test_files::EC_LEAF_PFX).expect("pfx asn1");
+        pfx.mac_data.as_mut().expect("mac data").iterations = MAX_ITERATIONS + 1;
+        let der = picky_asn1_der::to_vec(&pfx).expect("pfx der");
+
+        let err = Pfx::from_der(&der, "picky").err().expect("pkcs12 error");
+        assert_eq!(
+            err.to_string(),
+            "iteration count 1000001 exceeds the maximum of 1000000"
+        );
+    }
+
+    #[test]
+    fn pfx_roundtrip() {
+        let (private_key, chain) = ec_chain();
+        let pfx = Pfx::new(private_key, chain);
+
+        let der = pfx
+            .to_der("picky", &Pkcs8EncryptionParams::new(key::Pkcs8Cipher::Aes128Cbc, 1000))
+            .expect("pfx der");
+        assert_eq!(Pfx::from_der(&der, "picky").expect("pfx"), pfx);
+    }
+
+    #[test]
+    fn pkcs12_kdf_sha1() {
+        // well-known test vector (password "smeg", encryption key ID)
+        let password = to_bmp_password("smeg");
+        let salt = hex::decode("0A58CF64530D823F").unwrap();
+        let key = pkcs12_kdf::<Sha1>(1, &password, &salt, 1, 24);
+        assert_eq!(
+            key,
+            hex::decode("8AAAE6297B6CB04642AB5B077851284EB7128F1A2A7FBCA3").unwrap()
+        );
+    }
+}
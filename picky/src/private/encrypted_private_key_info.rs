@@ -0,0 +1,362 @@
+use crate::oids;
+use oid::ObjectIdentifier;
+use picky_asn1::{
+    tag::{Tag, TagPeeker},
+    wrapper::{ObjectIdentifierAsn1, OctetStringAsn1},
+};
+use serde::{de, ser, Deserialize, Serialize};
+use std::fmt;
+
+// Encrypted private key information:
+// https://tools.ietf.org/html/rfc5958#section-3
+//
+// EncryptedPrivateKeyInfo ::= SEQUENCE {
+//      encryptionAlgorithm  EncryptionAlgorithmIdentifier,
+//      encryptedData        EncryptedData }
+//
+// EncryptionAlgorithmIdentifier ::= AlgorithmIdentifier
+//                                    { CONTENT-ENCRYPTION,
+//                                      { KeyEncryptionAlgorithms } }
+//
+// EncryptedData ::= OCTET STRING
+//
+// Only the PBES2 encryption scheme (PKCS #5 v2.1) is supported:
+// https://tools.ietf.org/html/rfc8018#appendix-A.4
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct EncryptedPrivateKeyInfo {
+    pub encryption_algorithm: Pbes2AlgorithmIdentifier,
+    pub encrypted_data: OctetStringAsn1,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub(crate) struct Pbes2AlgorithmIdentifier {
+    pub algorithm: ObjectIdentifierAsn1,
+    pub parameters: Pbes2Params,
+}
+
+impl Pbes2AlgorithmIdentifier {
+    /// PBES2 with PBKDF2-HMAC-SHA256 key derivation
+    pub fn new(salt: Vec<u8>, iteration_count: u32, cipher: ObjectIdentifier, iv: Vec<u8>) -> Self {
+        Self {
+            algorithm: oids::pbes2().into(),
+            parameters: Pbes2Params {
+                key_derivation_func: Pbkdf2AlgorithmIdentifier {
+                    algorithm: oids::pbkdf2().into(),
+                    parameters: Pbkdf2Params {
+                        salt: salt.into(),
+                        iteration_count,
+                        key_length: None,
+                        prf: PrfAlgorithmIdentifier {
+                            algorithm: oids::hmac_with_sha256().into(),
+                        },
+                    },
+                },
+                encryption_scheme: EncryptionScheme {
+                    algorithm: cipher.into(),
+                    iv: iv.into(),
+                },
+            },
+        }
+    }
+
+    pub fn pbkdf2_params(&self) -> &Pbkdf2Params {
+        &self.parameters.key_derivation_func.parameters
+    }
+
+    pub fn encryption_scheme(&self) -> &EncryptionScheme {
+        &self.parameters.encryption_scheme
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Pbes2AlgorithmIdentifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Pbes2AlgorithmIdentifier;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded PBES2 algorithm identifier")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let algorithm: ObjectIdentifierAsn1 = seq_next_element!(seq, Pbes2AlgorithmIdentifier, "algorithm oid");
+                if algorithm.0 != oids::pbes2() {
+                    return Err(serde_invalid_value!(
+                        Pbes2AlgorithmIdentifier,
+                        "unsupported encryption algorithm (only PBES2 is supported)",
+                        "PBES2"
+                    ));
+                }
+
+                Ok(Pbes2AlgorithmIdentifier {
+                    algorithm,
+                    parameters: seq_next_element!(seq, Pbes2AlgorithmIdentifier, "PBES2 parameters"),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// PBES2-params ::= SEQUENCE {
+//      keyDerivationFunc AlgorithmIdentifier {{PBES2-KDFs}},
+//      encryptionScheme AlgorithmIdentifier {{PBES2-Encs}} }
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct Pbes2Params {
+    pub key_derivation_func: Pbkdf2AlgorithmIdentifier,
+    pub encryption_scheme: EncryptionScheme,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub(crate) struct Pbkdf2AlgorithmIdentifier {
+    pub algorithm: ObjectIdentifierAsn1,
+    pub parameters: Pbkdf2Params,
+}
+
+impl<'de> de::Deserialize<'de> for Pbkdf2AlgorithmIdentifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Pbkdf2AlgorithmIdentifier;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded PBKDF2 algorithm identifier")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let algorithm: ObjectIdentifierAsn1 =
+                    seq_next_element!(seq, Pbkdf2AlgorithmIdentifier, "algorithm oid");
+                if algorithm.0 != oids::pbkdf2() {
+                    return Err(serde_invalid_value!(
+                        Pbkdf2AlgorithmIdentifier,
+                        "unsupported key derivation function (only PBKDF2 is supported)",
+                        "PBKDF2"
+                    ));
+                }
+
+                Ok(Pbkdf2AlgorithmIdentifier {
+                    algorithm,
+                    parameters: seq_next_element!(seq, Pbkdf2AlgorithmIdentifier, "PBKDF2 parameters"),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// PBKDF2-params ::= SEQUENCE {
+//     salt CHOICE {
+//         specified OCTET STRING,
+//         otherSource AlgorithmIdentifier {{PBKDF2-SaltSources}}
+//     },
+//     iterationCount INTEGER (1..MAX),
+//     keyLength INTEGER (1..MAX) OPTIONAL,
+//     prf AlgorithmIdentifier {{PBKDF2-PRFs}} DEFAULT algid-hmacWithSHA1 }
+//
+// Only the `specified` salt choice is defined.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Pbkdf2Params {
+    pub salt: OctetStringAsn1,
+    pub iteration_count: u32,
+    pub key_length: Option<u16>,
+    pub prf: PrfAlgorithmIdentifier,
+}
+
+impl ser::Serialize for Pbkdf2Params {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(4))?;
+        seq.serialize_element(&self.salt)?;
+        seq.serialize_element(&self.iteration_count)?;
+        if let Some(key_length) = self.key_length {
+            seq.serialize_element(&key_length)?;
+        }
+        if self.prf.algorithm.0 != oids::hmac_with_sha1() {
+            seq.serialize_element(&self.prf)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Pbkdf2Params {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Pbkdf2Params;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded PBKDF2-params")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let salt = seq_next_element!(seq, Pbkdf2Params, "salt");
+                let iteration_count = seq_next_element!(seq, Pbkdf2Params, "iteration count");
+
+                let mut key_length = None;
+                let mut prf = PrfAlgorithmIdentifier {
+                    algorithm: oids::hmac_with_sha1().into(),
+                };
+
+                if let Some(TagPeeker { next_tag: Tag::INTEGER }) = seq.next_element()? {
+                    key_length = Some(seq_next_element!(seq, Pbkdf2Params, "key length"));
+                }
+
+                if let Some(TagPeeker {
+                    next_tag: Tag::SEQUENCE,
+                }) = seq.next_element()?
+                {
+                    prf = seq_next_element!(seq, Pbkdf2Params, "pseudorandom function");
+                }
+
+                Ok(Pbkdf2Params {
+                    salt,
+                    iteration_count,
+                    key_length,
+                    prf,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// PBKDF2-PRFs ALGORITHM-IDENTIFIER ::= {
+//      {NULL IDENTIFIED BY id-hmacWithSHA1},
+//      {NULL IDENTIFIED BY id-hmacWithSHA224},
+//      ... }
+//
+// The NULL parameters are always written but may be absent when parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PrfAlgorithmIdentifier {
+    pub algorithm: ObjectIdentifierAsn1,
+}
+
+impl ser::Serialize for PrfAlgorithmIdentifier {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(2))?;
+        seq.serialize_element(&self.algorithm)?;
+        seq.serialize_element(&())?;
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for PrfAlgorithmIdentifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = PrfAlgorithmIdentifier;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded PBKDF2 pseudorandom function")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let algorithm = seq_next_element!(seq, PrfAlgorithmIdentifier, "algorithm oid");
+                let _: Option<()> = seq.next_element()?;
+                Ok(PrfAlgorithmIdentifier { algorithm })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// PBES2-Encs ALGORITHM-IDENTIFIER ::= {
+//      ...
+//      {OCTET STRING (SIZE(16)) IDENTIFIED BY aes128-CBC-PAD} |
+//      {OCTET STRING (SIZE(16)) IDENTIFIED BY aes256-CBC-PAD},
+//      ... }
+//
+// Whether the cipher is supported is checked when decrypting.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct EncryptionScheme {
+    pub algorithm: ObjectIdentifierAsn1,
+    pub iv: OctetStringAsn1,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pbes2_aes256_encrypted_private_key_info() {
+        let encoded = base64::decode(
+            "MIH0MF8GCSqGSIb3DQEFDTBSMDEGCSqGSIb3DQEFDDAkBBD+iW2EO0YvjKAnZWy2\
+             QzmyAgIIADAMBggqhkiG9w0CCQUAMB0GCWCGSAFlAwQBKgQQk/t1bd2WawuQZ3Wy\
+             +C1tLwSBkCGuvw44IeCZtW6XCP3kGoDiQmpWzRFubyiu4bpB4LGPLKuLYQtW8gKj\
+             tccyCBNRMKbP9C8DFqwl9fMH0tu4TstU8IKxGo24LnKhSR6f2bEtuM9h7cZmX2hY\
+             qeG5Wbd9UsBsqMc2zRJMgN9+thlNIAFWXkABGlJkZZsIZ31hZRKIh9/wFftNSt90\
+             n3zxR4RIgw==",
+        )
+        .expect("invalid base64");
+
+        let encrypted_private_key_info = EncryptedPrivateKeyInfo {
+            encryption_algorithm: Pbes2AlgorithmIdentifier::new(
+                encoded[35..51].to_vec(),
+                2048,
+                oids::aes256_cbc(),
+                encoded[84..100].to_vec(),
+            ),
+            encrypted_data: encoded[103..].to_vec().into(),
+        };
+        check_serde!(encrypted_private_key_info: EncryptedPrivateKeyInfo in encoded);
+    }
+
+    #[test]
+    fn pbkdf2_params_with_default_prf() {
+        // salt, iteration count 1000 and key length 16 without the (default) hmacWithSHA1 PRF
+        let encoded = [
+            0x30, 0x11, 0x04, 0x08, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x02, 0x02, 0x03, 0xE8, 0x02, 0x01,
+            0x10,
+        ];
+        let params = Pbkdf2Params {
+            salt: vec![1, 2, 3, 4, 5, 6, 7, 8].into(),
+            iteration_count: 1000,
+            key_length: Some(16),
+            prf: PrfAlgorithmIdentifier {
+                algorithm: oids::hmac_with_sha1().into(),
+            },
+        };
+        check_serde!(params: Pbkdf2Params in encoded);
+    }
+}
@@ -1,3 +1,7 @@
+pub(crate) mod encrypted_private_key_info;
+pub(crate) mod openssh;
+#[cfg(feature = "x509")]
+pub(crate) mod pkcs12;
 pub(crate) mod private_key_info;
 pub(crate) mod subject_public_key_info;
 
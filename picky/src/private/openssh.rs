@@ -0,0 +1,491 @@
+// OpenSSH wire encoding (RFC 4251 section 5) and `openssh-key-v1` private key container.
+// https://github.com/openssh/openssh-portable/blob/master/PROTOCOL.key
+
+use crate::key::KeyError;
+
+pub(crate) const SSH_RSA: &str = "ssh-rsa";
+pub(crate) const ECDSA_SHA2_NISTP256: &str = "ecdsa-sha2-nistp256";
+pub(crate) const ECDSA_SHA2_NISTP384: &str = "ecdsa-sha2-nistp384";
+pub(crate) const ECDSA_SHA2_NISTP521: &str = "ecdsa-sha2-nistp521";
+pub(crate) const SSH_ED25519: &str = "ssh-ed25519";
+
+const AUTH_MAGIC: &[u8] = b"openssh-key-v1\0";
+const KDF_NONE: &str = "none";
+const KDF_BCRYPT: &str = "bcrypt";
+const BCRYPT_SALT_LENGTH: usize = 16;
+const BCRYPT_DEFAULT_ROUNDS: u32 = 16;
+/// Upper bound on the bcrypt-pbkdf rounds read from a key file (each round is costly).
+const BCRYPT_MAX_ROUNDS: u32 = 1024;
+
+pub(crate) fn openssh_error<S: Into<String>>(context: S) -> KeyError {
+    KeyError::OpenSsh {
+        context: context.into(),
+    }
+}
+
+pub(crate) struct SshReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> SshReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], KeyError> {
+        if self.data.len() < len {
+            return Err(openssh_error("unexpected end of data"));
+        }
+
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, KeyError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, KeyError> {
+        let bytes = self.read_bytes(8)?;
+        let mut buf = [0; 8];
+        buf.copy_from_slice(bytes);
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    pub fn read_string(&mut self) -> Result<&'a [u8], KeyError> {
+        let len = self.read_u32()?;
+        self.read_bytes(len as usize)
+    }
+
+    pub fn read_str(&mut self) -> Result<&'a str, KeyError> {
+        std::str::from_utf8(self.read_string()?).map_err(|_| openssh_error("invalid UTF-8 string"))
+    }
+
+    /// Reads a positive multiple precision integer and returns its unsigned big-endian magnitude.
+    pub fn read_mpint(&mut self) -> Result<&'a [u8], KeyError> {
+        let bytes = self.read_string()?;
+        if bytes.first().map(|b| b & 0x80 != 0).unwrap_or(false) {
+            return Err(openssh_error("negative mpint"));
+        }
+
+        let leading_zeros = bytes.iter().take_while(|b| **b == 0).count();
+        Ok(&bytes[leading_zeros..])
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SshWriter {
+    data: Vec<u8>,
+}
+
+impl SshWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_be_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_be_bytes());
+    }
+
+    pub fn write_string(&mut self, bytes: &[u8]) {
+        self.write_u32(bytes.len() as u32);
+        self.write_bytes(bytes);
+    }
+
+    pub fn write_str(&mut self, value: &str) {
+        self.write_string(value.as_bytes());
+    }
+
+    /// Writes an unsigned big-endian integer as a positive multiple precision integer.
+    pub fn write_mpint(&mut self, bytes: &[u8]) {
+        let leading_zeros = bytes.iter().take_while(|b| **b == 0).count();
+        let bytes = &bytes[leading_zeros..];
+        if bytes.first().map(|b| b & 0x80 != 0).unwrap_or(false) {
+            self.write_u32(bytes.len() as u32 + 1);
+            self.data.push(0);
+            self.write_bytes(bytes);
+        } else {
+            self.write_string(bytes);
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum OpensshCipher {
+    None,
+    Aes128Ctr,
+    Aes256Ctr,
+    Aes128Cbc,
+    Aes256Cbc,
+}
+
+impl OpensshCipher {
+    fn from_name(name: &str) -> Result<Self, KeyError> {
+        match name {
+            "none" => Ok(OpensshCipher::None),
+            "aes128-ctr" => Ok(OpensshCipher::Aes128Ctr),
+            "aes256-ctr" => Ok(OpensshCipher::Aes256Ctr),
+            "aes128-cbc" => Ok(OpensshCipher::Aes128Cbc),
+            "aes256-cbc" => Ok(OpensshCipher::Aes256Cbc),
+            unsupported => Err(KeyError::Encryption {
+                context: format!("unsupported cipher: {}", unsupported),
+            }),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            OpensshCipher::None => "none",
+            OpensshCipher::Aes128Ctr => "aes128-ctr",
+            OpensshCipher::Aes256Ctr => "aes256-ctr",
+            OpensshCipher::Aes128Cbc => "aes128-cbc",
+            OpensshCipher::Aes256Cbc => "aes256-cbc",
+        }
+    }
+
+    fn key_length(self) -> usize {
+        match self {
+            OpensshCipher::None => 0,
+            OpensshCipher::Aes128Ctr | OpensshCipher::Aes128Cbc => 16,
+            OpensshCipher::Aes256Ctr | OpensshCipher::Aes256Cbc => 32,
+        }
+    }
+
+    fn iv_length(self) -> usize {
+        match self {
+            OpensshCipher::None => 0,
+            _ => 16,
+        }
+    }
+
+    fn block_size(self) -> usize {
+        match self {
+            OpensshCipher::None => 8,
+            _ => 16,
+        }
+    }
+
+    fn encrypt(self, key_iv: &[u8], data: &mut [u8]) -> Result<(), KeyError> {
+        use aes::cipher::{block_padding::NoPadding, BlockEncryptMut, KeyIvInit, StreamCipher};
+
+        let (key, iv) = key_iv.split_at(self.key_length());
+        let error = |e: aes::cipher::InvalidLength| KeyError::Encryption { context: e.to_string() };
+
+        macro_rules! encrypt_cbc {
+            ($cipher:ty) => {{
+                let data_length = data.len();
+                cbc::Encryptor::<$cipher>::new_from_slices(key, iv)
+                    .map_err(error)?
+                    .encrypt_padded_mut::<NoPadding>(data, data_length)
+                    .map_err(|_| openssh_error("invalid private section length"))?;
+            }};
+        }
+
+        match self {
+            OpensshCipher::None => {}
+            OpensshCipher::Aes128Ctr => ctr::Ctr128BE::<aes::Aes128>::new_from_slices(key, iv)
+                .map_err(error)?
+                .apply_keystream(data),
+            OpensshCipher::Aes256Ctr => ctr::Ctr128BE::<aes::Aes256>::new_from_slices(key, iv)
+                .map_err(error)?
+                .apply_keystream(data),
+            OpensshCipher::Aes128Cbc => encrypt_cbc!(aes::Aes128),
+            OpensshCipher::Aes256Cbc => encrypt_cbc!(aes::Aes256),
+        }
+
+        Ok(())
+    }
+
+    fn decrypt(self, key_iv: &[u8], data: &mut [u8]) -> Result<(), KeyError> {
+        use aes::cipher::{block_padding::NoPadding, BlockDecryptMut, KeyIvInit};
+
+        let (key, iv) = key_iv.split_at(self.key_length());
+        let error = |e: aes::cipher::InvalidLength| KeyError::Encryption { context: e.to_string() };
+
+        macro_rules! decrypt_cbc {
+            ($cipher:ty) => {{
+                cbc::Decryptor::<$cipher>::new_from_slices(key, iv)
+                    .map_err(error)?
+                    .decrypt_padded_mut::<NoPadding>(data)
+                    .map_err(|_| openssh_error("invalid private section length"))?;
+            }};
+        }
+
+        match self {
+            OpensshCipher::Aes128Cbc => decrypt_cbc!(aes::Aes128),
+            OpensshCipher::Aes256Cbc => decrypt_cbc!(aes::Aes256),
+            // CTR mode encryption and decryption are the same operation
+            _ => self.encrypt(key_iv, data)?,
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum OpensshKdf {
+    None,
+    Bcrypt { salt: Vec<u8>, rounds: u32 },
+}
+
+impl OpensshKdf {
+    fn derive(&self, passphrase: &[u8], key_length: usize) -> Result<Vec<u8>, KeyError> {
+        match self {
+            OpensshKdf::None => Err(KeyError::Encryption {
+                context: "missing key derivation function".to_owned(),
+            }),
+            OpensshKdf::Bcrypt { salt, rounds } => {
+                let mut key = vec![0; key_length];
+                bcrypt_pbkdf::bcrypt_pbkdf(passphrase, salt, *rounds, &mut key)
+                    .map_err(|e| KeyError::Encryption { context: e.to_string() })?;
+                Ok(key)
+            }
+        }
+    }
+}
+
+/// `openssh-key-v1` container holding a single key pair.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OpensshKeyV1 {
+    cipher: OpensshCipher,
+    kdf: OpensshKdf,
+    pub public_key: Vec<u8>,
+    private_section: Vec<u8>,
+}
+
+impl OpensshKeyV1 {
+    /// `private_key` is the type specific private key encoding followed by the comment.
+    ///
+    /// When a passphrase is provided, the private section is encrypted with aes256-ctr
+    /// using a bcrypt-pbkdf derived key (16 rounds, as ssh-keygen does).
+    pub fn new(public_key: Vec<u8>, private_key: &[u8], passphrase: Option<&[u8]>) -> Result<Self, KeyError> {
+        use rand_core::RngCore;
+
+        let mut rng = rand_core::OsRng;
+        let (cipher, kdf) = match passphrase {
+            Some(_) => {
+                let mut salt = vec![0; BCRYPT_SALT_LENGTH];
+                rng.try_fill_bytes(&mut salt)
+                    .map_err(|e| KeyError::Encryption { context: e.to_string() })?;
+                (
+                    OpensshCipher::Aes256Ctr,
+                    OpensshKdf::Bcrypt {
+                        salt,
+                        rounds: BCRYPT_DEFAULT_ROUNDS,
+                    },
+                )
+            }
+            None => (OpensshCipher::None, OpensshKdf::None),
+        };
+
+        let check_int = rng.next_u32();
+        let mut private_section = SshWriter::new();
+        private_section.write_u32(check_int);
+        private_section.write_u32(check_int);
+        private_section.write_bytes(private_key);
+        let mut private_section = private_section.into_bytes();
+
+        let block_size = cipher.block_size();
+        let padding_length = (block_size - private_section.len() % block_size) % block_size;
+        private_section.extend((1..=padding_length).map(|i| i as u8));
+
+        if let Some(passphrase) = passphrase {
+            let key_iv = kdf.derive(passphrase, cipher.key_length() + cipher.iv_length())?;
+            cipher.encrypt(&key_iv, &mut private_section)?;
+        }
+
+        Ok(Self {
+            cipher,
+            kdf,
+            public_key,
+            private_section,
+        })
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, KeyError> {
+        if !data.starts_with(AUTH_MAGIC) {
+            return Err(openssh_error("not an openssh-key-v1 private key"));
+        }
+
+        let mut reader = SshReader::new(&data[AUTH_MAGIC.len()..]);
+        let cipher = OpensshCipher::from_name(reader.read_str()?)?;
+        let kdf = match reader.read_str()? {
+            KDF_NONE => {
+                reader.read_string()?;
+                OpensshKdf::None
+            }
+            KDF_BCRYPT => {
+                let mut kdf_options = SshReader::new(reader.read_string()?);
+                let salt = kdf_options.read_string()?.to_vec();
+                let rounds = kdf_options.read_u32()?;
+                if rounds > BCRYPT_MAX_ROUNDS {
+                    return Err(KeyError::Encryption {
+                        context: format!("too many bcrypt rounds: {} (maximum: {})", rounds, BCRYPT_MAX_ROUNDS),
+                    });
+                }
+                OpensshKdf::Bcrypt { salt, rounds }
+            }
+            unsupported => {
+                return Err(KeyError::Encryption {
+                    context: format!("unsupported key derivation function: {}", unsupported),
+                })
+            }
+        };
+
+        if (cipher == OpensshCipher::None) != (kdf == OpensshKdf::None) {
+            return Err(openssh_error("cipher and key derivation function mismatch"));
+        }
+
+        let number_of_keys = reader.read_u32()?;
+        if number_of_keys != 1 {
+            return Err(openssh_error(format!(
+                "only one key per file is supported (found {})",
+                number_of_keys
+            )));
+        }
+
+        let public_key = reader.read_string()?.to_vec();
+        let private_section = reader.read_string()?.to_vec();
+        if private_section.len() % cipher.block_size() != 0 {
+            return Err(openssh_error("invalid private section length"));
+        }
+
+        Ok(Self {
+            cipher,
+            kdf,
+            public_key,
+            private_section,
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = SshWriter::new();
+        writer.write_bytes(AUTH_MAGIC);
+        writer.write_str(self.cipher.name());
+        match &self.kdf {
+            OpensshKdf::None => {
+                writer.write_str(KDF_NONE);
+                writer.write_string(&[]);
+            }
+            OpensshKdf::Bcrypt { salt, rounds } => {
+                writer.write_str(KDF_BCRYPT);
+                let mut kdf_options = SshWriter::new();
+                kdf_options.write_string(salt);
+                kdf_options.write_u32(*rounds);
+                writer.write_string(&kdf_options.into_bytes());
+            }
+        }
+        writer.write_u32(1);
+        writer.write_string(&self.public_key);
+        writer.write_string(&self.private_section);
+        writer.into_bytes()
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher != OpensshCipher::None
+    }
+
+    /// Returns the private section without the check integers, that is the type specific
+    /// private key encoding followed by the comment and the padding.
+    pub fn decrypt(&self, passphrase: Option<&[u8]>) -> Result<Vec<u8>, KeyError> {
+        let mut private_section = self.private_section.clone();
+        if self.is_encrypted() {
+            let passphrase = passphrase.ok_or_else(|| KeyError::Encryption {
+                context: "private key is encrypted, use `from_encrypted_pem` instead".to_owned(),
+            })?;
+            let key_iv = self
+                .kdf
+                .derive(passphrase, self.cipher.key_length() + self.cipher.iv_length())?;
+            self.cipher.decrypt(&key_iv, &mut private_section)?;
+        }
+
+        let mut reader = SshReader::new(&private_section);
+        let check_int_1 = reader.read_u32()?;
+        let check_int_2 = reader.read_u32()?;
+        if check_int_1 != check_int_2 {
+            return Err(KeyError::Encryption {
+                context: "wrong password or corrupted data".to_owned(),
+            });
+        }
+
+        Ok(reader.remaining().to_vec())
+    }
+}
+
+/// Checks the deterministic padding (1, 2, 3, ...) ending the private section.
+pub(crate) fn check_padding(padding: &[u8]) -> Result<(), KeyError> {
+    if padding.iter().enumerate().all(|(i, b)| usize::from(*b) == i + 1) {
+        Ok(())
+    } else {
+        Err(openssh_error("invalid private section padding"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mpint_encoding() {
+        // RFC 4251 section 5 examples
+        for &(value, encoded) in &[
+            (&[][..], &[0x00, 0x00, 0x00, 0x00][..]),
+            (
+                &[0x09, 0xa3, 0x78, 0xf9, 0xb2, 0xe3, 0x32, 0xa7],
+                &[0x00, 0x00, 0x00, 0x08, 0x09, 0xa3, 0x78, 0xf9, 0xb2, 0xe3, 0x32, 0xa7],
+            ),
+            (&[0x80], &[0x00, 0x00, 0x00, 0x02, 0x00, 0x80]),
+        ] {
+            let mut writer = SshWriter::new();
+            writer.write_mpint(value);
+            let bytes = writer.into_bytes();
+            assert_eq!(bytes, encoded);
+
+            let mut reader = SshReader::new(&bytes);
+            assert_eq!(reader.read_mpint().unwrap(), value);
+            assert!(reader.is_empty());
+        }
+
+        let mut reader = SshReader::new(&[0x00, 0x00, 0x00, 0x02, 0xed, 0xcc]);
+        assert_eq!(
+            reader.read_mpint().unwrap_err().to_string(),
+            "OpenSSH key error: negative mpint"
+        );
+    }
+
+    #[test]
+    fn bcrypt_rounds_limit() {
+        let mut container = OpensshKeyV1::new(vec![0x00], b"private key", Some(b"picky")).unwrap();
+        container.kdf = match container.kdf {
+            OpensshKdf::Bcrypt { salt, .. } => OpensshKdf::Bcrypt { salt, rounds: u32::MAX },
+            OpensshKdf::None => unreachable!(),
+        };
+
+        assert_eq!(
+            OpensshKeyV1::decode(&container.encode()).unwrap_err().to_string(),
+            "private key encryption error: too many bcrypt rounds: 4294967295 (maximum: 1024)"
+        );
+    }
+}
@@ -0,0 +1,577 @@
+use crate::{
+    oids,
+    private::{
+        encrypted_private_key_info::{Pbes2AlgorithmIdentifier, Pbes2Params},
+        PrivateKeyInfo,
+    },
+    AlgorithmIdentifier,
+};
+use oid::ObjectIdentifier;
+use picky_asn1::wrapper::{
+    ApplicationTag0, Asn1SequenceOf, Asn1SetOf, ContextTag0, ObjectIdentifierAsn1, OctetStringAsn1,
+    OctetStringAsn1Container,
+};
+use serde::{de, ser, Deserialize, Serialize};
+use std::fmt;
+
+// PKCS #12: Personal Information Exchange Syntax v1.1
+// https://tools.ietf.org/html/rfc7292#section-4
+//
+// PFX ::= SEQUENCE {
+//      version     INTEGER {v3(3)}(v3,...),
+//      authSafe    ContentInfo,
+//      macData     MacData OPTIONAL }
+//
+// Only the password integrity mode is supported: `authSafe` is a `data` content info whose content
+// is the DER encoding of an `AuthenticatedSafe`. The raw content is kept as is since it is the
+// input of the MAC.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Pfx {
+    pub version: u8,
+    pub auth_safe: OctetStringAsn1,
+    pub mac_data: Option<MacData>,
+}
+
+impl ser::Serialize for Pfx {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(3))?;
+        seq.serialize_element(&self.version)?;
+        seq.serialize_element(&DataContentInfo {
+            content_type: oids::pkcs7_data().into(),
+            content: ApplicationTag0(self.auth_safe.clone()),
+        })?;
+        if let Some(mac_data) = &self.mac_data {
+            seq.serialize_element(mac_data)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Pfx {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Pfx;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded PFX (pkcs12)")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let version = seq_next_element!(seq, Pfx, "version");
+                if version != 3 {
+                    return Err(serde_invalid_value!(
+                        Pfx,
+                        "unsupported version (valid version number: 3)",
+                        "a supported PFX"
+                    ));
+                }
+
+                let auth_safe: DataContentInfo = seq_next_element!(seq, Pfx, "authenticated safe");
+                if auth_safe.content_type.0 != oids::pkcs7_data() {
+                    return Err(serde_invalid_value!(
+                        Pfx,
+                        "unsupported integrity mode (only password integrity mode is supported)",
+                        "a data content info"
+                    ));
+                }
+
+                let mac_data = seq.next_element()?;
+
+                Ok(Pfx {
+                    version,
+                    auth_safe: (auth_safe.content.0).0.into(),
+                    mac_data,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// ContentInfo ::= SEQUENCE {
+//      contentType ContentType,
+//      content [0] EXPLICIT ANY DEFINED BY contentType }
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct DataContentInfo {
+    content_type: ObjectIdentifierAsn1,
+    content: ApplicationTag0<OctetStringAsn1>,
+}
+
+// MacData ::= SEQUENCE {
+//      mac         DigestInfo,
+//      macSalt     OCTET STRING,
+//      iterations  INTEGER DEFAULT 1 }
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MacData {
+    pub mac: DigestInfo,
+    pub mac_salt: OctetStringAsn1,
+    pub iterations: u32,
+}
+
+impl ser::Serialize for MacData {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(3))?;
+        seq.serialize_element(&self.mac)?;
+        seq.serialize_element(&self.mac_salt)?;
+        if self.iterations != 1 {
+            seq.serialize_element(&self.iterations)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for MacData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = MacData;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded MacData")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                Ok(MacData {
+                    mac: seq_next_element!(seq, MacData, "mac"),
+                    mac_salt: seq_next_element!(seq, MacData, "mac salt"),
+                    iterations: seq.next_element()?.unwrap_or(1),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// DigestInfo ::= SEQUENCE {
+//      digestAlgorithm DigestAlgorithmIdentifier,
+//      digest Digest }
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct DigestInfo {
+    pub digest_algorithm: AlgorithmIdentifier,
+    pub digest: OctetStringAsn1,
+}
+
+// AuthenticatedSafe ::= SEQUENCE OF ContentInfo
+//      -- Data if unencrypted
+//      -- EncryptedData if password-encrypted
+//      -- EnvelopedData if public key-encrypted
+pub(crate) type AuthenticatedSafe = Asn1SequenceOf<SafeContentsInfo>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SafeContentsInfo {
+    Data(SafeContents),
+    EncryptedData(Box<EncryptedData>),
+}
+
+impl ser::Serialize for SafeContentsInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(2))?;
+        match self {
+            SafeContentsInfo::Data(safe_contents) => {
+                seq.serialize_element(&ObjectIdentifierAsn1::from(oids::pkcs7_data()))?;
+                seq.serialize_element(&ApplicationTag0(OctetStringAsn1Container(safe_contents)))?;
+            }
+            SafeContentsInfo::EncryptedData(encrypted_data) => {
+                seq.serialize_element(&ObjectIdentifierAsn1::from(oids::pkcs7_encrypted_data()))?;
+                seq.serialize_element(&ApplicationTag0(encrypted_data))?;
+            }
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for SafeContentsInfo {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = SafeContentsInfo;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded ContentInfo holding SafeContents")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let content_type: ObjectIdentifierAsn1 = seq_next_element!(seq, SafeContentsInfo, "content type");
+                match Into::<String>::into(&content_type.0).as_str() {
+                    oids::PKCS7_DATA => {
+                        let content: ApplicationTag0<OctetStringAsn1Container<SafeContents>> =
+                            seq_next_element!(seq, SafeContentsInfo, "safe contents");
+                        Ok(SafeContentsInfo::Data((content.0).0))
+                    }
+                    oids::PKCS7_ENCRYPTED_DATA => {
+                        let content: ApplicationTag0<EncryptedData> =
+                            seq_next_element!(seq, SafeContentsInfo, "encrypted safe contents");
+                        Ok(SafeContentsInfo::EncryptedData(Box::new(content.0)))
+                    }
+                    _ => Err(serde_invalid_value!(
+                        SafeContentsInfo,
+                        "unsupported content type",
+                        "data or encrypted data"
+                    )),
+                }
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// EncryptedData ::= SEQUENCE {
+//      version EncryptedDataVersion,
+//      encryptedContentInfo EncryptedContentInfo,
+//      unprotectedAttrs [1] IMPLICIT UnprotectedAttributes OPTIONAL }
+//
+// https://tools.ietf.org/html/rfc5652#section-8
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct EncryptedData {
+    pub version: u8,
+    pub encrypted_content_info: EncryptedContentInfo,
+}
+
+impl EncryptedData {
+    pub fn new(content_encryption_algorithm: Pbes2AlgorithmIdentifier, encrypted_content: Vec<u8>) -> Self {
+        Self {
+            version: 0,
+            encrypted_content_info: EncryptedContentInfo {
+                content_type: oids::pkcs7_data().into(),
+                content_encryption_algorithm: Pkcs12EncryptionAlgorithm::Pbes2(content_encryption_algorithm),
+                encrypted_content: ContextTag0(encrypted_content.into()),
+            },
+        }
+    }
+}
+
+// EncryptedContentInfo ::= SEQUENCE {
+//      contentType ContentType,
+//      contentEncryptionAlgorithm ContentEncryptionAlgorithmIdentifier,
+//      encryptedContent [0] IMPLICIT EncryptedContent OPTIONAL }
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct EncryptedContentInfo {
+    pub content_type: ObjectIdentifierAsn1,
+    pub content_encryption_algorithm: Pkcs12EncryptionAlgorithm,
+    pub encrypted_content: ContextTag0<OctetStringAsn1>,
+}
+
+// Encryption algorithms found in PKCS#12 bundles: either PBES2 (PKCS #5 v2.1) or one of the legacy
+// password based encryption algorithms using the PKCS#12 KDF.
+//
+// https://tools.ietf.org/html/rfc7292#appendix-C
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Pkcs12EncryptionAlgorithm {
+    Pbes2(Pbes2AlgorithmIdentifier),
+    Pbe(Pkcs12PbeAlgorithmIdentifier),
+}
+
+impl ser::Serialize for Pkcs12EncryptionAlgorithm {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            Pkcs12EncryptionAlgorithm::Pbes2(algorithm) => algorithm.serialize(serializer),
+            Pkcs12EncryptionAlgorithm::Pbe(algorithm) => algorithm.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Pkcs12EncryptionAlgorithm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Pkcs12EncryptionAlgorithm;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded PKCS#12 encryption algorithm identifier")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let algorithm: ObjectIdentifierAsn1 =
+                    seq_next_element!(seq, Pkcs12EncryptionAlgorithm, "algorithm oid");
+                match Into::<String>::into(&algorithm.0).as_str() {
+                    oids::PBES2 => {
+                        let parameters: Pbes2Params =
+                            seq_next_element!(seq, Pkcs12EncryptionAlgorithm, "PBES2 parameters");
+                        Ok(Pkcs12EncryptionAlgorithm::Pbes2(Pbes2AlgorithmIdentifier {
+                            algorithm,
+                            parameters,
+                        }))
+                    }
+                    oids::PBE_WITH_SHA_AND_3_KEY_TRIPLE_DES_CBC
+                    | oids::PBE_WITH_SHA_AND_128_BIT_RC2_CBC
+                    | oids::PBE_WITH_SHA_AND_40_BIT_RC2_CBC => {
+                        let parameters: Pkcs12PbeParams =
+                            seq_next_element!(seq, Pkcs12EncryptionAlgorithm, "PKCS#12 PBE parameters");
+                        Ok(Pkcs12EncryptionAlgorithm::Pbe(Pkcs12PbeAlgorithmIdentifier {
+                            algorithm,
+                            parameters,
+                        }))
+                    }
+                    _ => Err(serde_invalid_value!(
+                        Pkcs12EncryptionAlgorithm,
+                        "unsupported encryption algorithm",
+                        "PBES2, pbeWithSHAAnd3-KeyTripleDES-CBC or pbeWithSHAAnd{128,40}BitRC2-CBC"
+                    )),
+                }
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub(crate) struct Pkcs12PbeAlgorithmIdentifier {
+    pub algorithm: ObjectIdentifierAsn1,
+    pub parameters: Pkcs12PbeParams,
+}
+
+// pkcs-12PbeParams ::= SEQUENCE {
+//      salt        OCTET STRING,
+//      iterations  INTEGER }
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct Pkcs12PbeParams {
+    pub salt: OctetStringAsn1,
+    pub iterations: u32,
+}
+
+// PKCS8ShroudedKeyBag ::= EncryptedPrivateKeyInfo
+//
+// Unlike standalone encrypted PKCS#8 keys, the legacy PKCS#12 PBE algorithms are accepted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct ShroudedKeyBag {
+    pub encryption_algorithm: Pkcs12EncryptionAlgorithm,
+    pub encrypted_data: OctetStringAsn1,
+}
+
+// SafeContents ::= SEQUENCE OF SafeBag
+pub(crate) type SafeContents = Asn1SequenceOf<SafeBag>;
+
+// SafeBag ::= SEQUENCE {
+//      bagId          BAG-TYPE.&id ({PKCS12BagSet}),
+//      bagValue       [0] EXPLICIT BAG-TYPE.&Type({PKCS12BagSet}{@bagId}),
+//      bagAttributes  SET OF PKCS12Attribute OPTIONAL }
+//
+// Only the localKeyId attribute is kept, other attributes are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SafeBag {
+    pub value: SafeBagValue,
+    pub local_key_id: Option<OctetStringAsn1>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SafeBagValue {
+    Key(PrivateKeyInfo),
+    ShroudedKey(ShroudedKeyBag),
+    Cert(CertBag),
+    /// CRL, secret and nested safe contents bags are not supported
+    Unsupported(ObjectIdentifier),
+}
+
+impl ser::Serialize for SafeBag {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(3))?;
+        match &self.value {
+            SafeBagValue::Key(key) => {
+                seq.serialize_element(&ObjectIdentifierAsn1::from(oids::key_bag()))?;
+                seq.serialize_element(&ApplicationTag0(key))?;
+            }
+            SafeBagValue::ShroudedKey(key) => {
+                seq.serialize_element(&ObjectIdentifierAsn1::from(oids::pkcs8_shrouded_key_bag()))?;
+                seq.serialize_element(&ApplicationTag0(key))?;
+            }
+            SafeBagValue::Cert(cert) => {
+                seq.serialize_element(&ObjectIdentifierAsn1::from(oids::cert_bag()))?;
+                seq.serialize_element(&ApplicationTag0(cert))?;
+            }
+            SafeBagValue::Unsupported(_) => {
+                return Err(ser::Error::custom("unsupported safe bag can't be serialized"));
+            }
+        }
+        if let Some(local_key_id) = &self.local_key_id {
+            seq.serialize_element(&Asn1SetOf(vec![LocalKeyIdAttribute {
+                attr_id: oids::local_key_id().into(),
+                attr_values: Asn1SetOf(vec![local_key_id.clone()]),
+            }]))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for SafeBag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = SafeBag;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded SafeBag")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let bag_id: ObjectIdentifierAsn1 = seq_next_element!(seq, SafeBag, "bag id");
+                let value = match Into::<String>::into(&bag_id.0).as_str() {
+                    oids::KEY_BAG => {
+                        let key: ApplicationTag0<PrivateKeyInfo> = seq_next_element!(seq, SafeBag, "key bag");
+                        SafeBagValue::Key(key.0)
+                    }
+                    oids::PKCS8_SHROUDED_KEY_BAG => {
+                        let key: ApplicationTag0<ShroudedKeyBag> =
+                            seq_next_element!(seq, SafeBag, "pkcs8 shrouded key bag");
+                        SafeBagValue::ShroudedKey(key.0)
+                    }
+                    oids::CERT_BAG => {
+                        let cert: ApplicationTag0<CertBag> = seq_next_element!(seq, SafeBag, "cert bag");
+                        SafeBagValue::Cert(cert.0)
+                    }
+                    _ => {
+                        let _: de::IgnoredAny = seq_next_element!(seq, SafeBag, "bag value");
+                        SafeBagValue::Unsupported(bag_id.0)
+                    }
+                };
+
+                let attributes: Option<Asn1SetOf<SafeBagAttribute>> = seq.next_element()?;
+                let local_key_id = attributes.and_then(|attributes| {
+                    attributes.0.into_iter().find_map(|attribute| match attribute {
+                        SafeBagAttribute::LocalKeyId(local_key_id) => Some(local_key_id),
+                        SafeBagAttribute::Other => None,
+                    })
+                });
+
+                Ok(SafeBag { value, local_key_id })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// PKCS12Attribute ::= SEQUENCE {
+//      attrId      ATTRIBUTE.&id ({PKCS12AttrSet}),
+//      attrValues  SET OF ATTRIBUTE.&Type ({PKCS12AttrSet}{@attrId}) }
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct LocalKeyIdAttribute {
+    attr_id: ObjectIdentifierAsn1,
+    attr_values: Asn1SetOf<OctetStringAsn1>,
+}
+
+enum SafeBagAttribute {
+    LocalKeyId(OctetStringAsn1),
+    Other,
+}
+
+impl<'de> de::Deserialize<'de> for SafeBagAttribute {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = SafeBagAttribute;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded PKCS12Attribute")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let attr_id: ObjectIdentifierAsn1 = seq_next_element!(seq, SafeBagAttribute, "attribute id");
+                if attr_id.0 == oids::local_key_id() {
+                    let values: Asn1SetOf<OctetStringAsn1> = seq_next_element!(seq, SafeBagAttribute, "local key id");
+                    Ok(values
+                        .0
+                        .into_iter()
+                        .next()
+                        .map(SafeBagAttribute::LocalKeyId)
+                        .unwrap_or(SafeBagAttribute::Other))
+                } else {
+                    let _: de::IgnoredAny = seq_next_element!(seq, SafeBagAttribute, "attribute values");
+                    Ok(SafeBagAttribute::Other)
+                }
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// CertBag ::= SEQUENCE {
+//      certId      BAG-TYPE.&id   ({CertTypes}),
+//      certValue   [0] EXPLICIT BAG-TYPE.&Type ({CertTypes}{@certId}) }
+//
+// x509Certificate BAG-TYPE ::= {OCTET STRING IDENTIFIED BY {certTypes 1}}
+//      -- DER-encoded X.509 certificate stored in OCTET STRING
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct CertBag {
+    pub cert_id: ObjectIdentifierAsn1,
+    pub cert_value: ApplicationTag0<OctetStringAsn1>,
+}
+
+impl CertBag {
+    pub fn new_x509(der: Vec<u8>) -> Self {
+        Self {
+            cert_id: oids::x509_certificate().into(),
+            cert_value: ApplicationTag0(der.into()),
+        }
+    }
+}
@@ -1,6 +1,14 @@
-use crate::{oids, AlgorithmIdentifier};
-use picky_asn1::wrapper::{Asn1SequenceOf, IntegerAsn1, OctetStringAsn1Container};
-use serde::{de, ser, Deserialize, Serialize};
+use crate::{algorithm_identifier::ECParameters, oids, AlgorithmIdentifier};
+use oid::ObjectIdentifier;
+use picky_asn1::{
+    bit_string::BitString,
+    tag::{Tag, TagPeeker},
+    wrapper::{
+        ApplicationTag0, ApplicationTag1, Asn1SequenceOf, BitStringAsn1, IntegerAsn1, OctetStringAsn1,
+        OctetStringAsn1Container,
+    },
+};
+use serde::{de, ser, Serialize};
 use std::fmt;
 
 // Public-Key Cryptography Standards (PKCS) #8:
@@ -68,6 +76,33 @@ impl PrivateKeyInfo {
             private_key,
         }
     }
+
+    pub fn new_ec_encryption(curve: ObjectIdentifier, secret: Vec<u8>, point: Vec<u8>) -> Self {
+        let private_key = PrivateKeyValue::EC(
+            ECPrivateKey {
+                version: 1,
+                private_key: secret.into(),
+                parameters: None,
+                public_key: Some(BitStringAsn1::from(BitString::with_bytes(point)).into()),
+            }
+            .into(),
+        );
+
+        Self {
+            version: 0,
+            private_key_algorithm: AlgorithmIdentifier::new_elliptic_curve(curve),
+            private_key,
+        }
+    }
+
+    /// `algorithm` must be either `AlgorithmIdentifier::new_ed25519` or `AlgorithmIdentifier::new_ed448`
+    pub fn new_ed_encryption(algorithm: AlgorithmIdentifier, secret: Vec<u8>) -> Self {
+        Self {
+            version: 0,
+            private_key_algorithm: algorithm,
+            private_key: PrivateKeyValue::ED(OctetStringAsn1::from(secret).into()),
+        }
+    }
 }
 
 impl<'de> de::Deserialize<'de> for PrivateKeyInfo {
@@ -101,6 +136,10 @@ impl<'de> de::Deserialize<'de> for PrivateKeyInfo {
                     seq_next_element!(seq, PrivateKeyInfo, "private key algorithm");
                 let private_key = if private_key_algorithm.is_a(oids::rsa_encryption()) {
                     PrivateKeyValue::RSA(seq_next_element!(seq, PrivateKeyInfo, "rsa oid"))
+                } else if private_key_algorithm.is_a(oids::ec_public_key()) {
+                    PrivateKeyValue::EC(seq_next_element!(seq, PrivateKeyInfo, "elliptic curve private key"))
+                } else if private_key_algorithm.is_a(oids::ed25519()) || private_key_algorithm.is_a(oids::ed448()) {
+                    PrivateKeyValue::ED(seq_next_element!(seq, PrivateKeyInfo, "edwards curve private key"))
                 } else {
                     return Err(serde_invalid_value!(
                         PrivateKeyInfo,
@@ -124,6 +163,8 @@ impl<'de> de::Deserialize<'de> for PrivateKeyInfo {
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) enum PrivateKeyValue {
     RSA(OctetStringAsn1Container<RSAPrivateKey>),
+    EC(OctetStringAsn1Container<ECPrivateKey>),
+    ED(OctetStringAsn1Container<CurvePrivateKey>),
 }
 
 impl ser::Serialize for PrivateKeyValue {
@@ -133,11 +174,30 @@ impl ser::Serialize for PrivateKeyValue {
     {
         match self {
             PrivateKeyValue::RSA(rsa) => rsa.serialize(serializer),
+            PrivateKeyValue::EC(ec) => ec.serialize(serializer),
+            PrivateKeyValue::ED(ed) => ed.serialize(serializer),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+// RSA Private Key Structure:
+// https://tools.ietf.org/html/rfc8017#appendix-A.1.2
+//
+// RSAPrivateKey ::= SEQUENCE {
+//     version           Version,
+//     modulus           INTEGER,  -- n
+//     publicExponent    INTEGER,  -- e
+//     privateExponent   INTEGER,  -- d
+//     prime1            INTEGER,  -- p
+//     prime2            INTEGER,  -- q
+//     exponent1         INTEGER,  -- d mod (p-1)
+//     exponent2         INTEGER,  -- d mod (q-1)
+//     coefficient       INTEGER,  -- (inverse of q) mod p
+//     otherPrimeInfos   OtherPrimeInfos OPTIONAL
+// }
+//
+// Only two-prime keys (version 0) are supported: a deserialized key always holds the nine integers above.
+#[derive(Serialize, Debug, Clone, PartialEq)]
 pub(crate) struct RSAPrivateKey(Asn1SequenceOf<IntegerAsn1>);
 
 impl RSAPrivateKey {
@@ -153,8 +213,14 @@ impl RSAPrivateKey {
         &(self.0).0[3]
     }
 
+    /// prime1 (p) and prime2 (q)
     pub fn primes(&self) -> &[IntegerAsn1] {
-        &(self.0).0[4..]
+        &(self.0).0[4..6]
+    }
+
+    /// coefficient (q^-1 mod p)
+    pub fn coefficient(&self) -> &IntegerAsn1 {
+        &(self.0).0[8]
     }
 
     pub fn into_public_components(self) -> (IntegerAsn1, IntegerAsn1) {
@@ -167,6 +233,137 @@ impl RSAPrivateKey {
     }
 }
 
+impl<'de> de::Deserialize<'de> for RSAPrivateKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let integers: Asn1SequenceOf<IntegerAsn1> = de::Deserialize::deserialize(deserializer)?;
+
+        if (integers.0).len() != 9 {
+            return Err(serde_invalid_value!(
+                RSAPrivateKey,
+                "unexpected number of integers (a two-prime key has nine)",
+                "a two-prime RSAPrivateKey"
+            ));
+        }
+
+        if (integers.0)[0].as_signed_bytes_be() != [0] {
+            return Err(serde_invalid_value!(
+                RSAPrivateKey,
+                "unsupported version (valid version number: 0)",
+                "a supported RSAPrivateKey"
+            ));
+        }
+
+        let is_positive =
+            |integer: &IntegerAsn1| integer.is_positive() && integer.as_signed_bytes_be().iter().any(|&byte| byte != 0);
+        if !(integers.0)[1..].iter().all(is_positive) {
+            return Err(serde_invalid_value!(
+                RSAPrivateKey,
+                "negative or zero integer",
+                "an RSAPrivateKey made of positive integers"
+            ));
+        }
+
+        Ok(Self(integers))
+    }
+}
+
+// Elliptic Curve Private Key Structure:
+// https://tools.ietf.org/html/rfc5915#section-3
+//
+// ECPrivateKey ::= SEQUENCE {
+//      version        INTEGER { ecPrivkeyVer1(1) } (ecPrivkeyVer1),
+//      privateKey     OCTET STRING,
+//      parameters [0] ECParameters {{ NamedCurve }} OPTIONAL,
+//      publicKey  [1] BIT STRING OPTIONAL
+// }
+//
+// When embedded in a PrivateKeyInfo (pkcs8), the curve is usually given by the private key algorithm
+// parameters only.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub(crate) struct ECPrivateKey {
+    pub version: u8,
+    pub private_key: OctetStringAsn1,
+    pub parameters: Option<ApplicationTag0<ECParameters>>,
+    pub public_key: Option<ApplicationTag1<BitStringAsn1>>,
+}
+
+impl ECPrivateKey {
+    pub fn public_point(&self) -> Option<&[u8]> {
+        self.public_key.as_ref().map(|key| (key.0).0.payload_view())
+    }
+
+    pub fn named_curve(&self) -> Option<&ObjectIdentifier> {
+        match &self.parameters {
+            Some(ApplicationTag0(ECParameters::NamedCurve(curve))) => Some(&curve.0),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for ECPrivateKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = ECPrivateKey;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded ECPrivateKey (sec1)")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let version = seq_next_element!(seq, ECPrivateKey, "version");
+                if version != 1 {
+                    return Err(serde_invalid_value!(
+                        ECPrivateKey,
+                        "unsupported version (valid version number: 1)",
+                        "a supported ECPrivateKey"
+                    ));
+                }
+
+                let private_key = seq_next_element!(seq, ECPrivateKey, "private key");
+
+                let mut parameters = None;
+                let mut public_key = None;
+
+                if let Some(TagPeeker { next_tag: Tag::APP_0 }) = seq.next_element()? {
+                    parameters = Some(seq_next_element!(seq, ECPrivateKey, "parameters"));
+                }
+
+                if let Some(TagPeeker { next_tag: Tag::APP_1 }) = seq.next_element()? {
+                    public_key = Some(seq_next_element!(seq, ECPrivateKey, "public key"));
+                }
+
+                Ok(ECPrivateKey {
+                    version,
+                    private_key,
+                    parameters,
+                    public_key,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// Edwards curves private key structure:
+// https://tools.ietf.org/html/rfc8410#section-7
+//
+// CurvePrivateKey ::= OCTET STRING
+//
+// The raw secret key (32 bytes for Ed25519, 57 bytes for Ed448) wrapped in the PrivateKeyInfo octet string.
+pub(crate) type CurvePrivateKey = OctetStringAsn1;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +395,67 @@ mod tests {
         let private_key = PrivateKeyInfo::new_rsa_encryption(modulus, public_exponent, private_exponent, primes);
         check_serde!(private_key: PrivateKeyInfo in encoded);
     }
+
+    #[test]
+    fn sec1_private_key() {
+        let encoded = base64::decode(
+            "MHcCAQEEIMUuxvhwdf0IRC2evxDxzQGpdlZPCz+7gjzDkLcfMZBloAoGCCqGSM49\
+             AwEHoUQDQgAEkjQapUBEr5D2VpuKso3CMY+FZm3qJ9YFJVH8l1Foai1PFI09iCV/\
+             dLfuoJ6BnG8Y+h+9VIin3zEMtPjjx5JdAA==",
+        )
+        .expect("invalid base64");
+
+        let private_key = ECPrivateKey {
+            version: 1,
+            private_key: encoded[7..39].to_vec().into(),
+            parameters: Some(ECParameters::from(oids::secp256r1()).into()),
+            public_key: Some(BitStringAsn1::from(BitString::with_bytes(encoded[56..].to_vec())).into()),
+        };
+        check_serde!(private_key: ECPrivateKey in encoded);
+    }
+
+    #[test]
+    fn pkcs_8_ec_private_key_without_public_key() {
+        let encoded = base64::decode(
+            "MEECAQAwEwYHKoZIzj0CAQYIKoZIzj0DAQcEJzAlAgEBBCDFLsb4cHX9CEQtnr8Q\
+             8c0BqXZWTws/u4I8w5C3HzGQZQ==",
+        )
+        .expect("invalid base64");
+
+        let private_key = PrivateKeyInfo {
+            version: 0,
+            private_key_algorithm: AlgorithmIdentifier::new_elliptic_curve(oids::secp256r1()),
+            private_key: PrivateKeyValue::EC(
+                ECPrivateKey {
+                    version: 1,
+                    private_key: encoded[35..].to_vec().into(),
+                    parameters: None,
+                    public_key: None,
+                }
+                .into(),
+            ),
+        };
+        check_serde!(private_key: PrivateKeyInfo in encoded);
+    }
+
+    #[test]
+    fn pkcs_8_ed25519_private_key() {
+        let encoded =
+            base64::decode("MC4CAQAwBQYDK2VwBCIEIIOM/pbNenxDNJ8SeaE/4XimqfeKqS4/YyW8tpHlWvmn").expect("invalid base64");
+
+        let private_key = PrivateKeyInfo::new_ed_encryption(AlgorithmIdentifier::new_ed25519(), encoded[16..].to_vec());
+        check_serde!(private_key: PrivateKeyInfo in encoded);
+    }
+
+    #[test]
+    fn pkcs_8_ed448_private_key() {
+        let encoded = base64::decode(
+            "MEcCAQAwBQYDK2VxBDsEOb5xeStfGFQz2w874OgyqQbdo5KEO7zHGFwpg3sfXdpU\
+             UR8dks4qknRqrsP0EkwpcsGKS0kvJj2BUw==",
+        )
+        .expect("invalid base64");
+
+        let private_key = PrivateKeyInfo::new_ed_encryption(AlgorithmIdentifier::new_ed448(), encoded[16..].to_vec());
+        check_serde!(private_key: PrivateKeyInfo in encoded);
+    }
 }
@@ -1,5 +1,8 @@
-use crate::{oids, AlgorithmIdentifier};
-use picky_asn1::wrapper::{BitStringAsn1, BitStringAsn1Container, IntegerAsn1};
+use crate::{algorithm_identifier::ECParameters, oids, AlgorithmIdentifier};
+use picky_asn1::{
+    bit_string::BitString,
+    wrapper::{BitStringAsn1, BitStringAsn1Container, IntegerAsn1},
+};
 use serde::{de, ser, Deserialize, Serialize};
 use std::fmt;
 
@@ -7,6 +10,7 @@ use std::fmt;
 pub(crate) enum PublicKey {
     RSA(EncapsulatedRSAPublicKey),
     EC(EncapsulatedECPoint),
+    ED(EncapsulatedEdwardsPublicKey),
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -19,6 +23,9 @@ pub(crate) type EncapsulatedRSAPublicKey = BitStringAsn1Container<RSAPublicKey>;
 //pub(crate) type ECPoint = OctetStringAsn1;
 pub(crate) type EncapsulatedECPoint = BitStringAsn1;
 
+/// Raw Ed25519 or Ed448 public key as defined in RFC 8410
+pub(crate) type EncapsulatedEdwardsPublicKey = BitStringAsn1;
+
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct SubjectPublicKeyInfo {
     pub algorithm: AlgorithmIdentifier,
@@ -38,6 +45,21 @@ impl SubjectPublicKeyInfo {
             ),
         }
     }
+
+    pub fn new_ec_key(curve: ECParameters, point: Vec<u8>) -> Self {
+        Self {
+            algorithm: AlgorithmIdentifier::new_elliptic_curve(curve),
+            subject_public_key: PublicKey::EC(BitString::with_bytes(point).into()),
+        }
+    }
+
+    /// `algorithm` must be either `AlgorithmIdentifier::new_ed25519` or `AlgorithmIdentifier::new_ed448`
+    pub fn new_ed_key(algorithm: AlgorithmIdentifier, public_key: Vec<u8>) -> Self {
+        Self {
+            algorithm,
+            subject_public_key: PublicKey::ED(BitString::with_bytes(public_key).into()),
+        }
+    }
 }
 
 impl ser::Serialize for SubjectPublicKeyInfo {
@@ -51,6 +73,7 @@ impl ser::Serialize for SubjectPublicKeyInfo {
         match &self.subject_public_key {
             PublicKey::RSA(key) => seq.serialize_element(key)?,
             PublicKey::EC(key) => seq.serialize_element(key)?,
+            PublicKey::ED(key) => seq.serialize_element(key)?,
         }
         seq.end()
     }
@@ -81,6 +104,9 @@ impl<'de> de::Deserialize<'de> for SubjectPublicKeyInfo {
                     oids::EC_PUBLIC_KEY => {
                         PublicKey::EC(seq_next_element!(seq, SubjectPublicKeyInfo, "elliptic curves key"))
                     }
+                    oids::ED25519 | oids::ED448 => {
+                        PublicKey::ED(seq_next_element!(seq, SubjectPublicKeyInfo, "edwards curve key"))
+                    }
                     _ => {
                         return Err(serde_invalid_value!(
                             SubjectPublicKeyInfo,
@@ -165,4 +191,32 @@ mod tests {
         };
         check_serde!(info: SubjectPublicKeyInfo in encoded);
     }
+
+    #[test]
+    fn ed25519_subject_public_key_info() {
+        // RFC 8410 section 10.1
+        let encoded =
+            base64::decode("MCowBQYDK2VwAyEAGb9ECWmEzf6FQbrBZ9w7lshQhqowtrbLDFw4rXAxZuE=").expect("invalid base64");
+
+        let algorithm = AlgorithmIdentifier::new_ed25519();
+        check_serde!(algorithm: AlgorithmIdentifier in encoded[2..9]);
+
+        let info = SubjectPublicKeyInfo::new_ed_key(algorithm, encoded[12..].to_vec());
+        check_serde!(info: SubjectPublicKeyInfo in encoded);
+    }
+
+    #[test]
+    fn ed448_subject_public_key_info() {
+        let encoded = base64::decode(
+            "MEMwBQYDK2VxAzoA2ACLdh0L4eyUDKbJ+XCb5mW//6LfAOmYo4oNyOau9zMBkbS6\
+             W1PqwATrFIfUNuw71NDdbIaIO+6A",
+        )
+        .expect("invalid base64");
+
+        let algorithm = AlgorithmIdentifier::new_ed448();
+        check_serde!(algorithm: AlgorithmIdentifier in encoded[2..9]);
+
+        let info = SubjectPublicKeyInfo::new_ed_key(algorithm, encoded[12..].to_vec());
+        check_serde!(info: SubjectPublicKeyInfo in encoded);
+    }
 }
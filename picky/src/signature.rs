@@ -1,11 +1,12 @@
 use crate::{
-    key::{PrivateKey, PublicKey},
+    algorithm_identifier::{AlgorithmIdentifierParameters, HashAlgorithm, RsassaPssParameters},
+    key::{EcCurve, EdAlgorithm, PrivateKey, PublicKey},
     oids,
     private::private_key_info,
     AlgorithmIdentifier,
 };
 use picky_asn1::wrapper::{BitStringAsn1Container, OctetStringAsn1Container};
-use rsa::{hash::Hashes, BigUint, PaddingScheme, PublicKey as RsaPublicKeyInterface, RSAPrivateKey, RSAPublicKey};
+use rsa::{BigUint, Pkcs1v15Sign, Pss, RsaPrivateKey, RsaPublicKey};
 use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
 use sha2::{Sha224, Sha256, Sha384, Sha512};
@@ -17,12 +18,27 @@ pub enum SignatureError {
     #[snafu(display("RSA error: {}", context))]
     Rsa { context: String },
 
+    /// ECDSA error
+    #[snafu(display("ECDSA error: {}", context))]
+    Ecdsa { context: String },
+
+    /// EdDSA error
+    #[snafu(display("EdDSA error: {}", context))]
+    EdDsa { context: String },
+
     /// invalid signature
     BadSignature,
 
     /// unsupported algorithm
     #[snafu(display("unsupported algorithm: {}", algorithm))]
     UnsupportedAlgorithm { algorithm: String },
+
+    /// key can't be used with this signature algorithm
+    #[snafu(display("{:?} can't be used with {} key", signature_hash_type, key_type))]
+    IncompatibleKey {
+        signature_hash_type: SignatureHashType,
+        key_type: &'static str,
+    },
 }
 
 impl From<rsa::errors::Error> for SignatureError {
@@ -43,13 +59,69 @@ pub enum SignatureHashType {
     RsaSha384,
     #[serde(rename = "RS512")]
     RsaSha512,
+    /// RSASSA-PSS using MGF1 with the same hash algorithm and a salt as long as the digest
+    #[serde(rename = "PS256")]
+    RsaPssSha256,
+    #[serde(rename = "PS384")]
+    RsaPssSha384,
+    #[serde(rename = "PS512")]
+    RsaPssSha512,
+    #[serde(rename = "ES256")]
+    EcdsaSha256,
+    #[serde(rename = "ES384")]
+    EcdsaSha384,
+    #[serde(rename = "ES512")]
+    EcdsaSha512,
+    /// Pure Ed25519 (no prehash)
+    #[serde(rename = "EdDSA")]
+    Ed25519,
+    /// Pure Ed448 (no prehash)
+    ///
+    /// JWS uses `EdDSA` for both curves: deserializing `EdDSA` always gives `Ed25519`
+    /// and the actual curve is given by the verifying key (see `Jwt`).
+    #[serde(rename = "EdDSA", skip_deserializing)]
+    Ed448,
 }
 
 macro_rules! hash {
     ($algorithm:ident, $input:ident) => {{
-        let mut digest = $algorithm::new();
-        digest.input($input);
-        digest.result().as_slice().to_vec()
+        $algorithm::digest($input).to_vec()
+    }};
+}
+
+enum RsaPadding {
+    Pkcs1v15(Pkcs1v15Sign),
+    Pss(Pss),
+}
+
+// ECDSA signatures are computed and verified against the prehashed message in order to support any
+// hash algorithm with any curve (e.g. a P-256 key with ecdsa-with-SHA384).
+
+macro_rules! ecdsa_sign {
+    ($curve:ident, $secret:expr, $digest:expr) => {{
+        use $curve::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+
+        let signing_key = SigningKey::from_slice($secret).map_err(|e| SignatureError::Ecdsa {
+            context: format!("invalid private key: {}", e),
+        })?;
+        let signature: Signature = signing_key
+            .sign_prehash($digest)
+            .map_err(|e| SignatureError::Ecdsa { context: e.to_string() })?;
+        signature.to_der().as_bytes().to_vec()
+    }};
+}
+
+macro_rules! ecdsa_verify {
+    ($curve:ident, $point:expr, $digest:expr, $signature:expr) => {{
+        use $curve::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+
+        let verifying_key = VerifyingKey::from_sec1_bytes($point).map_err(|e| SignatureError::Ecdsa {
+            context: format!("invalid public key: {}", e),
+        })?;
+        let signature = Signature::from_der($signature).map_err(|_| SignatureError::BadSignature)?;
+        verifying_key
+            .verify_prehash($digest, &signature)
+            .map_err(|_| SignatureError::BadSignature)?;
     }};
 }
 
@@ -62,10 +134,59 @@ impl SignatureHashType {
             oids::SHA256_WITH_RSA_ENCRYPTION => Ok(Self::RsaSha256),
             oids::SHA384_WITH_RSA_ENCRYPTION => Ok(Self::RsaSha384),
             oids::SHA512_WITH_RSA_ENCRYPTION => Ok(Self::RsaSha512),
+            oids::RSASSA_PSS => match algorithm_identifier.parameters() {
+                AlgorithmIdentifierParameters::RsassaPss(params) => Self::from_rsassa_pss_parameters(params),
+                _ => Err(SignatureError::UnsupportedAlgorithm {
+                    algorithm: "RSASSA-PSS without parameters".to_owned(),
+                }),
+            },
+            oids::ECDSA_WITH_SHA256 => Ok(Self::EcdsaSha256),
+            oids::ECDSA_WITH_SHA384 => Ok(Self::EcdsaSha384),
+            oids::ECDSA_WITH_SHA512 => Ok(Self::EcdsaSha512),
+            oids::ED25519 => Ok(Self::Ed25519),
+            oids::ED448 => Ok(Self::Ed448),
             _ => Err(SignatureError::UnsupportedAlgorithm { algorithm: oid_string }),
         }
     }
 
+    fn from_rsassa_pss_parameters(params: &RsassaPssParameters) -> Result<Self, SignatureError> {
+        let hash = HashAlgorithm::from_oid(params.hash_algorithm().oid());
+
+        let mask_gen_algorithm = params.mask_gen_algorithm();
+        let mgf1_hash = if mask_gen_algorithm.oid() == &oids::id_mgf1() {
+            HashAlgorithm::from_oid(mask_gen_algorithm.hash_algorithm().oid())
+        } else {
+            None
+        };
+
+        let signature_hash_type = match hash {
+            Some(HashAlgorithm::Sha256) => Some(Self::RsaPssSha256),
+            Some(HashAlgorithm::Sha384) => Some(Self::RsaPssSha384),
+            Some(HashAlgorithm::Sha512) => Some(Self::RsaPssSha512),
+            _ => None,
+        };
+
+        // only the usual parameters are supported: MGF1 using the same hash algorithm and a salt as long as the digest
+        match (signature_hash_type, hash) {
+            (Some(signature_hash_type), Some(hash))
+                if mgf1_hash == Some(hash) && usize::from(params.salt_length()) == hash.output_size() =>
+            {
+                Ok(signature_hash_type)
+            }
+            _ => Err(SignatureError::UnsupportedAlgorithm {
+                algorithm: format!(
+                    "RSASSA-PSS (hash: {:?}, MGF1 hash: {:?}, salt length: {})",
+                    hash,
+                    mgf1_hash,
+                    params.salt_length()
+                ),
+            }),
+        }
+    }
+
+    /// Hashes the message as required by the signature algorithm.
+    ///
+    /// Pure EdDSA signs the message itself, which is returned unchanged.
     pub fn hash(self, msg: &[u8]) -> Vec<u8> {
         match self {
             Self::RsaSha1 => hash!(Sha1, msg),
@@ -73,75 +194,228 @@ impl SignatureHashType {
             Self::RsaSha256 => hash!(Sha256, msg),
             Self::RsaSha384 => hash!(Sha384, msg),
             Self::RsaSha512 => hash!(Sha512, msg),
+            Self::RsaPssSha256 => hash!(Sha256, msg),
+            Self::RsaPssSha384 => hash!(Sha384, msg),
+            Self::RsaPssSha512 => hash!(Sha512, msg),
+            Self::EcdsaSha256 => hash!(Sha256, msg),
+            Self::EcdsaSha384 => hash!(Sha384, msg),
+            Self::EcdsaSha512 => hash!(Sha512, msg),
+            Self::Ed25519 | Self::Ed448 => msg.to_vec(),
         }
     }
 
-    pub fn sign(self, msg: &[u8], private_key: &PrivateKey) -> Result<Vec<u8>, SignatureError> {
-        let rsa_private_key = match &private_key.as_inner().private_key {
-            private_key_info::PrivateKeyValue::RSA(OctetStringAsn1Container(key)) => RSAPrivateKey::from_components(
-                BigUint::from_bytes_be(key.modulus().as_bytes_be()),
-                BigUint::from_bytes_be(key.public_exponent().as_bytes_be()),
-                BigUint::from_bytes_be(key.private_exponent().as_bytes_be()),
-                key.primes()
-                    .iter()
-                    .map(|p| BigUint::from_bytes_be(p.as_bytes_be()))
-                    .collect(),
-            ),
-        };
+    fn rsa_padding(self) -> Option<RsaPadding> {
+        match self {
+            Self::RsaSha1 => Some(RsaPadding::Pkcs1v15(Pkcs1v15Sign::new::<Sha1>())),
+            Self::RsaSha224 => Some(RsaPadding::Pkcs1v15(Pkcs1v15Sign::new::<Sha224>())),
+            Self::RsaSha256 => Some(RsaPadding::Pkcs1v15(Pkcs1v15Sign::new::<Sha256>())),
+            Self::RsaSha384 => Some(RsaPadding::Pkcs1v15(Pkcs1v15Sign::new::<Sha384>())),
+            Self::RsaSha512 => Some(RsaPadding::Pkcs1v15(Pkcs1v15Sign::new::<Sha512>())),
+            Self::RsaPssSha256 => Some(RsaPadding::Pss(Pss::new::<Sha256>())),
+            Self::RsaPssSha384 => Some(RsaPadding::Pss(Pss::new::<Sha384>())),
+            Self::RsaPssSha512 => Some(RsaPadding::Pss(Pss::new::<Sha512>())),
+            Self::EcdsaSha256 | Self::EcdsaSha384 | Self::EcdsaSha512 | Self::Ed25519 | Self::Ed448 => None,
+        }
+    }
+
+    fn is_ecdsa(self) -> bool {
+        matches!(self, Self::EcdsaSha256 | Self::EcdsaSha384 | Self::EcdsaSha512)
+    }
 
+    fn ed_algorithm(self) -> Option<EdAlgorithm> {
+        match self {
+            Self::Ed25519 => Some(EdAlgorithm::Ed25519),
+            Self::Ed448 => Some(EdAlgorithm::Ed448),
+            _ => None,
+        }
+    }
+
+    pub fn sign(self, msg: &[u8], private_key: &PrivateKey) -> Result<Vec<u8>, SignatureError> {
         let digest = self.hash(msg);
 
-        let hash_algo = match self {
-            Self::RsaSha1 => &Hashes::SHA1,
-            Self::RsaSha224 => &Hashes::SHA2_224,
-            Self::RsaSha256 => &Hashes::SHA2_256,
-            Self::RsaSha384 => &Hashes::SHA2_384,
-            Self::RsaSha512 => &Hashes::SHA2_512,
-        };
+        match &private_key.as_inner().private_key {
+            private_key_info::PrivateKeyValue::RSA(OctetStringAsn1Container(key)) => {
+                let padding = self.rsa_padding().ok_or(SignatureError::IncompatibleKey {
+                    signature_hash_type: self,
+                    key_type: "RSA",
+                })?;
+
+                let rsa_private_key = RsaPrivateKey::from_components(
+                    BigUint::from_bytes_be(key.modulus().as_bytes_be()),
+                    BigUint::from_bytes_be(key.public_exponent().as_bytes_be()),
+                    BigUint::from_bytes_be(key.private_exponent().as_bytes_be()),
+                    key.primes()
+                        .iter()
+                        .map(|p| BigUint::from_bytes_be(p.as_bytes_be()))
+                        .collect(),
+                )?;
+
+                let mut rng = rand_core::OsRng;
+                let signature = match padding {
+                    RsaPadding::Pkcs1v15(padding) => rsa_private_key.sign_with_rng(&mut rng, padding, &digest)?,
+                    RsaPadding::Pss(padding) => rsa_private_key.sign_with_rng(&mut rng, padding, &digest)?,
+                };
+
+                Ok(signature)
+            }
+            private_key_info::PrivateKeyValue::EC(OctetStringAsn1Container(key)) => {
+                if !self.is_ecdsa() {
+                    return Err(SignatureError::IncompatibleKey {
+                        signature_hash_type: self,
+                        key_type: "elliptic curve",
+                    });
+                }
 
-        let signature = rsa_private_key.sign_blinded(
-            &mut rand::rngs::OsRng,
-            PaddingScheme::PKCS1v15,
-            Some(hash_algo),
-            &digest,
-        )?;
+                let secret = &key.private_key.0;
+                let signature = match ec_curve(&private_key.as_inner().private_key_algorithm)? {
+                    EcCurve::NistP256 => ecdsa_sign!(p256, secret, &digest),
+                    EcCurve::NistP384 => ecdsa_sign!(p384, secret, &digest),
+                    EcCurve::NistP521 => ecdsa_sign!(p521, secret, &digest),
+                };
 
-        Ok(signature)
+                Ok(signature)
+            }
+            private_key_info::PrivateKeyValue::ED(OctetStringAsn1Container(secret)) => {
+                let algorithm = ed_algorithm(&private_key.as_inner().private_key_algorithm)?;
+                if self.ed_algorithm() != Some(algorithm) {
+                    return Err(SignatureError::IncompatibleKey {
+                        signature_hash_type: self,
+                        key_type: ed_key_type(algorithm),
+                    });
+                }
+
+                let signature = match algorithm {
+                    EdAlgorithm::Ed25519 => {
+                        use ed25519_dalek::{Signer, SigningKey};
+                        use std::convert::TryFrom;
+
+                        let signing_key =
+                            SigningKey::try_from(secret.0.as_slice()).map_err(|e| SignatureError::EdDsa {
+                                context: format!("invalid private key: {}", e),
+                            })?;
+                        signing_key.sign(&digest).to_bytes().to_vec()
+                    }
+                    EdAlgorithm::Ed448 => {
+                        use std::convert::TryFrom;
+
+                        let signing_key = ed448_goldilocks::SigningKey::try_from(secret.0.as_slice()).map_err(|e| {
+                            SignatureError::EdDsa {
+                                context: format!("invalid private key: {}", e),
+                            }
+                        })?;
+                        signing_key.sign_raw(&digest).to_bytes().to_vec()
+                    }
+                };
+
+                Ok(signature)
+            }
+        }
     }
 
+    /// Verifies the signature.
     pub fn verify(self, public_key: &PublicKey, msg: &[u8], signature: &[u8]) -> Result<(), SignatureError> {
         use crate::private::subject_public_key_info::PublicKey as InnerPublicKey;
 
-        let public_key = match &public_key.as_inner().subject_public_key {
-            InnerPublicKey::RSA(BitStringAsn1Container(key)) => RSAPublicKey::new(
-                BigUint::from_bytes_be(key.modulus.as_bytes_be()),
-                BigUint::from_bytes_be(key.public_exponent.as_bytes_be()),
-            )?,
-            InnerPublicKey::EC(_) => {
-                return Err(SignatureError::UnsupportedAlgorithm {
-                    algorithm: "elliptic curves".into(),
-                });
+        let digest = self.hash(msg);
+
+        match &public_key.as_inner().subject_public_key {
+            InnerPublicKey::RSA(BitStringAsn1Container(key)) => {
+                let padding = self.rsa_padding().ok_or(SignatureError::IncompatibleKey {
+                    signature_hash_type: self,
+                    key_type: "RSA",
+                })?;
+
+                let public_key = RsaPublicKey::new(
+                    BigUint::from_bytes_be(key.modulus.as_bytes_be()),
+                    BigUint::from_bytes_be(key.public_exponent.as_bytes_be()),
+                )?;
+
+                match padding {
+                    RsaPadding::Pkcs1v15(padding) => public_key.verify(padding, &digest, signature),
+                    RsaPadding::Pss(padding) => public_key.verify(padding, &digest, signature),
+                }
+                .map_err(|_| SignatureError::BadSignature)?;
             }
-        };
+            InnerPublicKey::EC(point) => {
+                if !self.is_ecdsa() {
+                    return Err(SignatureError::IncompatibleKey {
+                        signature_hash_type: self,
+                        key_type: "elliptic curve",
+                    });
+                }
 
-        let hash_algorithm = match self {
-            Self::RsaSha1 => &Hashes::SHA1,
-            Self::RsaSha224 => &Hashes::SHA2_224,
-            Self::RsaSha256 => &Hashes::SHA2_256,
-            Self::RsaSha384 => &Hashes::SHA2_384,
-            Self::RsaSha512 => &Hashes::SHA2_512,
-        };
+                let point = point.0.payload_view();
+                match ec_curve(&public_key.as_inner().algorithm)? {
+                    EcCurve::NistP256 => ecdsa_verify!(p256, point, &digest, signature),
+                    EcCurve::NistP384 => ecdsa_verify!(p384, point, &digest, signature),
+                    EcCurve::NistP521 => ecdsa_verify!(p521, point, &digest, signature),
+                }
+            }
+            InnerPublicKey::ED(key) => {
+                let algorithm = ed_algorithm(&public_key.as_inner().algorithm)?;
+                if self.ed_algorithm() != Some(algorithm) {
+                    return Err(SignatureError::IncompatibleKey {
+                        signature_hash_type: self,
+                        key_type: ed_key_type(algorithm),
+                    });
+                }
 
-        let digest = self.hash(msg);
+                let key = key.0.payload_view();
+                match algorithm {
+                    EdAlgorithm::Ed25519 => {
+                        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+                        use std::convert::TryFrom;
 
-        public_key
-            .verify(PaddingScheme::PKCS1v15, Some(hash_algorithm), &digest, signature)
-            .map_err(|_| SignatureError::BadSignature)?;
+                        let verifying_key = VerifyingKey::try_from(key).map_err(|e| SignatureError::EdDsa {
+                            context: format!("invalid public key: {}", e),
+                        })?;
+                        let signature = Signature::from_slice(signature).map_err(|_| SignatureError::BadSignature)?;
+                        verifying_key
+                            .verify(&digest, &signature)
+                            .map_err(|_| SignatureError::BadSignature)?;
+                    }
+                    EdAlgorithm::Ed448 => {
+                        use ed448_goldilocks::{Signature, VerifyingKey, PUBLIC_KEY_LENGTH};
+
+                        if key.len() != PUBLIC_KEY_LENGTH {
+                            return Err(SignatureError::EdDsa {
+                                context: format!("invalid public key length: {}", key.len()),
+                            });
+                        }
+                        let mut key_bytes = [0; PUBLIC_KEY_LENGTH];
+                        key_bytes.copy_from_slice(key);
+                        let verifying_key =
+                            VerifyingKey::from_bytes(&key_bytes).map_err(|e| SignatureError::EdDsa {
+                                context: format!("invalid public key: {}", e),
+                            })?;
+                        let signature = Signature::from_slice(signature).map_err(|_| SignatureError::BadSignature)?;
+                        verifying_key
+                            .verify_raw(&signature, &digest)
+                            .map_err(|_| SignatureError::BadSignature)?;
+                    }
+                }
+            }
+        }
 
         Ok(())
     }
 }
 
+fn ec_curve(algorithm: &AlgorithmIdentifier) -> Result<EcCurve, SignatureError> {
+    EcCurve::from_algorithm_identifier(algorithm).map_err(|e| SignatureError::Ecdsa { context: e.to_string() })
+}
+
+fn ed_algorithm(algorithm: &AlgorithmIdentifier) -> Result<EdAlgorithm, SignatureError> {
+    EdAlgorithm::from_algorithm_identifier(algorithm).map_err(|e| SignatureError::EdDsa { context: e.to_string() })
+}
+
+fn ed_key_type(algorithm: EdAlgorithm) -> &'static str {
+    match algorithm {
+        EdAlgorithm::Ed25519 => "Ed25519",
+        EdAlgorithm::Ed448 => "Ed448",
+    }
+}
+
 impl From<SignatureHashType> for AlgorithmIdentifier {
     fn from(ty: SignatureHashType) -> Self {
         match ty {
@@ -150,6 +424,63 @@ impl From<SignatureHashType> for AlgorithmIdentifier {
             SignatureHashType::RsaSha256 => AlgorithmIdentifier::new_sha256_with_rsa_encryption(),
             SignatureHashType::RsaSha384 => AlgorithmIdentifier::new_sha384_with_rsa_encryption(),
             SignatureHashType::RsaSha512 => AlgorithmIdentifier::new_sha512_with_rsa_encryption(),
+            SignatureHashType::RsaPssSha256 => {
+                AlgorithmIdentifier::new_rsassa_pss(RsassaPssParameters::new(HashAlgorithm::Sha256))
+            }
+            SignatureHashType::RsaPssSha384 => {
+                AlgorithmIdentifier::new_rsassa_pss(RsassaPssParameters::new(HashAlgorithm::Sha384))
+            }
+            SignatureHashType::RsaPssSha512 => {
+                AlgorithmIdentifier::new_rsassa_pss(RsassaPssParameters::new(HashAlgorithm::Sha512))
+            }
+            SignatureHashType::EcdsaSha256 => AlgorithmIdentifier::new_ecdsa_with_sha256(),
+            SignatureHashType::EcdsaSha384 => AlgorithmIdentifier::new_ecdsa_with_sha384(),
+            SignatureHashType::EcdsaSha512 => AlgorithmIdentifier::new_ecdsa_with_sha512(),
+            SignatureHashType::Ed25519 => AlgorithmIdentifier::new_ed25519(),
+            SignatureHashType::Ed448 => AlgorithmIdentifier::new_ed448(),
+        }
+    }
+}
+
+/// A signing key, possibly living outside of the process (HSM, cloud KMS, agent…).
+///
+/// `PrivateKey` is the in-memory implementation. Anything implementing this trait can be used
+/// to sign certificates (`CertificateBuilder`), certificate signing requests (`Csr::generate`)
+/// and JWTs (`Jwt::encode`).
+pub trait Signer {
+    /// Public key matching the signing key.
+    fn public_key(&self) -> PublicKey;
+
+    /// Signature algorithm used when none is explicitly requested.
+    fn signature_hash_type(&self) -> SignatureHashType;
+
+    /// Signs `msg` with the given signature algorithm.
+    ///
+    /// The message is not prehashed. Signatures are encoded as in X.509 (ECDSA signatures are DER encoded).
+    fn sign(&self, msg: &[u8], signature_hash_type: SignatureHashType) -> Result<Vec<u8>, SignatureError>;
+}
+
+impl Signer for PrivateKey {
+    fn public_key(&self) -> PublicKey {
+        self.to_public_key()
+    }
+
+    fn signature_hash_type(&self) -> SignatureHashType {
+        match &self.as_inner().private_key {
+            private_key_info::PrivateKeyValue::RSA(_) => SignatureHashType::RsaSha256,
+            private_key_info::PrivateKeyValue::EC(_) => match ec_curve(&self.as_inner().private_key_algorithm) {
+                Ok(EcCurve::NistP256) => SignatureHashType::EcdsaSha256,
+                Ok(EcCurve::NistP521) => SignatureHashType::EcdsaSha512,
+                _ => SignatureHashType::EcdsaSha384,
+            },
+            private_key_info::PrivateKeyValue::ED(_) => match ed_algorithm(&self.as_inner().private_key_algorithm) {
+                Ok(EdAlgorithm::Ed448) => SignatureHashType::Ed448,
+                _ => SignatureHashType::Ed25519,
+            },
         }
     }
+
+    fn sign(&self, msg: &[u8], signature_hash_type: SignatureHashType) -> Result<Vec<u8>, SignatureError> {
+        signature_hash_type.sign(msg, self)
+    }
 }
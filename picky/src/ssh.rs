@@ -0,0 +1,1027 @@
+//! OpenSSH user and host certificates.
+//!
+//! Certificates are encoded as described in
+//! [PROTOCOL.certkeys](https://github.com/openssh/openssh-portable/blob/master/PROTOCOL.certkeys).
+//! Validity bounds are expressed in seconds since the UNIX epoch.
+
+use crate::{
+    key::{KeyError, PublicKey},
+    private::openssh::{self, SshReader, SshWriter},
+    signature::{SignatureError, SignatureHashType, Signer},
+};
+use picky_asn1::wrapper::IntegerAsn1;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{cell::RefCell, fmt};
+
+const CERT_KEY_TYPE_SUFFIX: &str = "-cert-v01@openssh.com";
+const NONCE_LENGTH: usize = 32;
+
+/// `valid_before` value of a certificate that never expires
+pub const SSH_CERT_FOREVER: u64 = u64::MAX;
+
+/// Supported CA key types, SSH signature formats and their matching signature algorithm.
+/// The first entry for a given key type is the default one.
+const SIGNATURE_FORMATS: &[(&str, &str, SignatureHashType)] = &[
+    (openssh::SSH_RSA, "rsa-sha2-512", SignatureHashType::RsaSha512),
+    (openssh::SSH_RSA, "rsa-sha2-256", SignatureHashType::RsaSha256),
+    (
+        openssh::ECDSA_SHA2_NISTP256,
+        openssh::ECDSA_SHA2_NISTP256,
+        SignatureHashType::EcdsaSha256,
+    ),
+    (
+        openssh::ECDSA_SHA2_NISTP384,
+        openssh::ECDSA_SHA2_NISTP384,
+        SignatureHashType::EcdsaSha384,
+    ),
+    (
+        openssh::ECDSA_SHA2_NISTP521,
+        openssh::ECDSA_SHA2_NISTP521,
+        SignatureHashType::EcdsaSha512,
+    ),
+    (openssh::SSH_ED25519, openssh::SSH_ED25519, SignatureHashType::Ed25519),
+];
+
+/// Critical options defined by OpenSSH for user certificates (none is defined for host certificates).
+/// Certificates with any other critical option are rejected by `SshCertificate::verify`.
+const USER_CRITICAL_OPTIONS: &[&str] = &["force-command", "source-address", "verify-required"];
+
+/// SHA-1 based formats, only accepted by `SshCertificate::verify_signature_allow_sha1`.
+/// OpenSSH stopped accepting them by default in release 8.8.
+const LEGACY_SIGNATURE_FORMATS: &[(&str, &str, SignatureHashType)] =
+    &[(openssh::SSH_RSA, "ssh-rsa", SignatureHashType::RsaSha1)];
+
+#[derive(Debug, Snafu)]
+pub enum SshCertError {
+    /// key error
+    #[snafu(display("key error: {}", source))]
+    Key { source: KeyError },
+
+    /// signature error
+    #[snafu(display("signature error: {}", source))]
+    Signature { source: SignatureError },
+
+    /// invalid certificate
+    #[snafu(display("invalid certificate: {}", context))]
+    InvalidCertificate { context: String },
+
+    /// unsupported signature algorithm
+    #[snafu(display("unsupported signature algorithm {} for {} CA key", algorithm, key_type))]
+    UnsupportedSignatureAlgorithm { algorithm: String, key_type: String },
+
+    /// missing required builder argument
+    #[snafu(display("missing required builder argument `{}`", arg))]
+    MissingBuilderArgument { arg: &'static str },
+
+    /// certificate is signed by another certificate authority
+    #[snafu(display("certificate is not signed by the expected certificate authority"))]
+    CaKeyMismatch,
+
+    /// certificate is not yet valid
+    #[snafu(display("certificate is not yet valid (valid after: {}, now: {})", valid_after, now))]
+    CertificateNotYetValid { valid_after: u64, now: u64 },
+
+    /// certificate expired
+    #[snafu(display("certificate expired (valid before: {}, now: {})", valid_before, now))]
+    CertificateExpired { valid_before: u64, now: u64 },
+
+    /// certificate has a critical option that isn't recognized
+    #[snafu(display("unsupported critical option `{}`", name))]
+    UnsupportedCriticalOption { name: String },
+
+    /// certificate isn't valid for the principal
+    #[snafu(display("certificate is not valid for principal `{}`", principal))]
+    PrincipalNotAllowed { principal: String },
+}
+
+impl From<KeyError> for SshCertError {
+    fn from(e: KeyError) -> Self {
+        Self::Key { source: e }
+    }
+}
+
+fn invalid_certificate<S: Into<String>>(context: S) -> SshCertError {
+    SshCertError::InvalidCertificate {
+        context: context.into(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SshCertType {
+    User,
+    Host,
+}
+
+impl SshCertType {
+    fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            1 => Some(Self::User),
+            2 => Some(Self::Host),
+            _ => None,
+        }
+    }
+
+    fn as_u32(self) -> u32 {
+        match self {
+            Self::User => 1,
+            Self::Host => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SshCertificate {
+    cert_key_type: String,
+    nonce: Vec<u8>,
+    public_key: PublicKey,
+    serial: u64,
+    cert_type: SshCertType,
+    key_id: String,
+    valid_principals: Vec<String>,
+    valid_after: u64,
+    valid_before: u64,
+    critical_options: Vec<(String, String)>,
+    extensions: Vec<(String, String)>,
+    signature_key: PublicKey,
+    signature: Vec<u8>,
+    tbs: Vec<u8>,
+}
+
+impl SshCertificate {
+    /// Parses an OpenSSH certificate line (`<cert type> <base64 certificate> [comment]`), as found
+    /// in `-cert.pub` files. The comment is ignored.
+    pub fn from_openssh(line: &str) -> Result<Self, SshCertError> {
+        let mut parts = line.split_whitespace();
+        let key_type = parts.next().ok_or_else(|| invalid_certificate("empty certificate"))?;
+        let blob = parts
+            .next()
+            .ok_or_else(|| invalid_certificate("missing certificate data"))?;
+        let blob = base64::decode(blob).map_err(|e| invalid_certificate(format!("invalid certificate data: {}", e)))?;
+
+        if SshReader::new(&blob).read_str()? != key_type {
+            return Err(invalid_certificate("certificate data doesn't match certificate type"));
+        }
+
+        Self::from_openssh_blob(&blob)
+    }
+
+    /// Formats the certificate as an OpenSSH certificate line. `comment` may be empty.
+    pub fn to_openssh(&self, comment: &str) -> String {
+        let mut line = format!("{} {}", self.cert_key_type(), base64::encode(&self.to_openssh_blob()));
+        if !comment.is_empty() {
+            line.push(' ');
+            line.push_str(comment);
+        }
+        line
+    }
+
+    /// Parses a certificate in the SSH wire format.
+    pub fn from_openssh_blob<T: ?Sized + AsRef<[u8]>>(blob: &T) -> Result<Self, SshCertError> {
+        let blob = blob.as_ref();
+        let mut reader = SshReader::new(blob);
+
+        let cert_key_type = reader.read_str()?.to_owned();
+        let key_type = cert_key_type
+            .strip_suffix(CERT_KEY_TYPE_SUFFIX)
+            .ok_or_else(|| invalid_certificate(format!("unsupported certificate type: {}", cert_key_type)))?;
+        let nonce = reader.read_string()?.to_vec();
+
+        // public key fields are inlined in the certificate right after the nonce
+        let key_field_count = match key_type {
+            openssh::SSH_ED25519 => 1,
+            _ => 2,
+        };
+        let mut key_writer = SshWriter::new();
+        key_writer.write_str(key_type);
+        for _ in 0..key_field_count {
+            key_writer.write_string(reader.read_string()?);
+        }
+        let public_key = PublicKey::from_openssh_blob(&key_writer.into_bytes())?;
+
+        let serial = reader.read_u64()?;
+        let cert_type = reader.read_u32()?;
+        let cert_type = SshCertType::from_u32(cert_type)
+            .ok_or_else(|| invalid_certificate(format!("unknown certificate type: {}", cert_type)))?;
+        let key_id = reader.read_str()?.to_owned();
+        let valid_principals = read_principals(reader.read_string()?)?;
+        let valid_after = reader.read_u64()?;
+        let valid_before = reader.read_u64()?;
+        let critical_options = read_options(reader.read_string()?)?;
+        let extensions = read_options(reader.read_string()?)?;
+        let _reserved = reader.read_string()?;
+        let signature_key = PublicKey::from_openssh_blob(reader.read_string()?)?;
+        let tbs = blob[..blob.len() - reader.remaining().len()].to_vec();
+        let signature = reader.read_string()?.to_vec();
+
+        if !reader.is_empty() {
+            return Err(invalid_certificate("trailing data after certificate"));
+        }
+
+        Ok(Self {
+            cert_key_type,
+            nonce,
+            public_key,
+            serial,
+            cert_type,
+            key_id,
+            valid_principals,
+            valid_after,
+            valid_before,
+            critical_options,
+            extensions,
+            signature_key,
+            signature,
+            tbs,
+        })
+    }
+
+    /// Serializes the certificate in the SSH wire format.
+    pub fn to_openssh_blob(&self) -> Vec<u8> {
+        let mut writer = SshWriter::new();
+        writer.write_bytes(&self.tbs);
+        writer.write_string(&self.signature);
+        writer.into_bytes()
+    }
+
+    /// Certificate type as found in the wire format (e.g. `ssh-ed25519-cert-v01@openssh.com`).
+    pub fn cert_key_type(&self) -> &str {
+        &self.cert_key_type
+    }
+
+    /// Certified public key.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    pub fn serial(&self) -> u64 {
+        self.serial
+    }
+
+    pub fn cert_type(&self) -> SshCertType {
+        self.cert_type
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// User names or host names this certificate is valid for. Empty means any.
+    pub fn valid_principals(&self) -> &[String] {
+        &self.valid_principals
+    }
+
+    pub fn valid_after(&self) -> u64 {
+        self.valid_after
+    }
+
+    pub fn valid_before(&self) -> u64 {
+        self.valid_before
+    }
+
+    /// Critical options as `(name, value)` pairs. Flags have an empty value.
+    pub fn critical_options(&self) -> &[(String, String)] {
+        &self.critical_options
+    }
+
+    /// Extensions as `(name, value)` pairs. Flags have an empty value.
+    pub fn extensions(&self) -> &[(String, String)] {
+        &self.extensions
+    }
+
+    /// Public key of the certificate authority that signed this certificate.
+    pub fn signature_key(&self) -> &PublicKey {
+        &self.signature_key
+    }
+
+    /// Verifies the certificate signature against the embedded CA key.
+    ///
+    /// SHA-1 `ssh-rsa` signatures are rejected.
+    pub fn verify_signature(&self) -> Result<(), SshCertError> {
+        self.verify_signature_impl(false)
+    }
+
+    /// Same as `verify_signature`, but also accepts legacy SHA-1 `ssh-rsa` signatures.
+    pub fn verify_signature_allow_sha1(&self) -> Result<(), SshCertError> {
+        self.verify_signature_impl(true)
+    }
+
+    fn verify_signature_impl(&self, allow_sha1: bool) -> Result<(), SshCertError> {
+        let ca_key_type = openssh_key_type(&self.signature_key)?;
+
+        let mut reader = SshReader::new(&self.signature);
+        let format = reader.read_str()?;
+        let blob = reader.read_string()?;
+        if !reader.is_empty() {
+            return Err(invalid_certificate("trailing data after signature"));
+        }
+
+        let legacy_formats = if allow_sha1 { LEGACY_SIGNATURE_FORMATS } else { &[] };
+        let signature_hash_type = SIGNATURE_FORMATS
+            .iter()
+            .chain(legacy_formats)
+            .find(|(key_type, f, _)| *key_type == ca_key_type && *f == format)
+            .map(|(_, _, signature_hash_type)| *signature_hash_type)
+            .ok_or_else(|| SshCertError::UnsupportedSignatureAlgorithm {
+                algorithm: format.to_owned(),
+                key_type: ca_key_type.clone(),
+            })?;
+
+        let signature = if ca_key_type.starts_with("ecdsa-sha2-") {
+            ecdsa_signature_to_der(blob)?
+        } else {
+            blob.to_vec()
+        };
+
+        signature_hash_type
+            .verify(&self.signature_key, &self.tbs, &signature)
+            .context(Signature)
+    }
+
+    /// Verifies the certificate was issued by `ca_key` and is valid at `now` (seconds since
+    /// the UNIX epoch).
+    ///
+    /// As OpenSSH does, certificates with critical options it doesn't define are rejected. The
+    /// restrictions of the recognized ones (`force-command`, `source-address` and
+    /// `verify-required`) must still be enforced by the caller (see `critical_options`).
+    ///
+    /// Principals are not checked: see `verify_for_principal`.
+    pub fn verify(&self, ca_key: &PublicKey, now: u64) -> Result<(), SshCertError> {
+        if &self.signature_key != ca_key {
+            return Err(SshCertError::CaKeyMismatch);
+        }
+
+        self.verify_signature()?;
+
+        let known_critical_options: &[&str] = match self.cert_type {
+            SshCertType::User => USER_CRITICAL_OPTIONS,
+            SshCertType::Host => &[],
+        };
+        if let Some((name, _)) = self
+            .critical_options
+            .iter()
+            .find(|(name, _)| !known_critical_options.contains(&name.as_str()))
+        {
+            return Err(SshCertError::UnsupportedCriticalOption { name: name.clone() });
+        }
+
+        if now < self.valid_after {
+            return Err(SshCertError::CertificateNotYetValid {
+                valid_after: self.valid_after,
+                now,
+            });
+        }
+
+        if now >= self.valid_before {
+            return Err(SshCertError::CertificateExpired {
+                valid_before: self.valid_before,
+                now,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Same as `verify`, and also checks `principal` (user name or host name) is one of the
+    /// certificate valid principals.
+    ///
+    /// Certificates without any principal are rejected, as OpenSSH does when authenticating users.
+    pub fn verify_for_principal(&self, ca_key: &PublicKey, principal: &str, now: u64) -> Result<(), SshCertError> {
+        self.verify(ca_key, now)?;
+
+        if !self
+            .valid_principals
+            .iter()
+            .any(|valid_principal| valid_principal == principal)
+        {
+            return Err(SshCertError::PrincipalNotAllowed {
+                principal: principal.to_owned(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn openssh_key_type(public_key: &PublicKey) -> Result<String, SshCertError> {
+    let blob = public_key.to_openssh_blob()?;
+    Ok(SshReader::new(&blob).read_str()?.to_owned())
+}
+
+fn read_principals(data: &[u8]) -> Result<Vec<String>, SshCertError> {
+    let mut reader = SshReader::new(data);
+    let mut principals = Vec::new();
+    while !reader.is_empty() {
+        principals.push(reader.read_str()?.to_owned());
+    }
+    Ok(principals)
+}
+
+fn write_principals(principals: &[String]) -> Vec<u8> {
+    let mut writer = SshWriter::new();
+    for principal in principals {
+        writer.write_str(principal);
+    }
+    writer.into_bytes()
+}
+
+fn read_options(data: &[u8]) -> Result<Vec<(String, String)>, SshCertError> {
+    let mut reader = SshReader::new(data);
+    let mut options = Vec::new();
+    while !reader.is_empty() {
+        let name = reader.read_str()?.to_owned();
+        let data = reader.read_string()?;
+        let value = if data.is_empty() {
+            String::new()
+        } else {
+            let mut data_reader = SshReader::new(data);
+            let value = data_reader.read_str()?.to_owned();
+            if !data_reader.is_empty() {
+                return Err(invalid_certificate(format!("trailing data in option `{}`", name)));
+            }
+            value
+        };
+        options.push((name, value));
+    }
+    Ok(options)
+}
+
+fn write_options(options: &[(String, String)]) -> Vec<u8> {
+    let mut writer = SshWriter::new();
+    for (name, value) in options {
+        writer.write_str(name);
+        if value.is_empty() {
+            writer.write_string(&[]);
+        } else {
+            let mut value_writer = SshWriter::new();
+            value_writer.write_str(value);
+            writer.write_string(&value_writer.into_bytes());
+        }
+    }
+    writer.into_bytes()
+}
+
+/// ECDSA signature as produced and expected by `SignatureHashType`
+#[derive(Serialize, Deserialize)]
+struct EcdsaSignature {
+    r: IntegerAsn1,
+    s: IntegerAsn1,
+}
+
+fn ecdsa_signature_to_der(blob: &[u8]) -> Result<Vec<u8>, SshCertError> {
+    fn integer(magnitude: &[u8]) -> IntegerAsn1 {
+        let mut bytes = Vec::with_capacity(magnitude.len() + 1);
+        if magnitude.first().map(|b| b & 0x80 != 0).unwrap_or(true) {
+            bytes.push(0);
+        }
+        bytes.extend_from_slice(magnitude);
+        IntegerAsn1::from(bytes)
+    }
+
+    let mut reader = SshReader::new(blob);
+    let r = reader.read_mpint()?;
+    let s = reader.read_mpint()?;
+    if !reader.is_empty() {
+        return Err(invalid_certificate("trailing data after ECDSA signature"));
+    }
+
+    picky_asn1_der::to_vec(&EcdsaSignature {
+        r: integer(r),
+        s: integer(s),
+    })
+    .map_err(|e| invalid_certificate(format!("couldn't encode ECDSA signature: {}", e)))
+}
+
+fn ecdsa_signature_from_der(der: &[u8]) -> Result<Vec<u8>, SshCertError> {
+    let signature: EcdsaSignature = picky_asn1_der::from_bytes(der)
+        .map_err(|e| invalid_certificate(format!("couldn't decode ECDSA signature: {}", e)))?;
+
+    let mut writer = SshWriter::new();
+    writer.write_mpint(signature.r.as_bytes_be());
+    writer.write_mpint(signature.s.as_bytes_be());
+    Ok(writer.into_bytes())
+}
+
+// === certificate builder === //
+
+struct CaKey<'a>(&'a dyn Signer);
+
+impl fmt::Debug for CaKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<signer>")
+    }
+}
+
+impl Clone for CaKey<'_> {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+macro_rules! field_str {
+    ($field:ident) => {{
+        ::static_assertions::assert_fields!(SshCertificateBuilderInner: $field);
+        stringify!($field)
+    }};
+}
+
+#[derive(Default, Clone, Debug)]
+struct SshCertificateBuilderInner<'a> {
+    cert_type: Option<SshCertType>,
+    public_key: Option<PublicKey>,
+    serial: Option<u64>,
+    key_id: Option<String>,
+    valid_principals: Option<Vec<String>>,
+    valid_after: Option<u64>,
+    valid_before: Option<u64>,
+    critical_options: Vec<(String, String)>,
+    extensions: Vec<(String, String)>,
+    ca_key: Option<CaKey<'a>>,
+    signature_hash_type: Option<SignatureHashType>,
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct SshCertificateBuilder<'a> {
+    inner: RefCell<SshCertificateBuilderInner<'a>>,
+}
+
+impl<'a> SshCertificateBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Required
+    #[inline]
+    pub fn cert_type(&self, cert_type: SshCertType) -> &Self {
+        self.inner.borrow_mut().cert_type = Some(cert_type);
+        self
+    }
+
+    /// Required
+    #[inline]
+    pub fn public_key(&self, public_key: PublicKey) -> &Self {
+        self.inner.borrow_mut().public_key = Some(public_key);
+        self
+    }
+
+    /// Required. Use `SSH_CERT_FOREVER` as `valid_before` for a certificate that never expires.
+    #[inline]
+    pub fn validity(&self, valid_after: u64, valid_before: u64) -> &Self {
+        let mut inner_mut = self.inner.borrow_mut();
+        inner_mut.valid_after = Some(valid_after);
+        inner_mut.valid_before = Some(valid_before);
+        drop(inner_mut);
+        self
+    }
+
+    /// Required
+    #[inline]
+    pub fn ca_key(&self, ca_key: &'a dyn Signer) -> &Self {
+        self.inner.borrow_mut().ca_key = Some(CaKey(ca_key));
+        self
+    }
+
+    /// Optional (defaults to a random serial)
+    #[inline]
+    pub fn serial(&self, serial: u64) -> &Self {
+        self.inner.borrow_mut().serial = Some(serial);
+        self
+    }
+
+    /// Optional (defaults to an empty key id)
+    #[inline]
+    pub fn key_id<S: Into<String>>(&self, key_id: S) -> &Self {
+        self.inner.borrow_mut().key_id = Some(key_id.into());
+        self
+    }
+
+    /// Optional (defaults to no principal, which `SshCertificate::verify_for_principal` rejects)
+    #[inline]
+    pub fn valid_principals(&self, valid_principals: Vec<String>) -> &Self {
+        self.inner.borrow_mut().valid_principals = Some(valid_principals);
+        self
+    }
+
+    /// Optional. Use an empty `value` for flags.
+    #[inline]
+    pub fn critical_option<N: Into<String>, V: Into<String>>(&self, name: N, value: V) -> &Self {
+        self.inner
+            .borrow_mut()
+            .critical_options
+            .push((name.into(), value.into()));
+        self
+    }
+
+    /// Optional. Use an empty `value` for flags (e.g. `permit-pty`).
+    #[inline]
+    pub fn extension<N: Into<String>, V: Into<String>>(&self, name: N, value: V) -> &Self {
+        self.inner.borrow_mut().extensions.push((name.into(), value.into()));
+        self
+    }
+
+    /// Optional (defaults to `rsa-sha2-512` for RSA CA keys and to the CA key algorithm otherwise)
+    #[inline]
+    pub fn signature_hash_type(&self, signature_hash_type: SignatureHashType) -> &Self {
+        self.inner.borrow_mut().signature_hash_type = Some(signature_hash_type);
+        self
+    }
+
+    pub fn build(&self) -> Result<SshCertificate, SshCertError> {
+        use rand_core::RngCore;
+
+        let mut inner = self.inner.borrow_mut();
+
+        let cert_type = inner.cert_type.take().ok_or(SshCertError::MissingBuilderArgument {
+            arg: field_str!(cert_type),
+        })?;
+        let public_key = inner.public_key.take().ok_or(SshCertError::MissingBuilderArgument {
+            arg: field_str!(public_key),
+        })?;
+        let valid_after = inner.valid_after.take().ok_or(SshCertError::MissingBuilderArgument {
+            arg: field_str!(valid_after),
+        })?;
+        let valid_before = inner.valid_before.take().ok_or(SshCertError::MissingBuilderArgument {
+            arg: field_str!(valid_before),
+        })?;
+        let ca_key = inner.ca_key.take().ok_or(SshCertError::MissingBuilderArgument {
+            arg: field_str!(ca_key),
+        })?;
+
+        let serial = inner.serial.take().unwrap_or_else(|| rand_core::OsRng.next_u64());
+        let key_id = inner.key_id.take().unwrap_or_default();
+        let valid_principals = inner.valid_principals.take().unwrap_or_default();
+        let critical_options = sorted_options(std::mem::take(&mut inner.critical_options))?;
+        let extensions = sorted_options(std::mem::take(&mut inner.extensions))?;
+
+        let signature_key = ca_key.0.public_key();
+        let ca_key_type = openssh_key_type(&signature_key)?;
+        let (format, signature_hash_type) = SIGNATURE_FORMATS
+            .iter()
+            .find(|(key_type, _, signature_hash_type)| {
+                *key_type == ca_key_type
+                    && inner
+                        .signature_hash_type
+                        .map(|requested| requested == *signature_hash_type)
+                        .unwrap_or(true)
+            })
+            .map(|(_, format, signature_hash_type)| (*format, *signature_hash_type))
+            .ok_or_else(|| SshCertError::UnsupportedSignatureAlgorithm {
+                algorithm: inner
+                    .signature_hash_type
+                    .map(|signature_hash_type| format!("{:?}", signature_hash_type))
+                    .unwrap_or_else(|| "(default)".to_owned()),
+                key_type: ca_key_type.clone(),
+            })?;
+
+        let mut nonce = vec![0; NONCE_LENGTH];
+        rand_core::OsRng.fill_bytes(&mut nonce);
+
+        let public_key_blob = public_key.to_openssh_blob()?;
+        let mut public_key_reader = SshReader::new(&public_key_blob);
+        let key_type = public_key_reader.read_str()?;
+
+        let cert_key_type = format!("{}{}", key_type, CERT_KEY_TYPE_SUFFIX);
+        let mut writer = SshWriter::new();
+        writer.write_str(&cert_key_type);
+        writer.write_string(&nonce);
+        writer.write_bytes(public_key_reader.remaining());
+        writer.write_u64(serial);
+        writer.write_u32(cert_type.as_u32());
+        writer.write_str(&key_id);
+        writer.write_string(&write_principals(&valid_principals));
+        writer.write_u64(valid_after);
+        writer.write_u64(valid_before);
+        writer.write_string(&write_options(&critical_options));
+        writer.write_string(&write_options(&extensions));
+        writer.write_string(&[]);
+        writer.write_string(&signature_key.to_openssh_blob()?);
+        let tbs = writer.into_bytes();
+
+        let signature = ca_key.0.sign(&tbs, signature_hash_type).context(Signature)?;
+        let signature = if ca_key_type.starts_with("ecdsa-sha2-") {
+            ecdsa_signature_from_der(&signature)?
+        } else {
+            signature
+        };
+
+        let mut signature_writer = SshWriter::new();
+        signature_writer.write_str(format);
+        signature_writer.write_string(&signature);
+
+        Ok(SshCertificate {
+            cert_key_type,
+            nonce,
+            public_key,
+            serial,
+            cert_type,
+            key_id,
+            valid_principals,
+            valid_after,
+            valid_before,
+            critical_options,
+            extensions,
+            signature_key,
+            signature: signature_writer.into_bytes(),
+            tbs,
+        })
+    }
+}
+
+/// Critical options and extensions must be sorted by name and appear at most once.
+fn sorted_options(mut options: Vec<(String, String)>) -> Result<Vec<(String, String)>, SshCertError> {
+    options.sort_by(|(a, _), (b, _)| a.cmp(b));
+    if let Some(window) = options.windows(2).find(|window| window[0].0 == window[1].0) {
+        return Err(invalid_certificate(format!("duplicated option `{}`", window[0].0)));
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{key::PrivateKey, pem::Pem, test_files};
+
+    // 2025-01-01T00:00:00Z
+    const NOW: u64 = 1_735_689_600;
+
+    fn private_key(pem_str: &str) -> PrivateKey {
+        PrivateKey::from_pem(&pem_str.parse::<Pem>().expect("pem")).expect("private key")
+    }
+
+    #[test]
+    fn parse_user_certificate() {
+        let cert = SshCertificate::from_openssh(test_files::SSH_ED25519_PK_1_USER_CERT).expect("ssh certificate");
+        let ca_key = PublicKey::from_openssh(test_files::SSH_RSA_2048_PK_1_PUB).expect("openssh public key");
+
+        assert_eq!(cert.cert_key_type(), "ssh-ed25519-cert-v01@openssh.com");
+        assert_eq!(
+            cert.public_key(),
+            &PublicKey::from_openssh(test_files::SSH_ED25519_PK_1_PUB).expect("openssh public key")
+        );
+        assert_eq!(cert.cert_type(), SshCertType::User);
+        assert_eq!(cert.serial(), 42);
+        assert_eq!(cert.key_id(), "alice@picky");
+        assert_eq!(cert.valid_principals(), &["alice".to_owned(), "admin".to_owned()]);
+        assert_eq!(cert.valid_after(), 1_577_836_800);
+        assert_eq!(cert.valid_before(), 1_893_456_000);
+        assert_eq!(
+            cert.critical_options(),
+            &[
+                ("force-command".to_owned(), "/bin/true".to_owned()),
+                ("source-address".to_owned(), "10.0.0.0/8".to_owned()),
+            ]
+        );
+        assert_eq!(cert.extensions().len(), 5);
+        assert_eq!(cert.extensions()[3], ("permit-pty".to_owned(), String::new()));
+        assert_eq!(cert.signature_key(), &ca_key);
+
+        cert.verify(&ca_key, NOW).expect("verify certificate");
+        assert_eq!(
+            SshCertificate::from_openssh_blob(&cert.to_openssh_blob()).expect("ssh certificate"),
+            cert
+        );
+    }
+
+    #[test]
+    fn parse_host_certificate() {
+        let cert = SshCertificate::from_openssh(test_files::SSH_EC_P384_PK_1_HOST_CERT).expect("ssh certificate");
+        let ca_key = PublicKey::from_openssh(test_files::SSH_EC_P256_PK_1_PUB).expect("openssh public key");
+
+        assert_eq!(cert.cert_type(), SshCertType::Host);
+        assert_eq!(cert.serial(), 1337);
+        assert_eq!(
+            cert.valid_principals(),
+            &["host.picky.com".to_owned(), "picky.com".to_owned()]
+        );
+        assert!(cert.critical_options().is_empty());
+        assert!(cert.extensions().is_empty());
+
+        cert.verify(&ca_key, NOW).expect("verify certificate");
+        assert_eq!(
+            cert.to_openssh("ec-p384-pk_1@picky"),
+            test_files::SSH_EC_P384_PK_1_HOST_CERT.trim_end()
+        );
+    }
+
+    #[test]
+    fn verify_errors() {
+        let cert = SshCertificate::from_openssh(test_files::SSH_ED25519_PK_1_USER_CERT).expect("ssh certificate");
+        let ca_key = PublicKey::from_openssh(test_files::SSH_RSA_2048_PK_1_PUB).expect("openssh public key");
+        let other_ca_key = PublicKey::from_openssh(test_files::SSH_EC_P256_PK_1_PUB).expect("openssh public key");
+
+        let err = cert.verify(&other_ca_key, NOW).expect_err("error");
+        assert!(matches!(err, SshCertError::CaKeyMismatch));
+
+        let err = cert.verify(&ca_key, 0).expect_err("error");
+        assert_eq!(
+            err.to_string(),
+            "certificate is not yet valid (valid after: 1577836800, now: 0)"
+        );
+
+        let err = cert.verify(&ca_key, 1_893_456_000).expect_err("error");
+        assert_eq!(
+            err.to_string(),
+            "certificate expired (valid before: 1893456000, now: 1893456000)"
+        );
+
+        let mut blob = cert.to_openssh_blob();
+        let key_id = blob
+            .windows(11)
+            .position(|window| window == b"alice@picky")
+            .expect("key id");
+        blob[key_id] = b'A';
+        let tampered = SshCertificate::from_openssh_blob(&blob).expect("ssh certificate");
+        let err = tampered.verify(&ca_key, NOW).expect_err("error");
+        assert_eq!(err.to_string(), "signature error: invalid signature");
+    }
+
+    #[test]
+    fn verify_for_principal() {
+        let cert = SshCertificate::from_openssh(test_files::SSH_ED25519_PK_1_USER_CERT).expect("ssh certificate");
+        let ca_key = PublicKey::from_openssh(test_files::SSH_RSA_2048_PK_1_PUB).expect("openssh public key");
+
+        cert.verify_for_principal(&ca_key, "admin", NOW)
+            .expect("verify certificate");
+
+        let err = cert.verify_for_principal(&ca_key, "bob", NOW).expect_err("error");
+        assert_eq!(err.to_string(), "certificate is not valid for principal `bob`");
+
+        let issuer = private_key(test_files::SSH_EC_P256_PK_1);
+        let no_principal = SshCertificateBuilder::new()
+            .cert_type(SshCertType::User)
+            .public_key(cert.public_key().clone())
+            .validity(NOW, NOW + 3600)
+            .ca_key(&issuer)
+            .build()
+            .expect("build certificate");
+        no_principal
+            .verify(&issuer.to_public_key(), NOW)
+            .expect("verify certificate");
+        let err = no_principal
+            .verify_for_principal(&issuer.to_public_key(), "bob", NOW)
+            .expect_err("error");
+        assert_eq!(err.to_string(), "certificate is not valid for principal `bob`");
+    }
+
+    #[test]
+    fn unsupported_critical_options() {
+        let ca_key = private_key(test_files::SSH_EC_P256_PK_1);
+        let public_key = PublicKey::from_openssh(test_files::SSH_ED25519_PK_1_PUB).expect("openssh public key");
+
+        let build = |cert_type: SshCertType, critical_option: &str| {
+            SshCertificateBuilder::new()
+                .cert_type(cert_type)
+                .public_key(public_key.clone())
+                .validity(NOW, NOW + 3600)
+                .valid_principals(vec!["bob".to_owned()])
+                .critical_option(critical_option, "")
+                .ca_key(&ca_key)
+                .build()
+                .expect("build certificate")
+        };
+
+        build(SshCertType::User, "verify-required")
+            .verify(&ca_key.to_public_key(), NOW)
+            .expect("verify certificate");
+
+        let err = build(SshCertType::User, "no-restrictions@picky")
+            .verify(&ca_key.to_public_key(), NOW)
+            .expect_err("error");
+        assert_eq!(err.to_string(), "unsupported critical option `no-restrictions@picky`");
+
+        // OpenSSH doesn't define any critical option for host certificates
+        let err = build(SshCertType::Host, "force-command")
+            .verify(&ca_key.to_public_key(), NOW)
+            .expect_err("error");
+        assert_eq!(err.to_string(), "unsupported critical option `force-command`");
+    }
+
+    fn check_issued_certificate(ca_key: &PrivateKey, public_key: PublicKey, expected_format: &str) {
+        let cert = SshCertificateBuilder::new()
+            .cert_type(SshCertType::User)
+            .public_key(public_key.clone())
+            .validity(NOW, NOW + 3600)
+            .key_id("bob@picky")
+            .valid_principals(vec!["bob".to_owned()])
+            .extension("permit-pty", "")
+            .extension("permit-agent-forwarding", "")
+            .critical_option("force-command", "/usr/bin/whoami")
+            .ca_key(ca_key)
+            .build()
+            .expect("build certificate");
+
+        assert_eq!(cert.public_key(), &public_key);
+        assert_eq!(cert.extensions()[0].0, "permit-agent-forwarding");
+        cert.verify(&ca_key.to_public_key(), NOW + 60)
+            .expect("verify certificate");
+
+        let parsed = SshCertificate::from_openssh(&cert.to_openssh("bob@picky")).expect("ssh certificate");
+        assert_eq!(parsed, cert);
+        assert_eq!(
+            SshReader::new(&parsed.signature).read_str().expect("signature format"),
+            expected_format
+        );
+    }
+
+    #[test]
+    fn build_certificate() {
+        let rsa_ca = private_key(test_files::SSH_RSA_2048_PK_1);
+        let ec_ca = private_key(test_files::SSH_EC_P256_PK_1);
+        let ed_ca = private_key(test_files::SSH_ED25519_PK_1);
+        let ec_p521_ca = private_key(test_files::SSH_EC_P521_PK_1);
+
+        let ed_key = PublicKey::from_openssh(test_files::SSH_ED25519_PK_1_PUB).expect("openssh public key");
+        let ec_key = PublicKey::from_openssh(test_files::SSH_EC_P521_PK_1_PUB).expect("openssh public key");
+        let rsa_key = PublicKey::from_openssh(test_files::SSH_RSA_2048_PK_1_PUB).expect("openssh public key");
+
+        check_issued_certificate(&rsa_ca, ed_key.clone(), "rsa-sha2-512");
+        check_issued_certificate(&ec_ca, rsa_key, "ecdsa-sha2-nistp256");
+        check_issued_certificate(&ed_ca, ec_key, "ssh-ed25519");
+        check_issued_certificate(&ec_p521_ca, ed_key, "ecdsa-sha2-nistp521");
+    }
+
+    #[test]
+    fn sha1_signature_requires_opt_in() {
+        let ca_key = private_key(test_files::SSH_RSA_2048_PK_1);
+        let public_key = PublicKey::from_openssh(test_files::SSH_ED25519_PK_1_PUB).expect("openssh public key");
+
+        let cert = SshCertificateBuilder::new()
+            .cert_type(SshCertType::User)
+            .public_key(public_key)
+            .validity(NOW, NOW + 3600)
+            .valid_principals(vec!["bob".to_owned()])
+            .ca_key(&ca_key)
+            .build()
+            .expect("build certificate");
+
+        // re-sign the certificate the way OpenSSH did before release 8.8
+        let signature = ca_key
+            .sign(&cert.tbs, SignatureHashType::RsaSha1)
+            .expect("rsa-sha1 signature");
+        let mut signature_writer = SshWriter::new();
+        signature_writer.write_str("ssh-rsa");
+        signature_writer.write_string(&signature);
+        let mut writer = SshWriter::new();
+        writer.write_bytes(&cert.tbs);
+        writer.write_string(&signature_writer.into_bytes());
+        let cert = SshCertificate::from_openssh_blob(&writer.into_bytes()).expect("ssh certificate");
+
+        let err = cert.verify(&ca_key.to_public_key(), NOW).expect_err("error");
+        assert_eq!(
+            err.to_string(),
+            "unsupported signature algorithm ssh-rsa for ssh-rsa CA key"
+        );
+        cert.verify_signature_allow_sha1().expect("verify sha1 signature");
+
+        let err = SshCertificateBuilder::new()
+            .cert_type(SshCertType::User)
+            .public_key(cert.public_key().clone())
+            .validity(NOW, NOW + 3600)
+            .ca_key(&ca_key)
+            .signature_hash_type(SignatureHashType::RsaSha1)
+            .build()
+            .expect_err("error");
+        assert_eq!(
+            err.to_string(),
+            "unsupported signature algorithm RsaSha1 for ssh-rsa CA key"
+        );
+    }
+
+    #[test]
+    fn build_certificate_errors() {
+        let ca_key = private_key(test_files::SSH_EC_P256_PK_1);
+        let public_key = PublicKey::from_openssh(test_files::SSH_ED25519_PK_1_PUB).expect("openssh public key");
+
+        let err = SshCertificateBuilder::new()
+            .cert_type(SshCertType::Host)
+            .public_key(public_key.clone())
+            .ca_key(&ca_key)
+            .build()
+            .expect_err("error");
+        assert_eq!(err.to_string(), "missing required builder argument `valid_after`");
+
+        let err = SshCertificateBuilder::new()
+            .cert_type(SshCertType::Host)
+            .public_key(public_key.clone())
+            .validity(NOW, SSH_CERT_FOREVER)
+            .ca_key(&ca_key)
+            .signature_hash_type(SignatureHashType::RsaSha256)
+            .build()
+            .expect_err("error");
+        assert_eq!(
+            err.to_string(),
+            "unsupported signature algorithm RsaSha256 for ecdsa-sha2-nistp256 CA key"
+        );
+
+        let err = SshCertificateBuilder::new()
+            .cert_type(SshCertType::Host)
+            .public_key(public_key)
+            .validity(NOW, SSH_CERT_FOREVER)
+            .ca_key(&ca_key)
+            .extension("permit-pty", "")
+            .extension("permit-pty", "")
+            .build()
+            .expect_err("error");
+        assert_eq!(err.to_string(), "invalid certificate: duplicated option `permit-pty`");
+    }
+}
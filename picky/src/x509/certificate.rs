@@ -1,17 +1,22 @@
 use crate::{
-    key::{PrivateKey, PublicKey},
+    algorithm_identifier::HashAlgorithm,
+    key::{KeyError, PublicKey},
     oids,
     pem::Pem,
-    signature::{SignatureError, SignatureHashType},
+    signature::{SignatureError, SignatureHashType, Signer},
     x509::{
+        chain::ChainValidationSettings,
         csr::{Csr, CsrError},
         date::UTCDate,
         extension::{
-            AuthorityKeyIdentifier, BasicConstraints, ExtendedKeyUsage, ExtensionView, KeyIdentifier, KeyUsage,
+            AuthorityInfoAccess, AuthorityKeyIdentifier, BasicConstraints, CRLDistributionPoints, CRLReason,
+            CertificatePolicies, ExtendedKeyUsage, ExtensionView, InhibitAnyPolicy, KeyIdentifier, KeyUsage,
+            NameConstraints, PolicyConstraints, PolicyMappings,
         },
         key_id_gen_method::{KeyIdGenError, KeyIdGenMethod, KeyIdHashAlgo},
-        name::{DirectoryName, GeneralNames},
+        name::{DirectoryName, GeneralName, GeneralNames},
         private::{certificate::TBSCertificate, Certificate, Validity, Version},
+        revocation::RevocationError,
         Extension, Extensions,
     },
     AlgorithmIdentifier,
@@ -19,7 +24,11 @@ use crate::{
 use picky_asn1::{bit_string::BitString, wrapper::IntegerAsn1};
 use picky_asn1_der::Asn1DerError;
 use snafu::{ResultExt, Snafu};
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+};
 
 #[derive(Debug, Snafu)]
 pub enum CertError {
@@ -68,6 +77,10 @@ pub enum CertError {
     #[snafu(display("CSR error: {}", source))]
     InvalidCsr { source: CsrError },
 
+    /// invalid public key
+    #[snafu(display("invalid public key: {}", source))]
+    InvalidPublicKey { source: KeyError },
+
     /// extension not found
     #[snafu(display("extension not found: {}", name))]
     ExtensionNotFound { name: &'static str },
@@ -102,6 +115,10 @@ pub enum CaChainError {
     /// chain is missing a root certificate
     NoRoot,
 
+    /// root certificate is not a trust anchor
+    #[snafu(display("root certificate '{}' is not in the trust store", cert_id))]
+    UntrustedRoot { cert_id: String },
+
     /// issuer certificate is not a CA
     #[snafu(display("issuer certificate '{}' is not a CA", issuer_id))]
     IssuerIsNotCA { issuer_id: String },
@@ -113,6 +130,79 @@ pub enum CaChainError {
     /// issuer name doesn't match
     #[snafu(display("issuer name doesn't match (expected: {}, got: {})", expected, actual))]
     IssuerNameMismatch { expected: String, actual: String },
+
+    /// no certification path found
+    #[snafu(display("no certification path found from '{}' to a trust anchor", cert_id))]
+    NoPathFound { cert_id: String },
+
+    /// path search limit exceeded
+    #[snafu(display("too many candidate paths to search from '{}'", cert_id))]
+    PathSearchLimitExceeded { cert_id: String },
+
+    /// unsupported critical name constraints
+    #[snafu(display("critical name constraints of '{}' are not supported", issuer_id))]
+    UnsupportedNameConstraints { issuer_id: String },
+
+    /// name constraints violation
+    #[snafu(display("certificate '{}' violates name constraints of '{}': {}", cert_id, issuer_id, name))]
+    NameConstraintViolation {
+        cert_id: String,
+        issuer_id: String,
+        name: String,
+    },
+
+    /// no valid certificate policy
+    #[snafu(display("no valid certificate policy for '{}'", cert_id))]
+    NoValidPolicy { cert_id: String },
+
+    /// too many nodes in the valid policy tree
+    #[snafu(display("too many certificate policies to process for '{}'", cert_id))]
+    PolicyTreeTooLarge { cert_id: String },
+
+    /// policy mapping to or from anyPolicy
+    #[snafu(display("certificate '{}' maps a policy to or from anyPolicy", cert_id))]
+    AnyPolicyMapping { cert_id: String },
+
+    /// certificate is revoked
+    #[snafu(display("certificate '{}' was revoked on {}", cert_id, revocation_time))]
+    CertificateRevoked {
+        cert_id: String,
+        revocation_time: UTCDate,
+        reason: Option<CRLReason>,
+    },
+
+    /// revocation status is unknown
+    #[snafu(display("revocation status of certificate '{}' is unknown", cert_id))]
+    RevocationStatusUnknown { cert_id: String },
+
+    /// revocation checking failed
+    #[snafu(display("couldn't check revocation status of certificate '{}': {}", cert_id, source))]
+    RevocationCheckFailed {
+        cert_id: String,
+        #[snafu(source(from(RevocationError, Box::new)))]
+        source: Box<RevocationError>,
+    },
+
+    /// issuer key usage doesn't allow certificate signing
+    #[snafu(display("issuer certificate '{}' key usage doesn't allow certificate signing", issuer_id))]
+    IssuerCannotSignCertificates { issuer_id: String },
+
+    /// key usage isn't consistent with the intended purpose
+    #[snafu(display(
+        "key usage of certificate '{}' doesn't allow {} (one of {} is required)",
+        cert_id,
+        purpose,
+        required
+    ))]
+    KeyUsageNotAllowed {
+        cert_id: String,
+        purpose: String,
+        required: String,
+    },
+
+    /// extended key usage doesn't allow the intended purpose
+    #[snafu(display("extended key usage of certificate '{}' doesn't allow {}", cert_id, purpose))]
+    ExtendedKeyUsageNotAllowed { cert_id: String, purpose: String },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -125,12 +215,25 @@ pub enum CertType {
 
 const CERT_PEM_LABEL: &str = "CERTIFICATE";
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct Cert(Certificate);
+/// The DER encoding the certificate was decoded from, if any, is kept for fingerprinting.
+#[derive(Clone, Debug)]
+pub struct Cert(Certificate, Option<Vec<u8>>);
+
+impl PartialEq for Cert {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
 
 impl From<Certificate> for Cert {
     fn from(certificate: Certificate) -> Self {
-        Self(certificate)
+        Self(certificate, None)
+    }
+}
+
+impl From<Cert> for Certificate {
+    fn from(cert: Cert) -> Self {
+        cert.0
     }
 }
 
@@ -147,8 +250,10 @@ macro_rules! find_ext {
 
 impl Cert {
     pub fn from_der<T: ?Sized + AsRef<[u8]>>(der: &T) -> Result<Self, CertError> {
+        let der = der.as_ref();
         Ok(Self(
-            picky_asn1_der::from_bytes(der.as_ref()).context(Asn1Deserialization { element: "certificate" })?,
+            picky_asn1_der::from_bytes(der).context(Asn1Deserialization { element: "certificate" })?,
+            Some(der.to_vec()),
         ))
     }
 
@@ -234,6 +339,126 @@ impl Cert {
         }
     }
 
+    pub fn key_usage(&self) -> Result<&KeyUsage, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(oids::key_usage(), certificate, "key usage")?;
+        match ext.extn_value() {
+            ExtensionView::KeyUsage(ku) => Ok(ku),
+            _ => unreachable!("invalid extension (expected key usage)"),
+        }
+    }
+
+    pub fn extended_key_usage(&self) -> Result<&ExtendedKeyUsage, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(oids::extended_key_usage(), certificate, "extended key usage")?;
+        match ext.extn_value() {
+            ExtensionView::ExtendedKeyUsage(eku) => Ok(eku),
+            _ => unreachable!("invalid extension (expected extended key usage)"),
+        }
+    }
+
+    pub fn subject_alt_name(&self) -> Result<GeneralNames, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(
+            oids::subject_alternative_name(),
+            certificate,
+            "subject alternative name"
+        )?;
+        match ext.extn_value() {
+            ExtensionView::SubjectAltName(san) => Ok(san),
+            _ => unreachable!("invalid extension (expected subject alternative name)"),
+        }
+    }
+
+    pub fn name_constraints(&self) -> Result<&NameConstraints, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(oids::name_constraints(), certificate, "name constraints")?;
+        match ext.extn_value() {
+            ExtensionView::NameConstraints(nc) => Ok(nc),
+            // name constraints that couldn't be parsed are kept as a generic extension
+            _ => Err(CertError::ExtensionNotFound {
+                name: "name constraints",
+            }),
+        }
+    }
+
+    pub fn certificate_policies(&self) -> Result<&CertificatePolicies, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(oids::certificate_policies(), certificate, "certificate policies")?;
+        match ext.extn_value() {
+            ExtensionView::CertificatePolicies(policies) => Ok(policies),
+            // policies that couldn't be fully parsed are kept as a generic extension
+            _ => Err(CertError::ExtensionNotFound {
+                name: "certificate policies",
+            }),
+        }
+    }
+
+    pub fn policy_mappings(&self) -> Result<&PolicyMappings, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(oids::policy_mappings(), certificate, "policy mappings")?;
+        match ext.extn_value() {
+            ExtensionView::PolicyMappings(mappings) => Ok(mappings),
+            _ => unreachable!("invalid extension (expected policy mappings)"),
+        }
+    }
+
+    pub fn policy_constraints(&self) -> Result<&PolicyConstraints, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(oids::policy_constraints(), certificate, "policy constraints")?;
+        match ext.extn_value() {
+            ExtensionView::PolicyConstraints(pc) => Ok(pc),
+            _ => unreachable!("invalid extension (expected policy constraints)"),
+        }
+    }
+
+    pub fn inhibit_any_policy(&self) -> Result<&InhibitAnyPolicy, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(oids::inhibit_any_policy(), certificate, "inhibit any policy")?;
+        match ext.extn_value() {
+            ExtensionView::InhibitAnyPolicy(iap) => Ok(iap),
+            _ => unreachable!("invalid extension (expected inhibit any policy)"),
+        }
+    }
+
+    pub fn crl_distribution_points(&self) -> Result<&CRLDistributionPoints, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(oids::crl_distribution_points(), certificate, "CRL distribution points")?;
+        match ext.extn_value() {
+            ExtensionView::CRLDistributionPoints(distribution_points) => Ok(distribution_points),
+            // distribution points that couldn't be fully parsed are kept as a generic extension
+            _ => Err(CertError::ExtensionNotFound {
+                name: "CRL distribution points",
+            }),
+        }
+    }
+
+    pub fn freshest_crl(&self) -> Result<&CRLDistributionPoints, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(oids::freshest_crl(), certificate, "freshest CRL")?;
+        match ext.extn_value() {
+            ExtensionView::FreshestCRL(distribution_points) => Ok(distribution_points),
+            // distribution points that couldn't be fully parsed are kept as a generic extension
+            _ => Err(CertError::ExtensionNotFound { name: "freshest CRL" }),
+        }
+    }
+
+    pub fn authority_info_access(&self) -> Result<&AuthorityInfoAccess, CertError> {
+        let certificate = &self.0;
+        let ext = find_ext!(
+            oids::authority_info_access(),
+            certificate,
+            "authority information access"
+        )?;
+        match ext.extn_value() {
+            ExtensionView::AuthorityInfoAccess(aia) => Ok(aia),
+            // access descriptions that couldn't be parsed are kept as a generic extension
+            _ => Err(CertError::ExtensionNotFound {
+                name: "authority information access",
+            }),
+        }
+    }
+
     pub fn subject_name(&self) -> DirectoryName {
         self.0.tbs_certificate.subject.clone().into()
     }
@@ -250,6 +475,20 @@ impl Cert {
         (&self.0.tbs_certificate.subject_public_key_info).into()
     }
 
+    /// Fingerprint (or thumbprint) of the certificate: hash of its DER encoding (as received when
+    /// decoded with `from_der`).
+    pub fn fingerprint(&self, hash_algorithm: HashAlgorithm) -> Result<Vec<u8>, CertError> {
+        match &self.1 {
+            Some(der) => Ok(hash_algorithm.digest(der)),
+            None => Ok(hash_algorithm.digest(&self.to_der()?)),
+        }
+    }
+
+    /// Base64 encoded SHA-256 hash of the subject public key info (see `PublicKey::spki_pin`).
+    pub fn spki_pin(&self) -> Result<String, CertError> {
+        self.public_key().spki_pin().context(InvalidPublicKey)
+    }
+
     pub fn verify(&self, now: &UTCDate) -> Result<(), CertError> {
         let validity = &self.0.tbs_certificate.validity;
         let not_before: UTCDate = validity.not_before.clone().into();
@@ -272,13 +511,11 @@ impl Cert {
         Ok(())
     }
 
+    /// Key identifiers are only compared when both certificates have them (a trust anchor
+    /// may lack the subject key identifier extension).
     pub fn is_parent_of(&self, other: &Cert) -> Result<(), CertError> {
         if let Ok(other_aki) = other.authority_key_identifier() {
-            if let Some(other_aki) = other_aki.key_identifier() {
-                let parent_ski = self.subject_key_identifier().with_context(|| InvalidCertificate {
-                    id: self.subject_name().to_string(),
-                })?;
-
+            if let (Some(other_aki), Ok(parent_ski)) = (other_aki.key_identifier(), self.subject_key_identifier()) {
                 if parent_ski != other_aki {
                     return Err(CaChainError::AuthorityKeyIdMismatch {
                         expected: other_aki.to_vec(),
@@ -308,10 +545,36 @@ impl Cert {
         Ok(())
     }
 
+    /// Verifies the certification path made of this certificate followed by `chain`, ordered up
+    /// to the root.
+    ///
+    /// **This doesn't check trust**: any self-signed root ending the chain is accepted, including
+    /// one provided by an untrusted peer. Use `verify_chain_with_settings` with
+    /// `ChainValidationSettings::trust_store`, or build the path from a `TrustStore` with
+    /// `ChainBuilder`, to require the root to be a trust anchor.
     pub fn verify_chain<'a, Chain: Iterator<Item = &'a Cert>>(
         &self,
         chain: Chain,
         now: &UTCDate,
+    ) -> Result<(), CertError> {
+        self.verify_path(chain, now, false)
+    }
+
+    /// Same as `verify_chain`, except the last certificate of the chain (or this certificate when
+    /// the chain is empty) is an explicitly trusted anchor which doesn't have to be a self-signed root.
+    pub(crate) fn verify_chain_to_trust_anchor<'a, Chain: Iterator<Item = &'a Cert>>(
+        &self,
+        chain: Chain,
+        now: &UTCDate,
+    ) -> Result<(), CertError> {
+        self.verify_path(chain, now, true)
+    }
+
+    fn verify_path<'a, Chain: Iterator<Item = &'a Cert>>(
+        &self,
+        chain: Chain,
+        now: &UTCDate,
+        ends_with_trust_anchor: bool,
     ) -> Result<(), CertError> {
         self.verify(now).with_context(|| InvalidCertificate {
             id: self.subject_name().to_string(),
@@ -319,6 +582,9 @@ impl Cert {
 
         let mut current_cert = self;
 
+        // certificates subject to the name constraints of the next parent
+        let mut subordinate_certs = vec![self];
+
         for (number_certs, parent_cert) in chain.enumerate() {
             // check basic constraints
             match parent_cert
@@ -342,6 +608,16 @@ impl Cert {
                 _ => {}
             }
 
+            // check parent key usage allows certificate signing
+            if let Ok(key_usage) = parent_cert.key_usage() {
+                if !key_usage.key_cert_sign() {
+                    return Err(CaChainError::IssuerCannotSignCertificates {
+                        issuer_id: parent_cert.subject_name().to_string(),
+                    })
+                    .context(InvalidChain);
+                }
+            }
+
             // verify parent
             parent_cert.verify(now).with_context(|| InvalidCertificate {
                 id: parent_cert.subject_name().to_string(),
@@ -351,37 +627,129 @@ impl Cert {
             parent_cert.is_parent_of(current_cert)?;
 
             // validate current cert signature using parent public key
-            let hash_type =
-                SignatureHashType::from_algorithm_identifier(&current_cert.0.signature_algorithm).context(Signature)?;
-            let public_key = &parent_cert.0.tbs_certificate.subject_public_key_info;
-            let msg = picky_asn1_der::to_vec(&current_cert.0.tbs_certificate)
-                .context(Asn1Serialization {
-                    element: "tbs certificate",
-                })
-                .with_context(|| InvalidCertificate {
-                    id: current_cert.subject_name().to_string(),
-                })?;
-            hash_type
-                .verify(
-                    &public_key.clone().into(),
-                    &msg,
-                    current_cert.0.signature_value.0.payload_view(),
-                )
-                .context(Signature)
+            current_cert
+                .verify_signature(parent_cert.public_key())
                 .with_context(|| InvalidCertificate {
                     id: current_cert.subject_name().to_string(),
                 })?;
 
+            // critical name constraints that couldn't be parsed can't be enforced
+            let parent_certificate = &parent_cert.0;
+            if let Ok(ext) = find_ext!(oids::name_constraints(), parent_certificate, "name constraints") {
+                if ext.critical() && parent_cert.name_constraints().is_err() {
+                    return Err(CaChainError::UnsupportedNameConstraints {
+                        issuer_id: parent_cert.subject_name().to_string(),
+                    })
+                    .context(InvalidChain);
+                }
+            }
+
+            // check names of all subordinate certificates against parent name constraints
+            if let Ok(name_constraints) = parent_cert.name_constraints() {
+                for cert in &subordinate_certs {
+                    // self-issued intermediate certificates are exempted (RFC 5280 section 6.1.3)
+                    let self_issued = !std::ptr::eq(*cert, self) && cert.subject_name() == cert.issuer_name();
+                    if !self_issued {
+                        cert.check_name_constraints(name_constraints, parent_cert)
+                            .context(InvalidChain)?;
+                    }
+                }
+            }
+
+            subordinate_certs.push(parent_cert);
             current_cert = parent_cert;
         }
 
-        // make sure `current_cert` (the last certificate of the chain) is a root CA
-        if current_cert.ty() != CertType::Root {
+        // unless explicitly trusted, `current_cert` (the last certificate of the chain) must be a root CA
+        if !ends_with_trust_anchor && current_cert.ty() != CertType::Root {
             return Err(CaChainError::NoRoot).context(InvalidChain);
         }
 
         Ok(())
     }
+
+    /// Same as `verify_chain`, followed by the additional checks enabled in `settings`.
+    ///
+    /// When `settings` has a trust store, the chain must end with one of its trust anchors, which
+    /// doesn't have to be a self-signed root.
+    pub fn verify_chain_with_settings<'a, Chain: Iterator<Item = &'a Cert>>(
+        &self,
+        chain: Chain,
+        now: &UTCDate,
+        settings: &ChainValidationSettings,
+    ) -> Result<(), CertError> {
+        let mut path = vec![self];
+        for cert in chain {
+            path.push(cert);
+        }
+        let last_cert = path[path.len() - 1];
+        if settings.is_trust_anchor(last_cert) {
+            self.verify_chain_to_trust_anchor(path[1..].iter().copied(), now)?;
+        } else {
+            self.verify_chain(path[1..].iter().copied(), now)?;
+        }
+        settings.validate(&path, now)
+    }
+
+    /// Verifies the certificate signature using the given issuer public key.
+    pub(crate) fn verify_signature(&self, issuer_public_key: &PublicKey) -> Result<(), CertError> {
+        let hash_type = SignatureHashType::from_algorithm_identifier(&self.0.signature_algorithm).context(Signature)?;
+        let msg = picky_asn1_der::to_vec(&self.0.tbs_certificate).context(Asn1Serialization {
+            element: "tbs certificate",
+        })?;
+        hash_type
+            .verify(issuer_public_key, &msg, self.0.signature_value.0.payload_view())
+            .context(Signature)
+    }
+
+    fn check_name_constraints(&self, name_constraints: &NameConstraints, issuer: &Cert) -> Result<(), CaChainError> {
+        let mut names = Vec::new();
+
+        let subject_name = self.subject_name();
+        // email addresses in the subject name are constrained as rfc822 names (RFC 5280 section 4.2.1.10)
+        names.extend(
+            subject_name
+                .email_addresses()
+                .into_iter()
+                .map(|address| GeneralName::RFC822Name(address.clone())),
+        );
+        if !subject_name.to_string().is_empty() {
+            names.push(GeneralName::DirectoryName(subject_name));
+        }
+
+        if let Ok(san) = self.subject_alt_name() {
+            names.extend(san.into_general_names());
+        }
+
+        match names.into_iter().find(|name| !name_constraints.is_permitted(name)) {
+            Some(name) => Err(CaChainError::NameConstraintViolation {
+                cert_id: self.subject_name().to_string(),
+                issuer_id: issuer.subject_name().to_string(),
+                name: general_name_to_string(&name),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn general_name_to_string(name: &GeneralName) -> String {
+    match name {
+        GeneralName::RFC822Name(name) => format!("email:{}", name),
+        GeneralName::DNSName(name) => format!("DNS:{}", name),
+        GeneralName::DirectoryName(name) => format!("DirName:{}", name),
+        GeneralName::URI(name) => format!("URI:{}", name),
+        GeneralName::IpAddress(addr) if addr.len() == 4 => {
+            let mut octets = [0; 4];
+            octets.copy_from_slice(addr);
+            format!("IP:{}", Ipv4Addr::from(octets))
+        }
+        GeneralName::IpAddress(addr) if addr.len() == 16 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(addr);
+            format!("IP:{}", Ipv6Addr::from(octets))
+        }
+        other => format!("{:?}", other),
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -390,13 +758,23 @@ enum SubjectInfos {
     NameAndPublicKey { name: DirectoryName, public_key: PublicKey },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 struct IssuerInfos<'a> {
     name: DirectoryName,
-    key: &'a PrivateKey,
+    key: &'a dyn Signer,
     self_signed: bool,
 }
 
+impl fmt::Debug for IssuerInfos<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IssuerInfos")
+            .field("name", &self.name)
+            .field("key", &"<signer>")
+            .field("self_signed", &self.self_signed)
+            .finish()
+    }
+}
+
 // Statically checks the field actually exists and returns a &'static str of the field name
 macro_rules! field_str {
     ($field:ident) => {{
@@ -420,6 +798,14 @@ struct CertificateBuilderInner<'a> {
     extended_key_usage: Option<ExtendedKeyUsage>,
     subject_alt_name: Option<GeneralNames>,
     issuer_alt_name: Option<GeneralNames>,
+    name_constraints: Option<NameConstraints>,
+    certificate_policies: Option<CertificatePolicies>,
+    policy_mappings: Option<PolicyMappings>,
+    policy_constraints: Option<PolicyConstraints>,
+    inhibit_any_policy: Option<u32>,
+    crl_distribution_points: Option<CRLDistributionPoints>,
+    freshest_crl: Option<CRLDistributionPoints>,
+    authority_info_access: Option<AuthorityInfoAccess>,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -461,7 +847,7 @@ impl<'a> CertificateBuilder<'a> {
 
     /// Required (alternative: `self_signed`, `issuer_cert`)
     #[inline]
-    pub fn issuer(&self, issuer_name: DirectoryName, issuer_key: &'a PrivateKey) -> &Self {
+    pub fn issuer(&self, issuer_name: DirectoryName, issuer_key: &'a dyn Signer) -> &Self {
         self.inner.borrow_mut().issuer_infos = Some(IssuerInfos {
             name: issuer_name,
             key: issuer_key,
@@ -472,7 +858,7 @@ impl<'a> CertificateBuilder<'a> {
 
     /// Required (alternative: `issuer`, `issuer_cert`)
     #[inline]
-    pub fn self_signed(&self, name: DirectoryName, key: &'a PrivateKey) -> &Self {
+    pub fn self_signed(&self, name: DirectoryName, key: &'a dyn Signer) -> &Self {
         self.inner.borrow_mut().issuer_infos = Some(IssuerInfos {
             name,
             key,
@@ -483,8 +869,8 @@ impl<'a> CertificateBuilder<'a> {
 
     /// Required (alternative: `issuer`, `self_signed`)
     #[inline]
-    pub fn issuer_cert(&self, issuer_cert: &Cert, issuer_key: &'a PrivateKey) -> &Self {
-        let builder = self.issuer(issuer_cert.subject_name(), &issuer_key);
+    pub fn issuer_cert(&self, issuer_cert: &Cert, issuer_key: &'a dyn Signer) -> &Self {
+        let builder = self.issuer(issuer_cert.subject_name(), issuer_key);
 
         if let Ok(issuer_ski) = issuer_cert.subject_key_identifier() {
             self.authority_key_identifier(issuer_ski.to_vec())
@@ -556,6 +942,62 @@ impl<'a> CertificateBuilder<'a> {
         self
     }
 
+    /// Optional
+    #[inline]
+    pub fn name_constraints(&self, name_constraints: NameConstraints) -> &Self {
+        self.inner.borrow_mut().name_constraints = Some(name_constraints);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn certificate_policies(&self, certificate_policies: CertificatePolicies) -> &Self {
+        self.inner.borrow_mut().certificate_policies = Some(certificate_policies);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn policy_mappings(&self, policy_mappings: PolicyMappings) -> &Self {
+        self.inner.borrow_mut().policy_mappings = Some(policy_mappings);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn policy_constraints(&self, policy_constraints: PolicyConstraints) -> &Self {
+        self.inner.borrow_mut().policy_constraints = Some(policy_constraints);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn inhibit_any_policy(&self, skip_certs: u32) -> &Self {
+        self.inner.borrow_mut().inhibit_any_policy = Some(skip_certs);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn crl_distribution_points(&self, crl_distribution_points: CRLDistributionPoints) -> &Self {
+        self.inner.borrow_mut().crl_distribution_points = Some(crl_distribution_points);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn freshest_crl(&self, freshest_crl: CRLDistributionPoints) -> &Self {
+        self.inner.borrow_mut().freshest_crl = Some(freshest_crl);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn authority_info_access(&self, authority_info_access: AuthorityInfoAccess) -> &Self {
+        self.inner.borrow_mut().authority_info_access = Some(authority_info_access);
+        self
+    }
+
     pub fn build(&self) -> Result<Cert, CertError> {
         let mut inner = self.inner.borrow_mut();
 
@@ -566,8 +1008,6 @@ impl<'a> CertificateBuilder<'a> {
             arg: field_str!(valid_to),
         })?;
 
-        let signature_hash_type = inner.signature_hash_type.take().unwrap_or(SignatureHashType::RsaSha256);
-
         let key_id_gen_method = inner
             .key_id_gen_method
             .take()
@@ -576,9 +1016,15 @@ impl<'a> CertificateBuilder<'a> {
         let issuer_infos = inner.issuer_infos.take().ok_or(CertError::MissingBuilderArgument {
             arg: field_str!(issuer_infos),
         })?;
+
+        let signature_hash_type = inner
+            .signature_hash_type
+            .take()
+            .unwrap_or_else(|| issuer_infos.key.signature_hash_type());
+
         let (issuer_name, issuer_key, aki, subject_infos) = {
             let (aki, subject_infos) = if issuer_infos.self_signed {
-                let public_key = issuer_infos.key.to_public_key();
+                let public_key = issuer_infos.key.public_key();
                 let aki = key_id_gen_method
                     .generate_from(&public_key)
                     .context(KeyIdGen)
@@ -617,6 +1063,14 @@ impl<'a> CertificateBuilder<'a> {
         let extended_key_usage_opt = inner.extended_key_usage.take();
         let subject_alt_name_opt = inner.subject_alt_name.take();
         let issuer_alt_name_opt = inner.issuer_alt_name.take();
+        let name_constraints_opt = inner.name_constraints.take();
+        let certificate_policies_opt = inner.certificate_policies.take();
+        let policy_mappings_opt = inner.policy_mappings.take();
+        let policy_constraints_opt = inner.policy_constraints.take();
+        let inhibit_any_policy_opt = inner.inhibit_any_policy.take();
+        let crl_distribution_points_opt = inner.crl_distribution_points.take();
+        let freshest_crl_opt = inner.freshest_crl.take();
+        let authority_info_access_opt = inner.authority_info_access.take();
 
         drop(inner);
 
@@ -657,6 +1111,36 @@ impl<'a> CertificateBuilder<'a> {
                 extensions.push(Extension::new_issuer_alt_name(ian));
             }
 
+            // name constraints
+            if let Some(name_constraints) = name_constraints_opt {
+                extensions.push(Extension::new_name_constraints(name_constraints));
+            }
+
+            // policies
+            if let Some(certificate_policies) = certificate_policies_opt {
+                extensions.push(Extension::new_certificate_policies(certificate_policies));
+            }
+            if let Some(policy_mappings) = policy_mappings_opt {
+                extensions.push(Extension::new_policy_mappings(policy_mappings));
+            }
+            if let Some(policy_constraints) = policy_constraints_opt {
+                extensions.push(Extension::new_policy_constraints(policy_constraints));
+            }
+            if let Some(skip_certs) = inhibit_any_policy_opt {
+                extensions.push(Extension::new_inhibit_any_policy(skip_certs));
+            }
+
+            // revocation information
+            if let Some(crl_distribution_points) = crl_distribution_points_opt {
+                extensions.push(Extension::new_crl_distribution_points(crl_distribution_points));
+            }
+            if let Some(freshest_crl) = freshest_crl_opt {
+                extensions.push(Extension::new_freshest_crl(freshest_crl));
+            }
+            if let Some(authority_info_access) = authority_info_access_opt {
+                extensions.push(Extension::new_authority_info_access(authority_info_access));
+            }
+
             // ski
             let ski = key_id_gen_method
                 .generate_from(&subject_public_key)
@@ -691,13 +1175,13 @@ impl<'a> CertificateBuilder<'a> {
             })
             .context(CertGeneration)?;
         let signature_value = BitString::with_bytes(
-            signature_hash_type
-                .sign(&tbs_der, issuer_key)
+            issuer_key
+                .sign(&tbs_der, signature_hash_type)
                 .context(Signature)
                 .context(CertGeneration)?,
         );
 
-        Ok(Cert(Certificate {
+        Ok(Cert::from(Certificate {
             tbs_certificate,
             signature_algorithm: signature_hash_type.into(),
             signature_value: signature_value.into(),
@@ -717,7 +1201,12 @@ fn generate_serial_number() -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pem::{parse_pem, Pem};
+    use crate::{
+        key::{EdAlgorithm, PrivateKey},
+        pem::{parse_pem, Pem},
+        x509::extension::{AccessDescription, DistributionPoint},
+    };
+    use picky_asn1::restricted_string::IA5String;
 
     #[test]
     fn read_pem_and_parse_certificate() {
@@ -775,7 +1264,7 @@ mod tests {
 
     fn parse_key(pem_str: &str) -> PrivateKey {
         let pem = pem_str.parse::<Pem>().unwrap();
-        PrivateKey::from_pkcs8(pem.data()).unwrap()
+        PrivateKey::from_pem(&pem).unwrap()
     }
 
     #[test]
@@ -1004,4 +1493,597 @@ mod tests {
             "CA chain error: issuer certificate \'CN=I Trust This V.E.R.Y Legitimate Intermediate Certificate\' is not a CA"
         );
     }
+
+    #[test]
+    fn issuer_without_key_cert_sign() {
+        let root_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let leaf_key = parse_key(crate::test_files::EC_P256_PK_2);
+
+        let build_root = |key_cert_sign: bool| {
+            let mut key_usage = KeyUsage::default();
+            key_usage.set_key_cert_sign(key_cert_sign);
+            key_usage.set_crl_sign(true);
+
+            CertificateBuilder::new()
+                .valididy(UTCDate::ymd(2065, 6, 15).unwrap(), UTCDate::ymd(2070, 6, 15).unwrap())
+                .self_signed(DirectoryName::new_common_name("CRL Only Root CA"), &root_key)
+                .ca(true)
+                .key_usage(key_usage)
+                .build()
+                .expect("couldn't build root ca")
+        };
+
+        let build_leaf = |root: &Cert| {
+            CertificateBuilder::new()
+                .valididy(UTCDate::ymd(2066, 1, 1).unwrap(), UTCDate::ymd(2069, 1, 1).unwrap())
+                .subject(DirectoryName::new_common_name("Leaf"), leaf_key.to_public_key())
+                .issuer_cert(root, &root_key)
+                .build()
+                .expect("couldn't build leaf")
+        };
+
+        let now = UTCDate::ymd(2067, 1, 1).unwrap();
+
+        let root = build_root(true);
+        build_leaf(&root)
+            .verify_chain([root].iter(), &now)
+            .expect("couldn't validate ca chain");
+
+        let root = build_root(false);
+        let err = build_leaf(&root).verify_chain([root].iter(), &now).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: issuer certificate 'CN=CRL Only Root CA' key usage doesn't allow certificate signing"
+        );
+    }
+
+    #[test]
+    fn valid_ecdsa_ca_chain() {
+        let root = Cert::from_pem(&crate::test_files::EC_ROOT_CA.parse::<Pem>().unwrap()).unwrap();
+        let intermediate = Cert::from_pem(&crate::test_files::EC_INTERMEDIATE_CA.parse::<Pem>().unwrap()).unwrap();
+        let leaf = Cert::from_pem(&crate::test_files::EC_LEAF.parse::<Pem>().unwrap()).unwrap();
+
+        assert_eq!(
+            SignatureHashType::from_algorithm_identifier(intermediate.signature_algorithm()).unwrap(),
+            SignatureHashType::EcdsaSha384
+        );
+        assert_eq!(
+            SignatureHashType::from_algorithm_identifier(leaf.signature_algorithm()).unwrap(),
+            SignatureHashType::EcdsaSha256
+        );
+
+        let chain = [intermediate, root];
+        leaf.verify_chain(chain.iter(), &UTCDate::ymd(2030, 1, 1).unwrap())
+            .expect("couldn't verify chain");
+    }
+
+    #[test]
+    fn ecdsa_ca_chain_generation() {
+        let root_key = parse_key(crate::test_files::EC_P384_PK_1);
+        let intermediate_key = parse_key(crate::test_files::EC_P256_PK_2);
+        let leaf_key = parse_key(crate::test_files::EC_P256_PK_1);
+
+        let root = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2065, 6, 15).unwrap(), UTCDate::ymd(2070, 6, 15).unwrap())
+            .self_signed(DirectoryName::new_common_name("Elliptic Root CA"), &root_key)
+            .ca(true)
+            .signature_hash_type(SignatureHashType::EcdsaSha384)
+            .build()
+            .expect("couldn't build root ca");
+
+        let intermediate = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2068, 1, 1).unwrap(), UTCDate::ymd(2071, 1, 1).unwrap())
+            .subject(
+                DirectoryName::new_common_name("Elliptic Authority"),
+                intermediate_key.to_public_key(),
+            )
+            .issuer_cert(&root, &root_key)
+            .signature_hash_type(SignatureHashType::EcdsaSha384)
+            .ca(true)
+            .pathlen(0)
+            .build()
+            .expect("couldn't build intermediate ca");
+
+        let csr = Csr::generate(
+            DirectoryName::new_common_name("Elliptic Leaf"),
+            &leaf_key,
+            SignatureHashType::EcdsaSha256,
+        )
+        .unwrap();
+
+        let signed_leaf = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2069, 1, 1).unwrap(), UTCDate::ymd(2072, 1, 1).unwrap())
+            .subject_from_csr(csr)
+            .issuer_cert(&intermediate, &intermediate_key)
+            .signature_hash_type(SignatureHashType::EcdsaSha256)
+            .build()
+            .expect("couldn't build signed leaf");
+
+        let chain = [intermediate, root];
+        signed_leaf
+            .verify_chain(chain.iter(), &UTCDate::ymd(2069, 10, 1).unwrap())
+            .expect("couldn't verify chain");
+
+        let rsa_key = parse_key(crate::test_files::RSA_2048_PK_1);
+        let err = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2069, 1, 1).unwrap(), UTCDate::ymd(2072, 1, 1).unwrap())
+            .self_signed(DirectoryName::new_common_name("Mismatch"), &rsa_key)
+            .signature_hash_type(SignatureHashType::EcdsaSha256)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "couldn't generate certificate: signature error: EcdsaSha256 can't be used with RSA key"
+        );
+    }
+
+    /// Stands for a key held by a HSM or a KMS: only the public key and a signing operation are exposed.
+    struct MockSigner {
+        remote_key: PrivateKey,
+        requests: RefCell<Vec<SignatureHashType>>,
+    }
+
+    impl Signer for MockSigner {
+        fn public_key(&self) -> PublicKey {
+            self.remote_key.to_public_key()
+        }
+
+        fn signature_hash_type(&self) -> SignatureHashType {
+            SignatureHashType::EcdsaSha384
+        }
+
+        fn sign(&self, msg: &[u8], signature_hash_type: SignatureHashType) -> Result<Vec<u8>, SignatureError> {
+            self.requests.borrow_mut().push(signature_hash_type);
+            signature_hash_type.sign(msg, &self.remote_key)
+        }
+    }
+
+    #[test]
+    fn ca_chain_generation_with_custom_signer() {
+        let root_signer = MockSigner {
+            remote_key: parse_key(crate::test_files::EC_P384_PK_1),
+            requests: RefCell::new(Vec::new()),
+        };
+        let leaf_signer = MockSigner {
+            remote_key: parse_key(crate::test_files::EC_P256_PK_1),
+            requests: RefCell::new(Vec::new()),
+        };
+
+        let root = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2065, 6, 15).unwrap(), UTCDate::ymd(2070, 6, 15).unwrap())
+            .self_signed(DirectoryName::new_common_name("HSM Root CA"), &root_signer)
+            .ca(true)
+            .build()
+            .expect("couldn't build root ca");
+        assert_eq!(
+            SignatureHashType::from_algorithm_identifier(root.signature_algorithm()).unwrap(),
+            SignatureHashType::EcdsaSha384
+        );
+
+        let csr = Csr::generate(
+            DirectoryName::new_common_name("HSM Leaf"),
+            &leaf_signer,
+            SignatureHashType::EcdsaSha256,
+        )
+        .unwrap();
+
+        let leaf = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2066, 1, 1).unwrap(), UTCDate::ymd(2069, 1, 1).unwrap())
+            .subject_from_csr(csr)
+            .issuer_cert(&root, &root_signer)
+            .signature_hash_type(SignatureHashType::EcdsaSha256)
+            .build()
+            .expect("couldn't build leaf");
+
+        assert_eq!(leaf.public_key(), &leaf_signer.public_key());
+        leaf.verify_chain([root].iter(), &UTCDate::ymd(2067, 1, 1).unwrap())
+            .expect("couldn't verify chain");
+
+        assert_eq!(
+            *root_signer.requests.borrow(),
+            [SignatureHashType::EcdsaSha384, SignatureHashType::EcdsaSha256]
+        );
+        assert_eq!(*leaf_signer.requests.borrow(), [SignatureHashType::EcdsaSha256]);
+    }
+
+    #[test]
+    fn name_constraints_ca_chain() {
+        let root_key = parse_key(crate::test_files::EC_P384_PK_1);
+        let intermediate_key = parse_key(crate::test_files::EC_P256_PK_2);
+        let leaf_key = parse_key(crate::test_files::EC_P256_PK_1);
+
+        let mut name_constraints = NameConstraints::new();
+        name_constraints.add_permitted_subtree(GeneralName::new_dns_name("example.com").unwrap());
+        name_constraints.add_excluded_subtree(GeneralName::new_dns_name("forbidden.example.com").unwrap());
+
+        let root = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2065, 6, 15).unwrap(), UTCDate::ymd(2070, 6, 15).unwrap())
+            .self_signed(DirectoryName::new_common_name("Constrained Root CA"), &root_key)
+            .ca(true)
+            .name_constraints(name_constraints.clone())
+            .build()
+            .expect("couldn't build root ca");
+        assert_eq!(root.name_constraints().expect("name constraints"), &name_constraints);
+
+        let intermediate = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2068, 1, 1).unwrap(), UTCDate::ymd(2071, 1, 1).unwrap())
+            .subject(
+                DirectoryName::new_common_name("Constrained Authority"),
+                intermediate_key.to_public_key(),
+            )
+            .issuer_cert(&root, &root_key)
+            .ca(true)
+            .build()
+            .expect("couldn't build intermediate ca");
+
+        let build_leaf = |dns_name: &str| {
+            CertificateBuilder::new()
+                .valididy(UTCDate::ymd(2069, 1, 1).unwrap(), UTCDate::ymd(2072, 1, 1).unwrap())
+                .subject(DirectoryName::new_common_name("Leaf"), leaf_key.to_public_key())
+                .subject_alt_name(GeneralNames::new(GeneralName::new_dns_name(dns_name).unwrap()))
+                .issuer_cert(&intermediate, &intermediate_key)
+                .build()
+                .expect("couldn't build leaf")
+        };
+
+        let now = UTCDate::ymd(2069, 10, 1).unwrap();
+        let chain = [intermediate.clone(), root.clone()];
+
+        build_leaf("www.example.com")
+            .verify_chain(chain.iter(), &now)
+            .expect("couldn't verify chain");
+
+        let err = build_leaf("www.example.org")
+            .verify_chain(chain.iter(), &now)
+            .expect_err("not permitted");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: certificate 'CN=Leaf' violates name constraints of 'CN=Constrained Root CA': \
+             DNS:www.example.org"
+        );
+
+        let err = build_leaf("forbidden.example.com")
+            .verify_chain(chain.iter(), &now)
+            .expect_err("excluded");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: certificate 'CN=Leaf' violates name constraints of 'CN=Constrained Root CA': \
+             DNS:forbidden.example.com"
+        );
+    }
+
+    #[test]
+    fn name_constraints_subject_email_address() {
+        let root_key = parse_key(crate::test_files::EC_P384_PK_1);
+        let leaf_key = parse_key(crate::test_files::EC_P256_PK_1);
+
+        let mut name_constraints = NameConstraints::new();
+        name_constraints.add_permitted_subtree(GeneralName::new_rfc822_name("example.com").unwrap());
+
+        let root = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2065, 6, 15).unwrap(), UTCDate::ymd(2070, 6, 15).unwrap())
+            .self_signed(DirectoryName::new_common_name("Constrained Root CA"), &root_key)
+            .ca(true)
+            .name_constraints(name_constraints)
+            .build()
+            .expect("couldn't build root ca");
+
+        let build_leaf = |email: &str| {
+            let mut subject = DirectoryName::new_common_name("Leaf");
+            subject.add_email_address(IA5String::from_string(email.to_owned()).unwrap());
+            let leaf = CertificateBuilder::new()
+                .valididy(UTCDate::ymd(2069, 1, 1).unwrap(), UTCDate::ymd(2072, 1, 1).unwrap())
+                .subject(subject, leaf_key.to_public_key())
+                .issuer_cert(&root, &root_key)
+                .build()
+                .expect("couldn't build leaf");
+            Cert::from_der(&leaf.to_der().unwrap()).expect("couldn't parse leaf")
+        };
+
+        let now = UTCDate::ymd(2069, 10, 1).unwrap();
+
+        let leaf = build_leaf("leaf@example.com");
+        assert_eq!(leaf.subject_name().to_string(), "CN=Leaf,E=leaf@example.com");
+        leaf.verify_chain([root.clone()].iter(), &now)
+            .expect("couldn't verify chain");
+
+        let err = build_leaf("leaf@example.org")
+            .verify_chain([root].iter(), &now)
+            .expect_err("not permitted");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: certificate 'CN=Leaf,E=leaf@example.org' violates name constraints of \
+             'CN=Constrained Root CA': email:leaf@example.org"
+        );
+    }
+
+    #[test]
+    fn unsupported_critical_name_constraints() {
+        let root_key = parse_key(crate::test_files::EC_P384_PK_1);
+        let leaf_key = parse_key(crate::test_files::EC_P256_PK_1);
+
+        let mut root = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2065, 6, 15).unwrap(), UTCDate::ymd(2070, 6, 15).unwrap())
+            .self_signed(DirectoryName::new_common_name("Constrained Root CA"), &root_key)
+            .ca(true)
+            .build()
+            .expect("couldn't build root ca");
+
+        // name constraints with an unknown [2] element
+        let extension_der = [
+            0x30, 0x0E, 0x06, 0x03, 0x55, 0x1D, 0x1E, 0x01, 0x01, 0xFF, 0x04, 0x04, 0x30, 0x02, 0xA2, 0x00,
+        ];
+        let extension: Extension = picky_asn1_der::from_bytes(&extension_der).expect("extension");
+        ((root.0.tbs_certificate.extensions.0).0).push(extension);
+        let tbs_der = picky_asn1_der::to_vec(&root.0.tbs_certificate).unwrap();
+        root.0.signature_value =
+            BitString::with_bytes(root_key.sign(&tbs_der, SignatureHashType::EcdsaSha256).unwrap()).into();
+        let root = Cert::from_der(&root.to_der().unwrap()).expect("couldn't parse root ca");
+        root.name_constraints().expect_err("unparsed name constraints");
+
+        let leaf = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2069, 1, 1).unwrap(), UTCDate::ymd(2072, 1, 1).unwrap())
+            .subject(DirectoryName::new_common_name("Leaf"), leaf_key.to_public_key())
+            .issuer_cert(&root, &root_key)
+            .build()
+            .expect("couldn't build leaf");
+
+        let err = leaf
+            .verify_chain([root].iter(), &UTCDate::ymd(2069, 10, 1).unwrap())
+            .expect_err("unsupported name constraints");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: critical name constraints of 'CN=Constrained Root CA' are not supported"
+        );
+    }
+
+    #[test]
+    fn revocation_information_generation() {
+        let issuer_key = parse_key(crate::test_files::EC_P384_PK_1);
+        let leaf_key = parse_key(crate::test_files::EC_P256_PK_1);
+
+        let issuer = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2065, 6, 15).unwrap(), UTCDate::ymd(2070, 6, 15).unwrap())
+            .self_signed(DirectoryName::new_common_name("Revocation CA"), &issuer_key)
+            .ca(true)
+            .build()
+            .expect("couldn't build issuer");
+
+        let distribution_points: CRLDistributionPoints =
+            vec![DistributionPoint::new_uri("http://crl.example.com/ca.crl").unwrap()].into();
+        let freshest_crl: CRLDistributionPoints =
+            vec![DistributionPoint::new_uri("http://crl.example.com/delta.crl").unwrap()].into();
+        let authority_info_access: AuthorityInfoAccess = vec![
+            AccessDescription::new_ocsp("http://ocsp.example.com").unwrap(),
+            AccessDescription::new_ca_issuers("http://ca.example.com/ca.crt").unwrap(),
+        ]
+        .into();
+
+        let leaf = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2066, 1, 1).unwrap(), UTCDate::ymd(2067, 1, 1).unwrap())
+            .subject(DirectoryName::new_common_name("Leaf"), leaf_key.to_public_key())
+            .issuer_cert(&issuer, &issuer_key)
+            .crl_distribution_points(distribution_points.clone())
+            .freshest_crl(freshest_crl.clone())
+            .authority_info_access(authority_info_access.clone())
+            .build()
+            .expect("couldn't build leaf");
+        let leaf = Cert::from_der(&leaf.to_der().unwrap()).expect("couldn't parse leaf");
+
+        assert_eq!(
+            leaf.crl_distribution_points().expect("crl distribution points"),
+            &distribution_points
+        );
+        assert_eq!(leaf.freshest_crl().expect("freshest crl"), &freshest_crl);
+        let aia = leaf.authority_info_access().expect("authority information access");
+        assert_eq!(aia, &authority_info_access);
+        assert_eq!(aia.ocsp_uris()[0].to_string(), "http://ocsp.example.com");
+        assert_eq!(aia.ca_issuers_uris()[0].to_string(), "http://ca.example.com/ca.crt");
+
+        let err = issuer
+            .crl_distribution_points()
+            .expect_err("no crl distribution points");
+        assert_eq!(err.to_string(), "extension not found: CRL distribution points");
+    }
+
+    #[test]
+    fn valid_eddsa_ca_chain() {
+        let root = Cert::from_pem(&crate::test_files::ED25519_ROOT_CA.parse::<Pem>().unwrap()).unwrap();
+        let intermediate = Cert::from_pem(&crate::test_files::ED448_INTERMEDIATE_CA.parse::<Pem>().unwrap()).unwrap();
+        let leaf = Cert::from_pem(&crate::test_files::ED25519_LEAF.parse::<Pem>().unwrap()).unwrap();
+
+        assert_eq!(
+            SignatureHashType::from_algorithm_identifier(intermediate.signature_algorithm()).unwrap(),
+            SignatureHashType::Ed25519
+        );
+        assert_eq!(
+            SignatureHashType::from_algorithm_identifier(leaf.signature_algorithm()).unwrap(),
+            SignatureHashType::Ed448
+        );
+
+        let chain = [intermediate, root];
+        leaf.verify_chain(chain.iter(), &UTCDate::ymd(2030, 1, 1).unwrap())
+            .expect("couldn't verify chain");
+    }
+
+    #[test]
+    fn eddsa_ca_chain_generation() {
+        let root_key = PrivateKey::generate_ed(EdAlgorithm::Ed25519).expect("root key");
+        let intermediate_key = parse_key(crate::test_files::ED448_PK_1);
+        let leaf_key = parse_key(crate::test_files::ED25519_PK_2);
+
+        let root = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2065, 6, 15).unwrap(), UTCDate::ymd(2070, 6, 15).unwrap())
+            .self_signed(DirectoryName::new_common_name("Edwards Root CA"), &root_key)
+            .ca(true)
+            .signature_hash_type(SignatureHashType::Ed25519)
+            .build()
+            .expect("couldn't build root ca");
+
+        let intermediate = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2068, 1, 1).unwrap(), UTCDate::ymd(2071, 1, 1).unwrap())
+            .subject(
+                DirectoryName::new_common_name("Edwards Authority"),
+                intermediate_key.to_public_key(),
+            )
+            .issuer_cert(&root, &root_key)
+            .signature_hash_type(SignatureHashType::Ed25519)
+            .ca(true)
+            .pathlen(0)
+            .build()
+            .expect("couldn't build intermediate ca");
+
+        let csr = Csr::generate(
+            DirectoryName::new_common_name("Edwards Leaf"),
+            &leaf_key,
+            SignatureHashType::Ed25519,
+        )
+        .unwrap();
+
+        let signed_leaf = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2069, 1, 1).unwrap(), UTCDate::ymd(2072, 1, 1).unwrap())
+            .subject_from_csr(csr)
+            .issuer_cert(&intermediate, &intermediate_key)
+            .signature_hash_type(SignatureHashType::Ed448)
+            .build()
+            .expect("couldn't build signed leaf");
+
+        let chain = [intermediate, root];
+        signed_leaf
+            .verify_chain(chain.iter(), &UTCDate::ymd(2069, 10, 1).unwrap())
+            .expect("couldn't verify chain");
+
+        let err = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2069, 1, 1).unwrap(), UTCDate::ymd(2072, 1, 1).unwrap())
+            .self_signed(DirectoryName::new_common_name("Mismatch"), &leaf_key)
+            .signature_hash_type(SignatureHashType::Ed448)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "couldn't generate certificate: signature error: Ed448 can't be used with Ed25519 key"
+        );
+    }
+
+    #[test]
+    fn valid_rsassa_pss_ca_chain() {
+        let root_pem = crate::test_files::RSA_PSS_ROOT_CA.parse::<Pem>().unwrap();
+        let root = Cert::from_pem(&root_pem).unwrap();
+        let leaf = Cert::from_pem(&crate::test_files::RSA_PSS_LEAF.parse::<Pem>().unwrap()).unwrap();
+
+        assert_eq!(root.to_der().unwrap(), root_pem.data());
+        assert_eq!(
+            SignatureHashType::from_algorithm_identifier(root.signature_algorithm()).unwrap(),
+            SignatureHashType::RsaPssSha256
+        );
+        assert_eq!(
+            SignatureHashType::from_algorithm_identifier(leaf.signature_algorithm()).unwrap(),
+            SignatureHashType::RsaPssSha512
+        );
+
+        let chain = [root];
+        leaf.verify_chain(chain.iter(), &UTCDate::ymd(2030, 1, 1).unwrap())
+            .expect("couldn't verify chain");
+
+        let csr = Csr::from_pem(&crate::test_files::RSA_PSS_CSR.parse::<Pem>().unwrap()).unwrap();
+        csr.verify().expect("couldn't verify csr");
+    }
+
+    #[test]
+    fn rsassa_pss_ca_chain_generation() {
+        let root_key = parse_key(crate::test_files::RSA_2048_PK_1);
+        let leaf_key = parse_key(crate::test_files::RSA_2048_PK_2);
+
+        let root = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2065, 6, 15).unwrap(), UTCDate::ymd(2070, 6, 15).unwrap())
+            .self_signed(DirectoryName::new_common_name("PSS Root CA"), &root_key)
+            .ca(true)
+            .signature_hash_type(SignatureHashType::RsaPssSha384)
+            .build()
+            .expect("couldn't build root ca");
+
+        let csr = Csr::generate(
+            DirectoryName::new_common_name("PSS Leaf"),
+            &leaf_key,
+            SignatureHashType::RsaPssSha256,
+        )
+        .unwrap();
+
+        let signed_leaf = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2069, 1, 1).unwrap(), UTCDate::ymd(2072, 1, 1).unwrap())
+            .subject_from_csr(csr)
+            .issuer_cert(&root, &root_key)
+            .signature_hash_type(SignatureHashType::RsaPssSha512)
+            .build()
+            .expect("couldn't build signed leaf");
+
+        let signed_leaf = Cert::from_der(&signed_leaf.to_der().unwrap()).unwrap();
+        assert_eq!(
+            SignatureHashType::from_algorithm_identifier(signed_leaf.signature_algorithm()).unwrap(),
+            SignatureHashType::RsaPssSha512
+        );
+
+        let chain = [root];
+        signed_leaf
+            .verify_chain(chain.iter(), &UTCDate::ymd(2069, 10, 1).unwrap())
+            .expect("couldn't verify chain");
+    }
+
+    #[test]
+    fn certificate_fingerprints() {
+        let pem = parse_pem(crate::test_files::INTERMEDIATE_CA.as_bytes()).expect("pem");
+        let cert = Cert::from_der(pem.data()).expect("certificate");
+
+        assert_eq!(
+            cert.fingerprint(HashAlgorithm::Sha1).expect("fingerprint"),
+            hex::decode("0aadd68de06220d9988a271ccbc840c4203aa930").expect("hex")
+        );
+        assert_eq!(
+            cert.fingerprint(HashAlgorithm::Sha256).expect("fingerprint"),
+            hex::decode("672eab1bdf7167c5f00dd4be04137d22af719ebd3d9f58e3af8974e878c7a700").expect("hex")
+        );
+        assert_eq!(
+            cert.fingerprint(HashAlgorithm::Sha512).expect("fingerprint"),
+            hex::decode(
+                "f50ca4db7efbe3a8e3949391859c460c58c5be71b4213367eb41608cac63c7c7\
+                 5e14f86cba620799a30825d9d420606858738e8376d9bd073079a85f509cca20"
+            )
+            .expect("hex")
+        );
+        assert_eq!(
+            cert.spki_pin().expect("spki pin"),
+            "rwEk6lD74Xs2gK7eIA6J5PCl3CZpcGFGefeuOqFwVZI="
+        );
+
+        let csr = Csr::from_pem(&crate::test_files::CSR.parse::<Pem>().expect("pem")).expect("csr");
+        assert_eq!(
+            csr.fingerprint(HashAlgorithm::Sha256).expect("fingerprint"),
+            hex::decode("ca94001babefce2224c0e16e23ac029b9cebed371676a3beaf235939fea4d162").expect("hex")
+        );
+        assert_eq!(
+            csr.spki_pin().expect("spki pin"),
+            "z7eflCRejxJCys+HZraQRfkiFrYOxJ5alR9CHOkNFv4="
+        );
+    }
+
+    #[test]
+    fn fingerprint_of_non_canonical_der() {
+        // same certificate, with a superfluous leading zero in the length of the outer SEQUENCE
+        let pem = parse_pem(crate::test_files::INTERMEDIATE_CA.as_bytes()).expect("pem");
+        let der = pem.data();
+        assert_eq!(der[1], 0x82);
+        let mut non_canonical_der = vec![der[0], 0x83, 0x00];
+        non_canonical_der.extend_from_slice(&der[2..]);
+
+        let cert = Cert::from_der(&non_canonical_der).expect("certificate");
+        assert_eq!(cert, Cert::from_der(der).expect("certificate"));
+        assert_eq!(
+            cert.fingerprint(HashAlgorithm::Sha256).expect("fingerprint"),
+            HashAlgorithm::Sha256.digest(&non_canonical_der)
+        );
+        assert_ne!(
+            cert.fingerprint(HashAlgorithm::Sha256).expect("fingerprint"),
+            HashAlgorithm::Sha256.digest(&cert.to_der().expect("der"))
+        );
+    }
 }
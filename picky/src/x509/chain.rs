@@ -0,0 +1,620 @@
+use crate::x509::{
+    certificate::{CaChainError, Cert, CertError},
+    date::UTCDate,
+    policy::PolicySettings,
+    purpose::KeyPurpose,
+    revocation::RevocationSettings,
+    trust_store::TrustStore,
+};
+use oid::ObjectIdentifier;
+use std::cell::RefCell;
+
+/// Maximum number of certificates in a certification path (leaf and trust anchor included)
+const MAX_PATH_LENGTH: usize = 10;
+
+/// Maximum number of candidate issuers tried while searching a certification path
+///
+/// Certificates sharing the same name and key can otherwise be combined in a factorial
+/// number of paths.
+const MAX_PATH_CANDIDATES: usize = 1000;
+
+// Statically checks the field actually exists and returns a &'static str of the field name
+macro_rules! field_str {
+    ($field:ident) => {{
+        ::static_assertions::assert_fields!(ChainBuilderInner: $field);
+        stringify!($field)
+    }};
+}
+
+#[derive(Default, Clone, Debug)]
+struct ChainBuilderInner<'a> {
+    leaf: Option<&'a Cert>,
+    intermediates: Vec<&'a Cert>,
+    trust_anchors: Vec<&'a Cert>,
+    trust_store: Option<&'a TrustStore>,
+    now: Option<UTCDate>,
+    policy_settings: Option<PolicySettings>,
+    purpose: Option<KeyPurpose>,
+    revocation_settings: Option<RevocationSettings<'a>>,
+}
+
+/// Optional checks performed by `Cert::verify_chain_with_settings` on top of the ones of
+/// `Cert::verify_chain`.
+#[derive(Default, Clone, Debug)]
+pub struct ChainValidationSettings<'a> {
+    trust_store: Option<&'a TrustStore>,
+    policy_settings: Option<PolicySettings>,
+    purpose: Option<KeyPurpose>,
+    revocation_settings: Option<RevocationSettings<'a>>,
+}
+
+impl<'a> ChainValidationSettings<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The path must end with a trust anchor of the store. Otherwise any self-signed root is accepted.
+    pub fn trust_store(mut self, trust_store: &'a TrustStore) -> Self {
+        self.trust_store = Some(trust_store);
+        self
+    }
+
+    /// Certificate policies are processed only when provided (see `PolicySettings::validate`).
+    pub fn policy_settings(mut self, policy_settings: PolicySettings) -> Self {
+        self.policy_settings = Some(policy_settings);
+        self
+    }
+
+    /// Intended usage of the leaf is checked only when provided (see `KeyPurpose::validate`).
+    pub fn purpose(mut self, purpose: KeyPurpose) -> Self {
+        self.purpose = Some(purpose);
+        self
+    }
+
+    /// Revocation status is checked only when provided.
+    pub fn revocation_settings(mut self, revocation_settings: RevocationSettings<'a>) -> Self {
+        self.revocation_settings = Some(revocation_settings);
+        self
+    }
+
+    pub(crate) fn is_trust_anchor(&self, cert: &Cert) -> bool {
+        self.trust_store.map(|store| store.contains(cert)).unwrap_or(false)
+    }
+
+    /// Runs the enabled checks on a certification path ordered from the leaf to the root.
+    pub(crate) fn validate(&self, path: &[&Cert], now: &UTCDate) -> Result<(), CertError> {
+        if let (Some(trust_store), Some(root)) = (self.trust_store, path.last()) {
+            if !trust_store.contains(root) {
+                return Err(CertError::InvalidChain {
+                    source: CaChainError::UntrustedRoot {
+                        cert_id: root.subject_name().to_string(),
+                    },
+                });
+            }
+        }
+
+        if let Some(policy_settings) = &self.policy_settings {
+            policy_settings.validate(path)?;
+        }
+
+        if let Some(purpose) = &self.purpose {
+            purpose.validate(path)?;
+        }
+
+        if let Some(revocation_settings) = &self.revocation_settings {
+            revocation_settings.validate(path, now)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Discovers and validates a certification path from a leaf certificate to a trust anchor.
+///
+/// Intermediate certificates may be provided in any order and may include certificates unrelated
+/// to the leaf (as sent by many TLS peers). Candidate issuers are selected using subject / issuer
+/// names and key identifiers (see `Cert::is_parent_of`) and alternative paths are tried when
+/// a candidate path fails validation. Trust anchors end the path as they are: unlike with
+/// `Cert::verify_chain`, they don't have to be self-signed roots (e.g. a pinned intermediate).
+///
+/// Certificate policies are processed only when `policy_settings` is provided, and the intended
+/// usage of the leaf is checked only when `purpose` is provided.
+/// Likewise, revocation status is checked only when `revocation_settings` is provided
+/// (and only once a candidate path is otherwise valid).
+#[derive(Default, Clone, Debug)]
+pub struct ChainBuilder<'a> {
+    inner: RefCell<ChainBuilderInner<'a>>,
+}
+
+impl<'a> ChainBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Required
+    #[inline]
+    pub fn leaf(&self, leaf: &'a Cert) -> &Self {
+        self.inner.borrow_mut().leaf = Some(leaf);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn intermediates<I: IntoIterator<Item = &'a Cert>>(&self, intermediates: I) -> &Self {
+        self.inner.borrow_mut().intermediates.extend(intermediates);
+        self
+    }
+
+    /// Required (alternative: `trust_store`)
+    #[inline]
+    pub fn trust_anchors<I: IntoIterator<Item = &'a Cert>>(&self, trust_anchors: I) -> &Self {
+        self.inner.borrow_mut().trust_anchors.extend(trust_anchors);
+        self
+    }
+
+    /// Required (alternative: `trust_anchors`)
+    #[inline]
+    pub fn trust_store(&self, trust_store: &'a TrustStore) -> &Self {
+        self.inner.borrow_mut().trust_store = Some(trust_store);
+        self
+    }
+
+    /// Required
+    #[inline]
+    pub fn now(&self, now: UTCDate) -> &Self {
+        self.inner.borrow_mut().now = Some(now);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn policy_settings(&self, policy_settings: PolicySettings) -> &Self {
+        self.inner.borrow_mut().policy_settings = Some(policy_settings);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn purpose(&self, purpose: KeyPurpose) -> &Self {
+        self.inner.borrow_mut().purpose = Some(purpose);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn revocation_settings(&self, revocation_settings: RevocationSettings<'a>) -> &Self {
+        self.inner.borrow_mut().revocation_settings = Some(revocation_settings);
+        self
+    }
+
+    /// Returns the validated certification path, ordered from the leaf to the trust anchor.
+    pub fn build(&self) -> Result<Vec<&'a Cert>, CertError> {
+        self.build_with_policies().map(|(path, _)| path)
+    }
+
+    /// Same as `build`, also returns the policies the path is valid for when `policy_settings`
+    /// is provided (see `PolicySettings::validate`).
+    pub fn build_with_policies(&self) -> Result<(Vec<&'a Cert>, Option<Vec<ObjectIdentifier>>), CertError> {
+        let mut inner = self.inner.borrow_mut();
+
+        let leaf = inner
+            .leaf
+            .take()
+            .ok_or(CertError::MissingBuilderArgument { arg: field_str!(leaf) })?;
+
+        if inner.trust_anchors.is_empty() && inner.trust_store.is_none() {
+            return Err(CertError::MissingBuilderArgument {
+                arg: field_str!(trust_anchors),
+            });
+        }
+
+        let now = inner
+            .now
+            .take()
+            .ok_or(CertError::MissingBuilderArgument { arg: field_str!(now) })?;
+
+        let mut search = PathSearch {
+            intermediates: &inner.intermediates,
+            trust_anchors: &inner.trust_anchors,
+            trust_store: inner.trust_store,
+            now: &now,
+            policy_settings: inner.policy_settings.as_ref(),
+            purpose: inner.purpose.as_ref(),
+            revocation_settings: inner.revocation_settings.as_ref(),
+            remaining_candidates: MAX_PATH_CANDIDATES,
+            first_error: None,
+            valid_policies: None,
+        };
+
+        let mut path = vec![leaf];
+        if search.search(&mut path) {
+            return Ok((path, search.valid_policies));
+        }
+
+        if search.remaining_candidates == 0 {
+            return Err(CertError::InvalidChain {
+                source: CaChainError::PathSearchLimitExceeded {
+                    cert_id: leaf.subject_name().to_string(),
+                },
+            });
+        }
+
+        Err(search.first_error.unwrap_or_else(|| CertError::InvalidChain {
+            source: CaChainError::NoPathFound {
+                cert_id: leaf.subject_name().to_string(),
+            },
+        }))
+    }
+}
+
+struct PathSearch<'a, 'b> {
+    intermediates: &'b [&'a Cert],
+    trust_anchors: &'b [&'a Cert],
+    trust_store: Option<&'a TrustStore>,
+    now: &'b UTCDate,
+    policy_settings: Option<&'b PolicySettings>,
+    purpose: Option<&'b KeyPurpose>,
+    revocation_settings: Option<&'b RevocationSettings<'a>>,
+    remaining_candidates: usize,
+    first_error: Option<CertError>,
+    /// Policies of the last validated path
+    valid_policies: Option<Vec<ObjectIdentifier>>,
+}
+
+impl<'a> PathSearch<'a, '_> {
+    /// Depth-first search of a valid path starting with the certificates in `path`.
+    fn search(&mut self, path: &mut Vec<&'a Cert>) -> bool {
+        let current = *path.last().expect("path is never empty");
+
+        if path.len() == 1 && self.is_trust_anchor(current) {
+            // leaf is itself a trust anchor
+            return self.validate(path);
+        }
+
+        // trust anchors are preferred to intermediates in order to find the shortest path
+        for anchor in self.trust_anchor_issuers(current) {
+            if path.contains(&anchor) {
+                continue;
+            }
+
+            if !self.take_candidate() {
+                return false;
+            }
+
+            path.push(anchor);
+            if self.validate(path) {
+                return true;
+            }
+            path.pop();
+        }
+
+        if path.len() + 1 >= MAX_PATH_LENGTH {
+            return false;
+        }
+
+        for &intermediate in self.intermediates {
+            if path.contains(&intermediate) || intermediate.is_parent_of(current).is_err() {
+                continue;
+            }
+
+            if !self.take_candidate() {
+                return false;
+            }
+
+            path.push(intermediate);
+            if self.search(path) {
+                return true;
+            }
+            path.pop();
+        }
+
+        false
+    }
+
+    /// Consumes one candidate from the search budget, returns false when the budget is exhausted.
+    fn take_candidate(&mut self) -> bool {
+        if self.remaining_candidates == 0 {
+            return false;
+        }
+
+        self.remaining_candidates -= 1;
+        true
+    }
+
+    fn is_trust_anchor(&self, cert: &Cert) -> bool {
+        self.trust_anchors.contains(&cert) || self.trust_store.map(|store| store.contains(cert)).unwrap_or(false)
+    }
+
+    fn trust_anchor_issuers(&self, cert: &Cert) -> Vec<&'a Cert> {
+        let mut issuers: Vec<&'a Cert> = self
+            .trust_anchors
+            .iter()
+            .copied()
+            .filter(|anchor| anchor.is_parent_of(cert).is_ok())
+            .collect();
+
+        if let Some(store) = self.trust_store {
+            issuers.extend(store.find_issuers(cert));
+        }
+
+        issuers
+    }
+
+    fn validate(&mut self, path: &[&'a Cert]) -> bool {
+        let mut valid_policies = None;
+        let result = path[0]
+            .verify_chain_to_trust_anchor(path[1..].iter().copied(), self.now)
+            .and_then(|()| match self.policy_settings {
+                Some(policy_settings) => policy_settings
+                    .validate(path)
+                    .map(|policies| valid_policies = Some(policies)),
+                None => Ok(()),
+            })
+            .and_then(|()| match self.purpose {
+                Some(purpose) => purpose.validate(path),
+                None => Ok(()),
+            })
+            .and_then(|()| match self.revocation_settings {
+                Some(revocation_settings) => revocation_settings.validate(path, self.now),
+                None => Ok(()),
+            });
+
+        match result {
+            Ok(()) => {
+                self.valid_policies = valid_policies;
+                true
+            }
+            Err(e) => {
+                if self.first_error.is_none() {
+                    self.first_error = Some(e);
+                }
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        key::PrivateKey,
+        pem::Pem,
+        x509::{certificate::CertificateBuilder, name::DirectoryName},
+    };
+
+    fn parse_key(pem_str: &str) -> PrivateKey {
+        let pem = pem_str.parse::<Pem>().unwrap();
+        PrivateKey::from_pem(&pem).unwrap()
+    }
+
+    fn date(year: u16) -> UTCDate {
+        UTCDate::ymd(year, 1, 1).unwrap()
+    }
+
+    struct Pki {
+        root: Cert,
+        intermediate: Cert,
+        expired_intermediate: Cert,
+        other_root: Cert,
+        other_intermediate: Cert,
+        leaf: Cert,
+    }
+
+    fn build_pki() -> Pki {
+        let root_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let intermediate_key = parse_key(crate::test_files::EC_P384_PK_1);
+        let other_root_key = parse_key(crate::test_files::ED25519_PK_1);
+        let leaf_key = parse_key(crate::test_files::EC_P256_PK_2);
+
+        let root = CertificateBuilder::new()
+            .valididy(date(2060), date(2080))
+            .self_signed(DirectoryName::new_common_name("Chain Root CA"), &root_key)
+            .ca(true)
+            .build()
+            .expect("root");
+
+        let intermediate_builder = CertificateBuilder::new();
+        intermediate_builder
+            .subject(
+                DirectoryName::new_common_name("Chain Authority"),
+                intermediate_key.to_public_key(),
+            )
+            .issuer_cert(&root, &root_key)
+            .ca(true);
+
+        // same name and key than `intermediate` but expired
+        let expired_intermediate = intermediate_builder
+            .clone()
+            .valididy(date(2060), date(2065))
+            .build()
+            .expect("expired intermediate");
+        let intermediate = intermediate_builder
+            .valididy(date(2060), date(2080))
+            .build()
+            .expect("intermediate");
+
+        let other_root = CertificateBuilder::new()
+            .valididy(date(2060), date(2080))
+            .self_signed(DirectoryName::new_common_name("Other Root CA"), &other_root_key)
+            .ca(true)
+            .build()
+            .expect("other root");
+
+        let other_intermediate = CertificateBuilder::new()
+            .valididy(date(2060), date(2080))
+            .subject(
+                DirectoryName::new_common_name("Other Authority"),
+                leaf_key.to_public_key(),
+            )
+            .issuer_cert(&other_root, &other_root_key)
+            .ca(true)
+            .build()
+            .expect("other intermediate");
+
+        let leaf = CertificateBuilder::new()
+            .valididy(date(2060), date(2080))
+            .subject(
+                DirectoryName::new_common_name("chain.example"),
+                leaf_key.to_public_key(),
+            )
+            .issuer_cert(&intermediate, &intermediate_key)
+            .build()
+            .expect("leaf");
+
+        Pki {
+            root,
+            intermediate,
+            expired_intermediate,
+            other_root,
+            other_intermediate,
+            leaf,
+        }
+    }
+
+    #[test]
+    fn unordered_chain_with_extra_certificates() {
+        let pki = build_pki();
+
+        let path = ChainBuilder::new()
+            .leaf(&pki.leaf)
+            .intermediates(vec![&pki.other_intermediate, &pki.root, &pki.intermediate])
+            .trust_anchors(vec![&pki.other_root, &pki.root])
+            .now(date(2070))
+            .build()
+            .expect("path");
+
+        assert_eq!(path, vec![&pki.leaf, &pki.intermediate, &pki.root]);
+    }
+
+    #[test]
+    fn alternative_path_is_tried() {
+        let pki = build_pki();
+
+        let path = ChainBuilder::new()
+            .leaf(&pki.leaf)
+            .intermediates(vec![&pki.expired_intermediate, &pki.intermediate])
+            .trust_anchors(vec![&pki.root])
+            .now(date(2070))
+            .build()
+            .expect("path");
+
+        assert_eq!(path, vec![&pki.leaf, &pki.intermediate, &pki.root]);
+
+        let err = ChainBuilder::new()
+            .leaf(&pki.leaf)
+            .intermediates(vec![&pki.expired_intermediate])
+            .trust_anchors(vec![&pki.root])
+            .now(date(2070))
+            .build()
+            .expect_err("expired intermediate");
+
+        assert_eq!(
+            err.to_string(),
+            "invalid certificate 'CN=Chain Authority': \
+             certificate expired (not after: 2065-01-01 00:00:00, now: 2070-01-01 00:00:00)"
+        );
+    }
+
+    #[test]
+    fn trust_anchor_as_leaf() {
+        let pki = build_pki();
+
+        let path = ChainBuilder::new()
+            .leaf(&pki.root)
+            .trust_anchors(vec![&pki.root])
+            .now(date(2070))
+            .build()
+            .expect("path");
+
+        assert_eq!(path, vec![&pki.root]);
+    }
+
+    #[test]
+    fn non_self_signed_trust_anchor() {
+        let pki = build_pki();
+
+        // the pinned intermediate ends the path even though it isn't a self-signed root
+        let path = ChainBuilder::new()
+            .leaf(&pki.leaf)
+            .intermediates(vec![&pki.root])
+            .trust_anchors(vec![&pki.intermediate])
+            .now(date(2070))
+            .build()
+            .expect("path");
+
+        assert_eq!(path, vec![&pki.leaf, &pki.intermediate]);
+
+        pki.leaf
+            .verify_chain([&pki.intermediate].iter().copied(), &date(2070))
+            .expect_err("intermediate is not a root");
+    }
+
+    #[test]
+    fn no_path_found() {
+        let pki = build_pki();
+
+        let err = ChainBuilder::new()
+            .leaf(&pki.leaf)
+            .intermediates(vec![&pki.intermediate, &pki.other_intermediate])
+            .trust_anchors(vec![&pki.other_root])
+            .now(date(2070))
+            .build()
+            .expect_err("untrusted root");
+
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: no certification path found from 'CN=chain.example' to a trust anchor"
+        );
+
+        let err = ChainBuilder::new()
+            .leaf(&pki.leaf)
+            .intermediates(vec![&pki.intermediate])
+            .now(date(2070))
+            .build()
+            .expect_err("no trust anchor");
+
+        assert_eq!(err.to_string(), "missing required builder argument `trust_anchors`");
+    }
+
+    #[test]
+    fn path_search_limit() {
+        let pki = build_pki();
+        let mesh_key = parse_key(crate::test_files::EC_P384_PK_1);
+
+        // distinct certificates with the same name and key are all parents of each other
+        let mesh: Vec<Cert> = (0..20)
+            .map(|i| {
+                CertificateBuilder::new()
+                    .valididy(date(2060), date(2080 + i))
+                    .self_signed(DirectoryName::new_common_name("Mesh Authority"), &mesh_key)
+                    .ca(true)
+                    .build()
+                    .expect("mesh authority")
+            })
+            .collect();
+
+        let leaf = CertificateBuilder::new()
+            .valididy(date(2060), date(2080))
+            .subject(
+                DirectoryName::new_common_name("mesh.example"),
+                parse_key(crate::test_files::EC_P256_PK_2).to_public_key(),
+            )
+            .issuer_cert(&mesh[0], &mesh_key)
+            .build()
+            .expect("leaf");
+
+        let err = ChainBuilder::new()
+            .leaf(&leaf)
+            .intermediates(mesh.iter())
+            .trust_anchors(vec![&pki.root])
+            .now(date(2070))
+            .build()
+            .expect_err("no trust anchor in the mesh");
+
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: too many candidate paths to search from 'CN=mesh.example'"
+        );
+    }
+}
@@ -0,0 +1,772 @@
+use crate::{
+    oids,
+    pem::Pem,
+    signature::{SignatureError, SignatureHashType, Signer},
+    x509::{
+        certificate::{Cert, CertError},
+        date::UTCDate,
+        extension::{
+            uint_to_integer_bytes, AuthorityKeyIdentifier, CRLNumber, CRLReason, ExtensionView, KeyIdentifier,
+        },
+        name::DirectoryName,
+        private::{CertificateList, RawCertificateList, RevokedCertificate, TBSCertList, Version},
+        Extension, Extensions,
+    },
+    AlgorithmIdentifier,
+};
+use oid::ObjectIdentifier;
+use picky_asn1::{
+    bit_string::BitString,
+    wrapper::{IntegerAsn1, RawDerAsn1},
+};
+use picky_asn1_der::Asn1DerError;
+use snafu::{ResultExt, Snafu};
+use std::{cell::RefCell, fmt};
+
+#[derive(Debug, Snafu)]
+pub enum CrlError {
+    /// couldn't generate CRL
+    #[snafu(display("couldn't generate CRL: {}", source))]
+    CrlGeneration {
+        #[snafu(source(from(CrlError, Box::new)))]
+        source: Box<CrlError>,
+    },
+
+    /// asn1 serialization error
+    #[snafu(display("(asn1) couldn't serialize {}: {}", element, source))]
+    Asn1Serialization {
+        element: &'static str,
+        source: Asn1DerError,
+    },
+
+    /// asn1 deserialization error
+    #[snafu(display("(asn1) couldn't deserialize {}: {}", element, source))]
+    Asn1Deserialization {
+        element: &'static str,
+        source: Asn1DerError,
+    },
+
+    /// signature error
+    #[snafu(display("signature error: {}", source))]
+    Signature { source: SignatureError },
+
+    /// invalid PEM label error
+    #[snafu(display("invalid PEM label: {}", label))]
+    InvalidPemLabel { label: String },
+
+    /// extension not found
+    #[snafu(display("extension not found: {}", name))]
+    ExtensionNotFound { name: &'static str },
+
+    /// missing required builder argument
+    #[snafu(display("missing required builder argument `{}`", arg))]
+    MissingBuilderArgument { arg: &'static str },
+
+    /// invalid issuer certificate
+    #[snafu(display("invalid issuer certificate: {}", source))]
+    InvalidIssuer { source: CertError },
+
+    /// issuer name doesn't match
+    #[snafu(display("issuer name doesn't match (expected: {}, got: {})", expected, actual))]
+    IssuerNameMismatch { expected: String, actual: String },
+
+    /// authority key id doesn't match
+    #[snafu(display("authority key id doesn't match (expected: {:?}, got: {:?})", expected, actual))]
+    AuthorityKeyIdMismatch { expected: Vec<u8>, actual: Vec<u8> },
+
+    /// issuer certificate is not allowed to sign CRLs
+    #[snafu(display("issuer certificate '{}' is not allowed to sign CRLs", issuer_id))]
+    IssuerCannotSignCrl { issuer_id: String },
+
+    /// CRL contains a critical extension that isn't supported
+    #[snafu(display("unsupported critical extension: {}", oid))]
+    UnsupportedCriticalExtension { oid: String },
+}
+
+const CRL_PEM_LABEL: &str = "X509 CRL";
+
+/// CRL extensions understood by `Crl`. Any other critical extension (delta CRL indicator,
+/// issuing distribution point…) makes the CRL unusable.
+const SUPPORTED_CRL_EXTENSIONS: &[&str] = &[oids::AUTHORITY_KEY_IDENTIFIER, oids::CRL_NUMBER];
+
+/// CRL entry extensions understood by `RevokedCert`.
+const SUPPORTED_CRL_ENTRY_EXTENSIONS: &[&str] = &[oids::REASON_CODE];
+
+/// Certificate Revocation List (X.509 v2 CRL)
+///
+/// https://tools.ietf.org/html/rfc5280#section-5
+///
+/// The TBS cert list DER is kept as received (when any) to verify the signature over it.
+#[derive(Clone, Debug)]
+pub struct Crl(CertificateList, Option<Vec<u8>>);
+
+impl PartialEq for Crl {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl From<CertificateList> for Crl {
+    fn from(certificate_list: CertificateList) -> Self {
+        Self(certificate_list, None)
+    }
+}
+
+fn find_extension(extensions: &[Extension], oid: ObjectIdentifier) -> Option<&Extension> {
+    extensions.iter().find(|ext| ext.extn_id() == &oid)
+}
+
+fn check_critical_extensions(extensions: &[Extension], supported: &[&str]) -> Result<(), CrlError> {
+    for ext in extensions.iter().filter(|ext| ext.critical()) {
+        let oid: String = (&ext.extn_id().0).into();
+        if !supported.contains(&oid.as_str()) {
+            return Err(CrlError::UnsupportedCriticalExtension { oid });
+        }
+    }
+    Ok(())
+}
+
+impl Crl {
+    pub fn from_der<T: ?Sized + AsRef<[u8]>>(der: &T) -> Result<Self, CrlError> {
+        let raw_certificate_list: RawCertificateList =
+            picky_asn1_der::from_bytes(der.as_ref()).context(Asn1Deserialization {
+                element: "certificate list",
+            })?;
+        let tbs_der = raw_certificate_list.tbs_cert_list.0;
+        let tbs_cert_list = picky_asn1_der::from_bytes(&tbs_der).context(Asn1Deserialization {
+            element: "tbs cert list",
+        })?;
+        Ok(Self(
+            CertificateList {
+                tbs_cert_list,
+                signature_algorithm: raw_certificate_list.signature_algorithm,
+                signature_value: raw_certificate_list.signature_value,
+            },
+            Some(tbs_der),
+        ))
+    }
+
+    pub fn from_pem(pem: &Pem) -> Result<Self, CrlError> {
+        match pem.label() {
+            CRL_PEM_LABEL => Self::from_der(pem.data()),
+            _ => Err(CrlError::InvalidPemLabel {
+                label: pem.label().to_owned(),
+            }),
+        }
+    }
+
+    pub fn to_der(&self) -> Result<Vec<u8>, CrlError> {
+        match &self.1 {
+            // keep the received TBS cert list encoding so the signature stays valid
+            Some(tbs_der) => picky_asn1_der::to_vec(&RawCertificateList {
+                tbs_cert_list: RawDerAsn1(tbs_der.clone()),
+                signature_algorithm: self.0.signature_algorithm.clone(),
+                signature_value: self.0.signature_value.clone(),
+            }),
+            None => picky_asn1_der::to_vec(&self.0),
+        }
+        .context(Asn1Serialization {
+            element: "certificate list",
+        })
+    }
+
+    pub fn to_pem(&self) -> Result<Pem<'static>, CrlError> {
+        Ok(Pem::new(CRL_PEM_LABEL, self.to_der()?))
+    }
+
+    pub fn signature_algorithm(&self) -> &AlgorithmIdentifier {
+        &self.0.tbs_cert_list.signature
+    }
+
+    pub fn issuer_name(&self) -> DirectoryName {
+        self.0.tbs_cert_list.issuer.clone().into()
+    }
+
+    pub fn this_update(&self) -> UTCDate {
+        self.0.tbs_cert_list.this_update.clone().into()
+    }
+
+    pub fn next_update(&self) -> Option<UTCDate> {
+        self.0.tbs_cert_list.next_update.clone().map(UTCDate::from)
+    }
+
+    pub fn extensions(&self) -> &[Extension] {
+        match &self.0.tbs_cert_list.crl_extensions {
+            Some(extensions) => (extensions.0).0.as_slice(),
+            None => &[],
+        }
+    }
+
+    pub fn crl_number(&self) -> Result<&CRLNumber, CrlError> {
+        let ext = find_extension(self.extensions(), oids::crl_number())
+            .ok_or(CrlError::ExtensionNotFound { name: "CRL number" })?;
+        match ext.extn_value() {
+            ExtensionView::CRLNumber(crl_number) => Ok(crl_number),
+            _ => unreachable!("invalid extension (expected CRL number)"),
+        }
+    }
+
+    pub fn authority_key_identifier(&self) -> Result<&AuthorityKeyIdentifier, CrlError> {
+        let ext =
+            find_extension(self.extensions(), oids::authority_key_identifier()).ok_or(CrlError::ExtensionNotFound {
+                name: "authority key identifier",
+            })?;
+        match ext.extn_value() {
+            ExtensionView::AuthorityKeyIdentifier(aki) => Ok(aki),
+            _ => unreachable!("invalid extension (expected authority key identifier)"),
+        }
+    }
+
+    pub fn revoked_certificates(&self) -> impl Iterator<Item = RevokedCert<'_>> {
+        self.0
+            .tbs_cert_list
+            .revoked_certificates
+            .iter()
+            .flat_map(|revoked_certificates| revoked_certificates.0.iter())
+            .map(RevokedCert)
+    }
+
+    pub fn find_revoked(&self, serial_number: &IntegerAsn1) -> Option<RevokedCert<'_>> {
+        self.revoked_certificates()
+            .find(|revoked| revoked.serial_number().as_bytes_be() == serial_number.as_bytes_be())
+    }
+
+    /// Whether the given certificate has been revoked by this CRL.
+    ///
+    /// Only the issuer name and the serial number are checked: the CRL itself should be
+    /// verified against the issuer with `verify` beforehand, and it must be current
+    /// (`next_update` not reached yet) since an outdated CRL doesn't list the certificates
+    /// revoked since then. `CrlRevocationChecker` performs all these checks.
+    pub fn is_revoked(&self, cert: &Cert) -> bool {
+        cert.issuer_name() == self.issuer_name() && self.find_revoked(cert.serial_number()).is_some()
+    }
+
+    /// Checks the CRL has been issued and signed by the given certificate.
+    ///
+    /// CRLs with unsupported critical CRL or CRL entry extensions are rejected.
+    pub fn verify(&self, issuer: &Cert) -> Result<(), CrlError> {
+        let crl_issuer_name = self.issuer_name();
+        let issuer_subject_name = issuer.subject_name();
+        if crl_issuer_name != issuer_subject_name {
+            return Err(CrlError::IssuerNameMismatch {
+                expected: crl_issuer_name.to_string(),
+                actual: issuer_subject_name.to_string(),
+            });
+        }
+
+        if let Ok(aki) = self.authority_key_identifier() {
+            if let Some(key_identifier) = aki.key_identifier() {
+                let issuer_ski = issuer.subject_key_identifier().context(InvalidIssuer)?;
+                if issuer_ski != key_identifier {
+                    return Err(CrlError::AuthorityKeyIdMismatch {
+                        expected: key_identifier.to_vec(),
+                        actual: issuer_ski.to_vec(),
+                    });
+                }
+            }
+        }
+
+        if let Ok(key_usage) = issuer.key_usage() {
+            if !key_usage.crl_sign() {
+                return Err(CrlError::IssuerCannotSignCrl {
+                    issuer_id: issuer_subject_name.to_string(),
+                });
+            }
+        }
+
+        let hash_type = SignatureHashType::from_algorithm_identifier(&self.0.signature_algorithm).context(Signature)?;
+        // the signature is computed over the TBS cert list as received
+        let msg = match &self.1 {
+            Some(tbs_der) => tbs_der.clone(),
+            None => picky_asn1_der::to_vec(&self.0.tbs_cert_list).context(Asn1Serialization {
+                element: "tbs cert list",
+            })?,
+        };
+        hash_type
+            .verify(issuer.public_key(), &msg, self.0.signature_value.0.payload_view())
+            .context(Signature)?;
+
+        check_critical_extensions(self.extensions(), SUPPORTED_CRL_EXTENSIONS)?;
+        for revoked in self.revoked_certificates() {
+            check_critical_extensions(revoked.extensions(), SUPPORTED_CRL_ENTRY_EXTENSIONS)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An entry of the revoked certificates list
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RevokedCert<'a>(&'a RevokedCertificate);
+
+impl<'a> RevokedCert<'a> {
+    pub fn serial_number(&self) -> &'a IntegerAsn1 {
+        &self.0.user_certificate
+    }
+
+    pub fn revocation_date(&self) -> UTCDate {
+        self.0.revocation_date.clone().into()
+    }
+
+    pub fn extensions(&self) -> &'a [Extension] {
+        match &self.0.crl_entry_extensions {
+            Some(extensions) => extensions.0.as_slice(),
+            None => &[],
+        }
+    }
+
+    /// Returns `None` when the reason code entry extension is absent (or unknown).
+    pub fn reason(&self) -> Option<CRLReason> {
+        match find_extension(self.extensions(), oids::reason_code())?.extn_value() {
+            ExtensionView::ReasonCode(reason) => Some(reason),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct IssuerInfos<'a> {
+    name: DirectoryName,
+    key: &'a dyn Signer,
+}
+
+impl fmt::Debug for IssuerInfos<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IssuerInfos")
+            .field("name", &self.name)
+            .field("key", &"<signer>")
+            .finish()
+    }
+}
+
+// Statically checks the field actually exists and returns a &'static str of the field name
+macro_rules! field_str {
+    ($field:ident) => {{
+        ::static_assertions::assert_fields!(CrlBuilderInner: $field);
+        stringify!($field)
+    }};
+}
+
+#[derive(Default, Clone, Debug)]
+struct CrlBuilderInner<'a> {
+    this_update: Option<UTCDate>,
+    next_update: Option<UTCDate>,
+    issuer_infos: Option<IssuerInfos<'a>>,
+    authority_key_identifier: Option<Vec<u8>>,
+    crl_number: Option<u64>,
+    signature_hash_type: Option<SignatureHashType>,
+    revoked_certificates: Vec<RevokedCertificate>,
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct CrlBuilder<'a> {
+    inner: RefCell<CrlBuilderInner<'a>>,
+}
+
+impl<'a> CrlBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Required
+    #[inline]
+    pub fn this_update(&self, this_update: UTCDate) -> &Self {
+        self.inner.borrow_mut().this_update = Some(this_update);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn next_update(&self, next_update: UTCDate) -> &Self {
+        self.inner.borrow_mut().next_update = Some(next_update);
+        self
+    }
+
+    /// Required (alternative: `issuer_cert`)
+    #[inline]
+    pub fn issuer(&self, issuer_name: DirectoryName, issuer_key: &'a dyn Signer) -> &Self {
+        self.inner.borrow_mut().issuer_infos = Some(IssuerInfos {
+            name: issuer_name,
+            key: issuer_key,
+        });
+        self
+    }
+
+    /// Required (alternative: `issuer`)
+    #[inline]
+    pub fn issuer_cert(&self, issuer_cert: &Cert, issuer_key: &'a dyn Signer) -> &Self {
+        let builder = self.issuer(issuer_cert.subject_name(), issuer_key);
+
+        if let Ok(issuer_ski) = issuer_cert.subject_key_identifier() {
+            self.authority_key_identifier(issuer_ski.to_vec())
+        } else {
+            builder
+        }
+    }
+
+    /// Required (alternative: `issuer_cert`)
+    #[inline]
+    pub fn authority_key_identifier(&self, aki: Vec<u8>) -> &Self {
+        self.inner.borrow_mut().authority_key_identifier = Some(aki);
+        self
+    }
+
+    /// Required
+    #[inline]
+    pub fn crl_number(&self, crl_number: u64) -> &Self {
+        self.inner.borrow_mut().crl_number = Some(crl_number);
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn signature_hash_type(&self, signature_hash_type: SignatureHashType) -> &Self {
+        self.inner.borrow_mut().signature_hash_type = Some(signature_hash_type);
+        self
+    }
+
+    /// Optional, may be called multiple times
+    #[inline]
+    pub fn revoke(&self, serial_number: IntegerAsn1, revocation_date: UTCDate, reason: Option<CRLReason>) -> &Self {
+        let crl_entry_extensions = reason.map(|reason| Extensions(vec![Extension::new_reason_code(reason)]));
+        self.inner.borrow_mut().revoked_certificates.push(RevokedCertificate {
+            user_certificate: serial_number,
+            revocation_date: revocation_date.into(),
+            crl_entry_extensions,
+        });
+        self
+    }
+
+    /// Optional, may be called multiple times (alternative: `revoke`)
+    #[inline]
+    pub fn revoke_cert(&self, cert: &Cert, revocation_date: UTCDate, reason: Option<CRLReason>) -> &Self {
+        self.revoke(cert.serial_number().clone(), revocation_date, reason)
+    }
+
+    pub fn build(&self) -> Result<Crl, CrlError> {
+        let mut inner = self.inner.borrow_mut();
+
+        let this_update = inner.this_update.take().ok_or(CrlError::MissingBuilderArgument {
+            arg: field_str!(this_update),
+        })?;
+        let next_update = inner.next_update.take();
+
+        let issuer_infos = inner.issuer_infos.take().ok_or(CrlError::MissingBuilderArgument {
+            arg: field_str!(issuer_infos),
+        })?;
+        let aki = inner
+            .authority_key_identifier
+            .take()
+            .ok_or(CrlError::MissingBuilderArgument {
+                arg: field_str!(authority_key_identifier),
+            })?;
+        let crl_number = inner.crl_number.take().ok_or(CrlError::MissingBuilderArgument {
+            arg: field_str!(crl_number),
+        })?;
+
+        let signature_hash_type = inner
+            .signature_hash_type
+            .take()
+            .unwrap_or_else(|| issuer_infos.key.signature_hash_type());
+
+        let revoked_certificates = std::mem::take(&mut inner.revoked_certificates);
+
+        drop(inner);
+
+        let extensions = Extensions(vec![
+            Extension::new_authority_key_identifier(KeyIdentifier::from(aki), None, None),
+            Extension::new_crl_number(IntegerAsn1::from(uint_to_integer_bytes(crl_number))),
+        ]);
+
+        let tbs_cert_list = TBSCertList {
+            version: Some(Version::V2),
+            signature: signature_hash_type.into(),
+            issuer: issuer_infos.name.into(),
+            this_update: this_update.into(),
+            next_update: next_update.map(Into::into),
+            revoked_certificates: if revoked_certificates.is_empty() {
+                None
+            } else {
+                Some(revoked_certificates.into())
+            },
+            crl_extensions: Some(extensions.into()),
+        };
+
+        let tbs_der = picky_asn1_der::to_vec(&tbs_cert_list)
+            .context(Asn1Serialization {
+                element: "tbs cert list",
+            })
+            .context(CrlGeneration)?;
+        let signature_value = BitString::with_bytes(
+            issuer_infos
+                .key
+                .sign(&tbs_der, signature_hash_type)
+                .context(Signature)
+                .context(CrlGeneration)?,
+        );
+
+        Ok(Crl(
+            CertificateList {
+                tbs_cert_list,
+                signature_algorithm: signature_hash_type.into(),
+                signature_value: signature_value.into(),
+            },
+            Some(tbs_der),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{key::PrivateKey, pem::parse_pem, x509::certificate::CertificateBuilder};
+
+    fn parse_cert(pem_str: &str) -> Cert {
+        Cert::from_pem(&parse_pem(pem_str).unwrap()).unwrap()
+    }
+
+    fn parse_key(pem_str: &str) -> PrivateKey {
+        PrivateKey::from_pem(&parse_pem(pem_str).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn openssl_crl() {
+        let pem = parse_pem(crate::test_files::CRL).unwrap();
+        let crl = Crl::from_pem(&pem).expect("crl");
+        assert_eq!(crl.to_der().unwrap(), pem.data());
+
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        crl.verify(&ca).expect("verify crl");
+
+        assert_eq!(crl.issuer_name().to_string(), "CN=CRL Test CA");
+        assert_eq!(crl.crl_number().unwrap().as_bytes_be(), &[0x2A]);
+        assert_eq!(
+            crl.authority_key_identifier().unwrap().key_identifier(),
+            Some(ca.subject_key_identifier().unwrap())
+        );
+        assert!(crl.next_update().unwrap() > crl.this_update());
+
+        let revoked: Vec<RevokedCert> = crl.revoked_certificates().collect();
+        assert_eq!(revoked.len(), 1);
+        assert_eq!(revoked[0].serial_number().as_bytes_be(), &[0x10, 0x00]);
+        assert_eq!(revoked[0].reason(), Some(CRLReason::KeyCompromise));
+
+        assert!(crl.is_revoked(&parse_cert(crate::test_files::CRL_REVOKED_LEAF)));
+        assert!(!crl.is_revoked(&parse_cert(crate::test_files::CRL_VALID_LEAF)));
+        assert!(!crl.is_revoked(&parse_cert(crate::test_files::EC_LEAF)));
+    }
+
+    #[test]
+    fn crl_generation() {
+        let ca_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let revoked_leaf = parse_cert(crate::test_files::CRL_REVOKED_LEAF);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+
+        let crl = CrlBuilder::new()
+            .this_update(UTCDate::ymd(2026, 1, 1).unwrap())
+            .next_update(UTCDate::ymd(2026, 2, 1).unwrap())
+            .issuer_cert(&ca, &ca_key)
+            .crl_number(0x80)
+            .revoke_cert(&revoked_leaf, UTCDate::ymd(2025, 12, 24).unwrap(), None)
+            .revoke(
+                vec![0x42].into(),
+                UTCDate::ymd(2025, 12, 25).unwrap(),
+                Some(CRLReason::Superseded),
+            )
+            .build()
+            .expect("couldn't build crl");
+
+        let crl = Crl::from_pem(&crl.to_pem().unwrap()).expect("pem roundtrip");
+        crl.verify(&ca).expect("verify crl");
+
+        assert_eq!(crl.this_update(), UTCDate::ymd(2026, 1, 1).unwrap());
+        assert_eq!(crl.next_update(), UTCDate::ymd(2026, 2, 1));
+        assert_eq!(crl.crl_number().unwrap().as_signed_bytes_be(), &[0x00, 0x80]);
+
+        assert!(crl.is_revoked(&revoked_leaf));
+        assert!(!crl.is_revoked(&valid_leaf));
+        assert_eq!(crl.find_revoked(revoked_leaf.serial_number()).unwrap().reason(), None);
+        let other = crl.find_revoked(&vec![0x42].into()).unwrap();
+        assert_eq!(other.reason(), Some(CRLReason::Superseded));
+        assert_eq!(other.revocation_date(), UTCDate::ymd(2025, 12, 25).unwrap());
+
+        // signed by another key
+        let other_key = parse_key(crate::test_files::EC_P256_PK_2);
+        let forged = CrlBuilder::new()
+            .this_update(UTCDate::ymd(2026, 1, 1).unwrap())
+            .issuer_cert(&ca, &other_key)
+            .crl_number(1)
+            .build()
+            .unwrap();
+        assert_eq!(forged.revoked_certificates().count(), 0);
+        let err = forged.verify(&ca).err().unwrap();
+        assert_eq!(err.to_string(), "signature error: invalid signature");
+
+        // not the issuer
+        let err = crl.verify(&revoked_leaf).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "issuer name doesn't match (expected: CN=CRL Test CA, got: CN=leaf1)"
+        );
+
+        // issuer cert without the cRLSign key usage
+        let issuer = CertificateBuilder::new()
+            .valididy(UTCDate::ymd(2026, 1, 1).unwrap(), UTCDate::ymd(2027, 1, 1).unwrap())
+            .self_signed(ca.subject_name(), &ca_key)
+            .ca(true)
+            .key_usage({
+                let mut key_usage = crate::x509::extension::KeyUsage::new(7);
+                key_usage.set_key_cert_sign(true);
+                key_usage
+            })
+            .build()
+            .unwrap();
+        let crl = CrlBuilder::new()
+            .this_update(UTCDate::ymd(2026, 1, 1).unwrap())
+            .issuer_cert(&issuer, &ca_key)
+            .crl_number(2)
+            .build()
+            .unwrap();
+        let err = crl.verify(&issuer).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "issuer certificate 'CN=CRL Test CA' is not allowed to sign CRLs"
+        );
+    }
+
+    fn add_extension_and_resign(crl: &mut Crl, extension_der: &[u8], entry_extension: bool, key: &PrivateKey) {
+        let extension: Extension = picky_asn1_der::from_bytes(extension_der).expect("extension");
+        let tbs_cert_list = &mut crl.0.tbs_cert_list;
+        if entry_extension {
+            let revoked = &mut tbs_cert_list
+                .revoked_certificates
+                .as_mut()
+                .expect("revoked certificates")
+                .0[0];
+            revoked
+                .crl_entry_extensions
+                .get_or_insert_with(|| Extensions(Vec::new()))
+                .0
+                .push(extension);
+        } else {
+            ((tbs_cert_list.crl_extensions.as_mut().expect("crl extensions").0).0).push(extension);
+        }
+
+        let hash_type = SignatureHashType::from_algorithm_identifier(&crl.0.signature_algorithm).unwrap();
+        let tbs_der = picky_asn1_der::to_vec(&crl.0.tbs_cert_list).unwrap();
+        crl.0.signature_value = BitString::with_bytes(key.sign(&tbs_der, hash_type).unwrap()).into();
+        crl.1 = Some(tbs_der);
+    }
+
+    #[test]
+    fn unsupported_critical_extensions() {
+        let ca_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let revoked_leaf = parse_cert(crate::test_files::CRL_REVOKED_LEAF);
+
+        let crl = CrlBuilder::new()
+            .this_update(UTCDate::ymd(2026, 1, 1).unwrap())
+            .issuer_cert(&ca, &ca_key)
+            .crl_number(1)
+            .revoke_cert(&revoked_leaf, UTCDate::ymd(2025, 12, 24).unwrap(), None)
+            .build()
+            .unwrap();
+
+        // critical delta CRL indicator (BaseCRLNumber 1)
+        let mut delta_crl = crl.clone();
+        add_extension_and_resign(
+            &mut delta_crl,
+            &[
+                0x30, 0x0D, 0x06, 0x03, 0x55, 0x1D, 0x1B, 0x01, 0x01, 0xFF, 0x04, 0x03, 0x02, 0x01, 0x01,
+            ],
+            false,
+            &ca_key,
+        );
+        let err = delta_crl.verify(&ca).err().unwrap();
+        assert_eq!(err.to_string(), "unsupported critical extension: 2.5.29.27");
+
+        // the same extension marked as non-critical is ignored
+        let mut crl_with_extension = crl.clone();
+        add_extension_and_resign(
+            &mut crl_with_extension,
+            &[0x30, 0x0A, 0x06, 0x03, 0x55, 0x1D, 0x1B, 0x04, 0x03, 0x02, 0x01, 0x01],
+            false,
+            &ca_key,
+        );
+        crl_with_extension.verify(&ca).expect("verify crl");
+
+        // critical invalidity date entry extension
+        let mut extension = vec![
+            0x30, 0x1B, 0x06, 0x03, 0x55, 0x1D, 0x18, 0x01, 0x01, 0xFF, 0x04, 0x11, 0x18, 0x0F,
+        ];
+        extension.extend_from_slice(b"20251220000000Z");
+        let mut crl = crl;
+        add_extension_and_resign(&mut crl, &extension, true, &ca_key);
+        let err = crl.verify(&ca).err().unwrap();
+        assert_eq!(err.to_string(), "unsupported critical extension: 2.5.29.24");
+    }
+
+    #[test]
+    fn signature_over_non_canonical_der() {
+        let ca_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let crl = CrlBuilder::new()
+            .this_update(UTCDate::ymd(2026, 1, 1).unwrap())
+            .issuer_cert(&ca, &ca_key)
+            .crl_number(1)
+            .build()
+            .unwrap();
+
+        // same TBS cert list, with superfluous leading zeros in the length of its SEQUENCE
+        let tbs_der = picky_asn1_der::to_vec(&crl.0.tbs_cert_list).unwrap();
+        let header_len = if tbs_der[1] < 0x80 {
+            2
+        } else {
+            2 + usize::from(tbs_der[1] & 0x7F)
+        };
+        let content = &tbs_der[header_len..];
+        let mut non_canonical_tbs_der = vec![tbs_der[0], 0x84];
+        non_canonical_tbs_der.extend_from_slice(&(content.len() as u32).to_be_bytes());
+        non_canonical_tbs_der.extend_from_slice(content);
+
+        let hash_type = SignatureHashType::from_algorithm_identifier(&crl.0.signature_algorithm).unwrap();
+        let signature_value = BitString::with_bytes(ca_key.sign(&non_canonical_tbs_der, hash_type).unwrap()).into();
+        let der = picky_asn1_der::to_vec(&RawCertificateList {
+            tbs_cert_list: RawDerAsn1(non_canonical_tbs_der),
+            signature_algorithm: crl.0.signature_algorithm.clone(),
+            signature_value,
+        })
+        .unwrap();
+
+        let crl = Crl::from_der(&der).expect("crl");
+        crl.verify(&ca).expect("verify crl");
+
+        // the received encoding is kept when the CRL is serialized again
+        assert_eq!(crl.to_der().unwrap(), der);
+        Crl::from_der(&crl.to_der().unwrap())
+            .expect("crl")
+            .verify(&ca)
+            .expect("verify reserialized crl");
+
+        // a CRL without the received encoding is verified over its canonical encoding
+        let err = Crl::from(crl.0).verify(&ca).err().unwrap();
+        assert_eq!(err.to_string(), "signature error: invalid signature");
+    }
+
+    #[test]
+    fn missing_builder_argument() {
+        let ca_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let err = CrlBuilder::new()
+            .this_update(UTCDate::ymd(2026, 1, 1).unwrap())
+            .issuer(DirectoryName::new_common_name("CRL Test CA"), &ca_key)
+            .crl_number(1)
+            .build()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "missing required builder argument `authority_key_identifier`"
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
-    key::{PrivateKey, PublicKey},
+    algorithm_identifier::HashAlgorithm,
+    key::{KeyError, PublicKey},
     pem::Pem,
-    signature::{SignatureError, SignatureHashType},
+    signature::{SignatureError, SignatureHashType, Signer},
     x509::{
         name::DirectoryName,
         private::{certification_request::CertificationRequestInfo, CertificationRequest},
@@ -34,27 +35,41 @@ pub enum CsrError {
     /// invalid PEM label error
     #[snafu(display("invalid PEM label: {}", label))]
     InvalidPemLabel { label: String },
+
+    /// invalid public key
+    #[snafu(display("invalid public key: {}", source))]
+    InvalidPublicKey { source: KeyError },
 }
 
 const CSR_PEM_LABEL: &str = "CERTIFICATE REQUEST";
 
 /// Certificate Signing Request
-#[derive(Clone, Debug, PartialEq)]
-pub struct Csr(CertificationRequest);
+///
+/// The DER encoding the request was decoded from, if any, is kept for fingerprinting.
+#[derive(Clone, Debug)]
+pub struct Csr(CertificationRequest, Option<Vec<u8>>);
+
+impl PartialEq for Csr {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
 
 impl From<CertificationRequest> for Csr {
     fn from(certification_request: CertificationRequest) -> Self {
-        Self(certification_request)
+        Self(certification_request, None)
     }
 }
 
 impl Csr {
     pub fn from_der<T: ?Sized + AsRef<[u8]>>(der: &T) -> Result<Self, CsrError> {
-        Ok(Self(picky_asn1_der::from_bytes(der.as_ref()).context(
-            Asn1Deserialization {
+        let der = der.as_ref();
+        Ok(Self(
+            picky_asn1_der::from_bytes(der).context(Asn1Deserialization {
                 element: "certification request",
-            },
-        )?))
+            })?,
+            Some(der.to_vec()),
+        ))
     }
 
     pub fn from_pem(pem: &Pem) -> Result<Self, CsrError> {
@@ -78,16 +93,16 @@ impl Csr {
 
     pub fn generate(
         subject: DirectoryName,
-        private_key: &PrivateKey,
+        signer: &dyn Signer,
         signature_hash_type: SignatureHashType,
     ) -> Result<Self, CsrError> {
-        let info = CertificationRequestInfo::new(subject.into(), private_key.to_public_key().into());
+        let info = CertificationRequestInfo::new(subject.into(), signer.public_key().into());
         let info_der = picky_asn1_der::to_vec(&info).context(Asn1Serialization {
             element: "certification request info",
         })?;
-        let signature = BitString::with_bytes(signature_hash_type.sign(&info_der, private_key).context(Signature)?);
+        let signature = BitString::with_bytes(signer.sign(&info_der, signature_hash_type).context(Signature)?);
 
-        Ok(Self(CertificationRequest {
+        Ok(Self::from(CertificationRequest {
             certification_request_info: info,
             signature_algorithm: signature_hash_type.into(),
             signature: signature.into(),
@@ -102,6 +117,20 @@ impl Csr {
         (&self.0.certification_request_info.subject_public_key_info).into()
     }
 
+    /// Fingerprint of the certification request: hash of its DER encoding (as received when
+    /// decoded with `from_der`).
+    pub fn fingerprint(&self, hash_algorithm: HashAlgorithm) -> Result<Vec<u8>, CsrError> {
+        match &self.1 {
+            Some(der) => Ok(hash_algorithm.digest(der)),
+            None => Ok(hash_algorithm.digest(&self.to_der()?)),
+        }
+    }
+
+    /// Base64 encoded SHA-256 hash of the subject public key info (see `PublicKey::spki_pin`).
+    pub fn spki_pin(&self) -> Result<String, CsrError> {
+        self.public_key().spki_pin().context(InvalidPublicKey)
+    }
+
     pub fn into_subject_infos(self) -> (DirectoryName, PublicKey) {
         (
             self.0.certification_request_info.subject.into(),
@@ -1,16 +1,26 @@
 use core::slice::{Iter, IterMut};
+use oid::ObjectIdentifier;
 use picky_asn1::{
     bit_string::BitString,
-    wrapper::{Asn1SequenceOf, BitStringAsn1},
+    restricted_string::{BMPString, CharSetError, IA5String, VisibleString},
+    tag::{Tag, TagPeeker},
+    wrapper::{Asn1SequenceOf, BitStringAsn1, EnumeratedAsn1},
 };
 
 use crate::{
     oids,
-    x509::private::name::{GeneralName, GeneralNames},
+    x509::{
+        name::DirectoryName,
+        private::{
+            name::{GeneralName, GeneralNames},
+            AttributeTypeAndValue, Name,
+        },
+    },
 };
 use picky_asn1::wrapper::{
-    ApplicationTag1, ContextTag0, ContextTag2, Implicit, IntegerAsn1, ObjectIdentifierAsn1, OctetStringAsn1,
-    OctetStringAsn1Container,
+    ApplicationTag0, ApplicationTag1, BMPStringAsn1, ContextTag0, ContextTag1, ContextTag2, IA5StringAsn1, Implicit,
+    ImplicitContextTag0, ImplicitContextTag1, ImplicitContextTag2, IntegerAsn1, ObjectIdentifierAsn1, OctetStringAsn1,
+    OctetStringAsn1Container, RawDerAsn1, VisibleStringAsn1,
 };
 use serde::{de, ser, Deserialize, Serialize};
 use std::fmt;
@@ -168,6 +178,127 @@ impl Extension {
             extn_value: ExtensionValue::IssuerAltName(name.into()),
         }
     }
+
+    /// This extension MAY be either critical or non-critical.
+    ///
+    /// Default is non-critical.
+    pub(crate) fn new_certificate_policies<P: Into<CertificatePolicies>>(policies: P) -> Self {
+        Self {
+            extn_id: oids::certificate_policies().into(),
+            critical: false.into(),
+            extn_value: ExtensionValue::CertificatePolicies(OctetStringAsn1Container(policies.into())),
+        }
+    }
+
+    /// Conforming CAs SHOULD mark this extension as critical.
+    ///
+    /// Default is critical.
+    pub(crate) fn new_policy_mappings<M: Into<PolicyMappings>>(mappings: M) -> Self {
+        Self {
+            extn_id: oids::policy_mappings().into(),
+            critical: true.into(),
+            extn_value: ExtensionValue::PolicyMappings(mappings.into().into()),
+        }
+    }
+
+    /// Conforming CAs MUST mark this extension as critical.
+    ///
+    /// Default is critical.
+    pub(crate) fn new_policy_constraints(policy_constraints: PolicyConstraints) -> Self {
+        Self {
+            extn_id: oids::policy_constraints().into(),
+            critical: true.into(),
+            extn_value: ExtensionValue::PolicyConstraints(policy_constraints.into()),
+        }
+    }
+
+    /// Conforming CAs MUST mark this extension as critical.
+    ///
+    /// Default is critical.
+    pub(crate) fn new_inhibit_any_policy(skip_certs: u32) -> Self {
+        Self {
+            extn_id: oids::inhibit_any_policy().into(),
+            critical: true.into(),
+            extn_value: ExtensionValue::InhibitAnyPolicy(InhibitAnyPolicy::new(skip_certs).into()),
+        }
+    }
+
+    /// The extension SHOULD be non-critical.
+    ///
+    /// Default is non-critical.
+    pub(crate) fn new_crl_distribution_points<D: Into<CRLDistributionPoints>>(distribution_points: D) -> Self {
+        Self {
+            extn_id: oids::crl_distribution_points().into(),
+            critical: false.into(),
+            extn_value: ExtensionValue::CRLDistributionPoints(distribution_points.into().into()),
+        }
+    }
+
+    /// The extension MUST be marked as non-critical by conforming CAs.
+    ///
+    /// Default is non-critical.
+    pub(crate) fn new_freshest_crl<D: Into<CRLDistributionPoints>>(distribution_points: D) -> Self {
+        Self {
+            extn_id: oids::freshest_crl().into(),
+            critical: false.into(),
+            extn_value: ExtensionValue::FreshestCRL(distribution_points.into().into()),
+        }
+    }
+
+    /// Conforming CAs MUST mark this extension as non-critical.
+    ///
+    /// Default is non-critical.
+    pub(crate) fn new_authority_info_access<A: Into<AuthorityInfoAccess>>(authority_info_access: A) -> Self {
+        Self {
+            extn_id: oids::authority_info_access().into(),
+            critical: false.into(),
+            extn_value: ExtensionValue::AuthorityInfoAccess(authority_info_access.into().into()),
+        }
+    }
+
+    /// CRL extension. Conforming CRL issuers MUST mark this extension as non-critical.
+    ///
+    /// Default is non-critical.
+    pub(crate) fn new_crl_number<N: Into<CRLNumber>>(crl_number: N) -> Self {
+        Self {
+            extn_id: oids::crl_number().into(),
+            critical: false.into(),
+            extn_value: ExtensionValue::CRLNumber(crl_number.into().into()),
+        }
+    }
+
+    /// CRL entry extension. This extension MUST be non-critical.
+    ///
+    /// Default is non-critical.
+    pub(crate) fn new_reason_code(reason: CRLReason) -> Self {
+        Self {
+            extn_id: oids::reason_code().into(),
+            critical: false.into(),
+            extn_value: ExtensionValue::ReasonCode(reason),
+        }
+    }
+
+    /// OCSP request and response extension (https://tools.ietf.org/html/rfc8954).
+    ///
+    /// Default is non-critical.
+    pub(crate) fn new_ocsp_nonce<V: Into<Vec<u8>>>(nonce: V) -> Self {
+        Self {
+            extn_id: oids::ocsp_nonce().into(),
+            critical: false.into(),
+            extn_value: ExtensionValue::OcspNonce(OctetStringAsn1(nonce.into()).into()),
+        }
+    }
+
+    /// Conforming CAs MUST mark this extension as critical
+    ///
+    /// Default is critical.
+    pub(crate) fn new_name_constraints(name_constraints: NameConstraints) -> Self {
+        Self {
+            extn_id: oids::name_constraints().into(),
+            critical: true.into(),
+            extn_value: ExtensionValue::NameConstraints(name_constraints.into()),
+        }
+    }
 }
 
 impl ser::Serialize for Extension {
@@ -231,6 +362,74 @@ impl<'de> de::Deserialize<'de> for Extension {
                     oids::EXTENDED_KEY_USAGE => {
                         ExtensionValue::ExtendedKeyUsage(seq_next_element!(seq, Extension, "ExtendedKeyUsage"))
                     }
+                    oids::CERTIFICATE_POLICIES => {
+                        let encoded: OctetStringAsn1 = seq_next_element!(seq, Extension, "CertificatePolicies");
+                        // policies that wouldn't be encoded back identically are kept as is
+                        match CertificatePolicies::from_der(&encoded.0) {
+                            Some(policies) => ExtensionValue::CertificatePolicies(policies.into()),
+                            None => ExtensionValue::Generic(encoded),
+                        }
+                    }
+                    oids::POLICY_MAPPINGS => {
+                        ExtensionValue::PolicyMappings(seq_next_element!(seq, Extension, "PolicyMappings"))
+                    }
+                    oids::POLICY_CONSTRAINTS => {
+                        ExtensionValue::PolicyConstraints(seq_next_element!(seq, Extension, "PolicyConstraints"))
+                    }
+                    oids::INHIBIT_ANY_POLICY => {
+                        ExtensionValue::InhibitAnyPolicy(seq_next_element!(seq, Extension, "InhibitAnyPolicy"))
+                    }
+                    oids::CRL_DISTRIBUTION_POINTS => {
+                        let encoded: OctetStringAsn1 = seq_next_element!(seq, Extension, "CRLDistributionPoints");
+                        match CRLDistributionPoints::from_der(&encoded.0) {
+                            Some(distribution_points) => {
+                                ExtensionValue::CRLDistributionPoints(distribution_points.into())
+                            }
+                            None => ExtensionValue::Generic(encoded),
+                        }
+                    }
+                    oids::FRESHEST_CRL => {
+                        let encoded: OctetStringAsn1 = seq_next_element!(seq, Extension, "FreshestCRL");
+                        match CRLDistributionPoints::from_der(&encoded.0) {
+                            Some(distribution_points) => ExtensionValue::FreshestCRL(distribution_points.into()),
+                            None => ExtensionValue::Generic(encoded),
+                        }
+                    }
+                    oids::AUTHORITY_INFO_ACCESS => {
+                        let encoded: OctetStringAsn1 = seq_next_element!(seq, Extension, "AuthorityInfoAccess");
+                        // unsupported access locations (e.g. otherName) are kept as a generic extension
+                        match picky_asn1_der::from_bytes::<AuthorityInfoAccess>(&encoded.0) {
+                            Ok(aia) => ExtensionValue::AuthorityInfoAccess(aia.into()),
+                            Err(_) => ExtensionValue::Generic(encoded),
+                        }
+                    }
+                    oids::CRL_NUMBER => ExtensionValue::CRLNumber(seq_next_element!(seq, Extension, "CRLNumber")),
+                    oids::REASON_CODE => {
+                        let encoded: OctetStringAsn1 = seq_next_element!(seq, Extension, "ReasonCode");
+                        match picky_asn1_der::from_bytes::<EnumeratedAsn1>(&encoded.0)
+                            .ok()
+                            .and_then(|reason| CRLReason::from_enumerated(&reason))
+                        {
+                            Some(reason) => ExtensionValue::ReasonCode(reason),
+                            None => ExtensionValue::Generic(encoded),
+                        }
+                    }
+                    oids::OCSP_NONCE => {
+                        // some implementations don't wrap the nonce in an OCTET STRING
+                        let encoded: OctetStringAsn1 = seq_next_element!(seq, Extension, "OcspNonce");
+                        match picky_asn1_der::from_bytes::<OctetStringAsn1>(&encoded.0) {
+                            Ok(nonce) => ExtensionValue::OcspNonce(nonce.into()),
+                            Err(_) => ExtensionValue::Generic(encoded),
+                        }
+                    }
+                    oids::NAME_CONSTRAINTS => {
+                        let encoded: OctetStringAsn1 = seq_next_element!(seq, Extension, "NameConstraints");
+                        // unsupported subtrees are kept as a generic extension (rejected by chain validation when critical)
+                        match picky_asn1_der::from_bytes::<NameConstraints>(&encoded.0) {
+                            Ok(name_constraints) => ExtensionValue::NameConstraints(name_constraints.into()),
+                            Err(_) => ExtensionValue::Generic(encoded),
+                        }
+                    }
                     _ => ExtensionValue::Generic(seq_next_element!(seq, Extension, "Generic")),
                 };
 
@@ -252,10 +451,21 @@ pub enum ExtensionView<'a> {
     AuthorityKeyIdentifier(&'a AuthorityKeyIdentifier),
     SubjectKeyIdentifier(&'a SubjectKeyIdentifier),
     KeyUsage(&'a KeyUsage),
+    CertificatePolicies(&'a CertificatePolicies),
+    PolicyMappings(&'a PolicyMappings),
     SubjectAltName(super::name::GeneralNames),
     IssuerAltName(super::name::GeneralNames),
     BasicConstraints(&'a BasicConstraints),
     ExtendedKeyUsage(&'a ExtendedKeyUsage),
+    NameConstraints(&'a NameConstraints),
+    PolicyConstraints(&'a PolicyConstraints),
+    CRLDistributionPoints(&'a CRLDistributionPoints),
+    InhibitAnyPolicy(&'a InhibitAnyPolicy),
+    FreshestCRL(&'a CRLDistributionPoints),
+    AuthorityInfoAccess(&'a AuthorityInfoAccess),
+    CRLNumber(&'a CRLNumber),
+    ReasonCode(CRLReason),
+    OcspNonce(&'a OctetStringAsn1),
     Generic(&'a OctetStringAsn1),
 }
 
@@ -265,10 +475,21 @@ impl<'a> From<&'a ExtensionValue> for ExtensionView<'a> {
             ExtensionValue::AuthorityKeyIdentifier(OctetStringAsn1Container(val)) => Self::AuthorityKeyIdentifier(val),
             ExtensionValue::SubjectKeyIdentifier(OctetStringAsn1Container(val)) => Self::SubjectKeyIdentifier(val),
             ExtensionValue::KeyUsage(OctetStringAsn1Container(val)) => Self::KeyUsage(val),
+            ExtensionValue::CertificatePolicies(OctetStringAsn1Container(val)) => Self::CertificatePolicies(val),
+            ExtensionValue::PolicyMappings(OctetStringAsn1Container(val)) => Self::PolicyMappings(val),
             ExtensionValue::SubjectAltName(OctetStringAsn1Container(val)) => Self::SubjectAltName(val.clone().into()),
             ExtensionValue::IssuerAltName(OctetStringAsn1Container(val)) => Self::IssuerAltName(val.clone().into()),
             ExtensionValue::BasicConstraints(OctetStringAsn1Container(val)) => Self::BasicConstraints(val),
             ExtensionValue::ExtendedKeyUsage(OctetStringAsn1Container(val)) => Self::ExtendedKeyUsage(val),
+            ExtensionValue::NameConstraints(OctetStringAsn1Container(val)) => Self::NameConstraints(val),
+            ExtensionValue::PolicyConstraints(OctetStringAsn1Container(val)) => Self::PolicyConstraints(val),
+            ExtensionValue::CRLDistributionPoints(OctetStringAsn1Container(val)) => Self::CRLDistributionPoints(val),
+            ExtensionValue::InhibitAnyPolicy(OctetStringAsn1Container(val)) => Self::InhibitAnyPolicy(val),
+            ExtensionValue::FreshestCRL(OctetStringAsn1Container(val)) => Self::FreshestCRL(val),
+            ExtensionValue::AuthorityInfoAccess(OctetStringAsn1Container(val)) => Self::AuthorityInfoAccess(val),
+            ExtensionValue::CRLNumber(OctetStringAsn1Container(val)) => Self::CRLNumber(val),
+            ExtensionValue::ReasonCode(val) => Self::ReasonCode(*val),
+            ExtensionValue::OcspNonce(OctetStringAsn1Container(val)) => Self::OcspNonce(val),
             ExtensionValue::Generic(val) => Self::Generic(val),
         }
     }
@@ -279,18 +500,22 @@ enum ExtensionValue {
     AuthorityKeyIdentifier(OctetStringAsn1Container<AuthorityKeyIdentifier>),
     SubjectKeyIdentifier(OctetStringAsn1Container<SubjectKeyIdentifier>),
     KeyUsage(OctetStringAsn1Container<KeyUsage>),
-    //CertificatePolicies(OctetStringAsn1Container<Asn1SequenceOf<PolicyInformation>>),
-    //PolicyMappings(OctetStringAsn1Container<Asn1SequenceOfPolicyMapping>>),
+    CertificatePolicies(OctetStringAsn1Container<CertificatePolicies>),
+    PolicyMappings(OctetStringAsn1Container<PolicyMappings>),
     SubjectAltName(OctetStringAsn1Container<SubjectAltName>),
     IssuerAltName(OctetStringAsn1Container<IssuerAltName>),
     //SubjectDirectoryAttributes(OctetStringAsn1Container<Asn1SequenceOf<Attribute>>),
     BasicConstraints(OctetStringAsn1Container<BasicConstraints>),
-    //NameConstraints(…),
-    //PolicyConstraints(…),
+    NameConstraints(OctetStringAsn1Container<NameConstraints>),
+    PolicyConstraints(OctetStringAsn1Container<PolicyConstraints>),
     ExtendedKeyUsage(OctetStringAsn1Container<ExtendedKeyUsage>),
-    //CRLDistributionPoints(…),
-    //InhibitAnyPolicy(…),
-    //FreshestCRL(…),
+    CRLDistributionPoints(OctetStringAsn1Container<CRLDistributionPoints>),
+    InhibitAnyPolicy(OctetStringAsn1Container<InhibitAnyPolicy>),
+    FreshestCRL(OctetStringAsn1Container<CRLDistributionPoints>),
+    AuthorityInfoAccess(OctetStringAsn1Container<AuthorityInfoAccess>),
+    CRLNumber(OctetStringAsn1Container<CRLNumber>),
+    ReasonCode(CRLReason),
+    OcspNonce(OctetStringAsn1Container<OctetStringAsn1>),
     Generic(OctetStringAsn1),
 }
 
@@ -303,10 +528,23 @@ impl ser::Serialize for ExtensionValue {
             ExtensionValue::AuthorityKeyIdentifier(aki) => aki.serialize(serializer),
             ExtensionValue::SubjectKeyIdentifier(ski) => ski.serialize(serializer),
             ExtensionValue::KeyUsage(key_usage) => key_usage.serialize(serializer),
+            ExtensionValue::CertificatePolicies(policies) => policies.serialize(serializer),
+            ExtensionValue::PolicyMappings(mappings) => mappings.serialize(serializer),
             ExtensionValue::SubjectAltName(san) => san.serialize(serializer),
             ExtensionValue::IssuerAltName(ian) => ian.serialize(serializer),
             ExtensionValue::BasicConstraints(basic_constraints) => basic_constraints.serialize(serializer),
             ExtensionValue::ExtendedKeyUsage(eku) => eku.serialize(serializer),
+            ExtensionValue::NameConstraints(name_constraints) => name_constraints.serialize(serializer),
+            ExtensionValue::PolicyConstraints(policy_constraints) => policy_constraints.serialize(serializer),
+            ExtensionValue::CRLDistributionPoints(distribution_points)
+            | ExtensionValue::FreshestCRL(distribution_points) => distribution_points.serialize(serializer),
+            ExtensionValue::InhibitAnyPolicy(inhibit_any_policy) => inhibit_any_policy.serialize(serializer),
+            ExtensionValue::AuthorityInfoAccess(aia) => aia.serialize(serializer),
+            ExtensionValue::CRLNumber(crl_number) => crl_number.serialize(serializer),
+            ExtensionValue::ReasonCode(reason) => {
+                OctetStringAsn1Container(reason.to_enumerated()).serialize(serializer)
+            }
+            ExtensionValue::OcspNonce(nonce) => nonce.serialize(serializer),
             ExtensionValue::Generic(octet_string) => octet_string.serialize(serializer),
         }
     }
@@ -419,30 +657,135 @@ impl KeyUsage {
     }
 }
 
-/// https://tools.ietf.org/html/rfc5280#section-4.2.1.6
-type SubjectAltName = GeneralNames;
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.4
+///
+/// Policy qualifiers other than CPS pointers and user notices are kept as raw DER.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct CertificatePolicies(Vec<PolicyInformation>);
+
+// CertificatePolicies ::= SEQUENCE SIZE (1..MAX) OF PolicyInformation
+//
+// PolicyInformation ::= SEQUENCE {
+//      policyIdentifier   CertPolicyId,
+//      policyQualifiers   SEQUENCE SIZE (1..MAX) OF PolicyQualifierInfo OPTIONAL }
+//
+// PolicyQualifierInfo ::= SEQUENCE {
+//      policyQualifierId  PolicyQualifierId,
+//      qualifier          ANY DEFINED BY policyQualifierId }
+//
+// UserNotice ::= SEQUENCE {
+//      noticeRef        NoticeReference OPTIONAL,
+//      explicitText     DisplayText OPTIONAL }
+//
+// NoticeReference ::= SEQUENCE {
+//      organization     DisplayText,
+//      noticeNumbers    SEQUENCE OF INTEGER }
+
+impl CertificatePolicies {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-/// https://tools.ietf.org/html/rfc5280#section-4.2.1.7
-type IssuerAltName = GeneralNames;
+    pub fn add_policy(&mut self, policy: PolicyInformation) {
+        self.0.push(policy);
+    }
 
-/// https://tools.ietf.org/html/rfc5280#section-4.2.1.9
-#[derive(Serialize, Debug, PartialEq, Clone)]
-pub struct BasicConstraints {
-    ca: Implicit<Option<bool>>, // default is false
-    path_len_constraint: Implicit<Option<u8>>,
+    pub fn iter(&self) -> Iter<'_, PolicyInformation> {
+        self.0.iter()
+    }
+
+    pub fn contains<C: PartialEq<ObjectIdentifier>>(&self, policy_identifier: C) -> bool {
+        self.0
+            .iter()
+            .any(|policy| policy_identifier.eq(&policy.policy_identifier))
+    }
+
+    /// Policies that wouldn't be encoded back identically are rejected.
+    fn from_der(der: &[u8]) -> Option<Self> {
+        let policies: Self = picky_asn1_der::from_bytes(der).ok()?;
+        if picky_asn1_der::to_vec(&policies).ok()?.as_slice() == der {
+            Some(policies)
+        } else {
+            None
+        }
+    }
 }
 
-impl BasicConstraints {
-    pub fn ca(&self) -> Option<bool> {
-        self.ca.0
+impl ser::Serialize for CertificatePolicies {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for policy in &self.0 {
+            seq.serialize_element(policy)?;
+        }
+        seq.end()
     }
+}
 
-    pub fn pathlen(&self) -> Option<u8> {
-        self.path_len_constraint.0
+impl<'de> de::Deserialize<'de> for CertificatePolicies {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let policies = Asn1SequenceOf::<PolicyInformation>::deserialize(deserializer)?;
+        Ok(Self(policies.0))
     }
 }
 
-impl<'de> de::Deserialize<'de> for BasicConstraints {
+impl From<Vec<PolicyInformation>> for CertificatePolicies {
+    fn from(policies: Vec<PolicyInformation>) -> Self {
+        Self(policies)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PolicyInformation {
+    policy_identifier: ObjectIdentifier,
+    policy_qualifiers: Vec<PolicyQualifier>,
+}
+
+impl PolicyInformation {
+    pub fn new<OID: Into<ObjectIdentifier>>(policy_identifier: OID) -> Self {
+        Self {
+            policy_identifier: policy_identifier.into(),
+            policy_qualifiers: Vec::new(),
+        }
+    }
+
+    pub fn add_qualifier(&mut self, qualifier: PolicyQualifier) {
+        self.policy_qualifiers.push(qualifier);
+    }
+
+    pub fn policy_identifier(&self) -> &ObjectIdentifier {
+        &self.policy_identifier
+    }
+
+    pub fn policy_qualifiers(&self) -> &[PolicyQualifier] {
+        &self.policy_qualifiers
+    }
+}
+
+impl ser::Serialize for PolicyInformation {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element(&ObjectIdentifierAsn1::from(self.policy_identifier.clone()))?;
+        if !self.policy_qualifiers.is_empty() {
+            seq.serialize_element(&Asn1SequenceOf(self.policy_qualifiers.iter().collect::<Vec<_>>()))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for PolicyInformation {
     fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
     where
         D: de::Deserializer<'de>,
@@ -450,19 +793,32 @@ impl<'de> de::Deserialize<'de> for BasicConstraints {
         struct Visitor;
 
         impl<'de> de::Visitor<'de> for Visitor {
-            type Value = BasicConstraints;
+            type Value = PolicyInformation;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("a valid DER-encoded basic constraints extension")
+                formatter.write_str("a valid DER-encoded policy information")
             }
 
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
             where
                 A: de::SeqAccess<'de>,
             {
-                Ok(BasicConstraints {
-                    ca: Implicit(seq.next_element().unwrap_or(Some(None)).unwrap_or(None)),
-                    path_len_constraint: Implicit(seq.next_element().unwrap_or(Some(None)).unwrap_or(None)),
+                let policy_identifier: ObjectIdentifierAsn1 =
+                    seq_next_element!(seq, PolicyInformation, "policyIdentifier");
+                let policy_qualifiers = seq
+                    .next_element::<Asn1SequenceOf<PolicyQualifier>>()?
+                    .map(|policy_qualifiers| policy_qualifiers.0)
+                    .unwrap_or_default();
+                if seq.next_element::<TagPeeker>()?.is_some() {
+                    return Err(serde_invalid_value!(
+                        PolicyInformation,
+                        "unexpected element",
+                        "policyIdentifier and policyQualifiers"
+                    ));
+                }
+                Ok(PolicyInformation {
+                    policy_identifier: policy_identifier.0,
+                    policy_qualifiers,
                 })
             }
         }
@@ -471,67 +827,1401 @@ impl<'de> de::Deserialize<'de> for BasicConstraints {
     }
 }
 
-/// https://tools.ietf.org/html/rfc5280#section-4.2.1.12
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
-pub struct ExtendedKeyUsage(Asn1SequenceOf<ObjectIdentifierAsn1>);
+#[derive(Debug, PartialEq, Clone)]
+pub enum PolicyQualifier {
+    CpsUri(IA5String),
+    UserNotice(UserNotice),
+    Other {
+        id: ObjectIdentifier,
+        /// DER encoded qualifier
+        qualifier: Vec<u8>,
+    },
+}
 
-impl<OID: Into<ObjectIdentifierAsn1>> From<Vec<OID>> for ExtendedKeyUsage {
-    fn from(purpose_oids: Vec<OID>) -> Self {
-        ExtendedKeyUsage::new(purpose_oids)
+impl PolicyQualifier {
+    pub fn new_cps_uri<S: Into<String>>(uri: S) -> Result<Self, CharSetError> {
+        Ok(Self::CpsUri(IA5String::from_string(uri.into())?))
+    }
+
+    pub fn new_user_notice(notice_ref: Option<NoticeReference>, explicit_text: Option<DisplayText>) -> Self {
+        Self::UserNotice(UserNotice {
+            notice_ref,
+            explicit_text,
+        })
     }
 }
 
-impl ExtendedKeyUsage {
-    pub fn new<OID: Into<ObjectIdentifierAsn1>>(purpose_oids: Vec<OID>) -> Self {
-        Self(
-            purpose_oids
-                .into_iter()
-                .map(|oid| oid.into())
-                .collect::<Vec<_>>()
-                .into(),
-        )
+impl ser::Serialize for PolicyQualifier {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(2))?;
+        match self {
+            PolicyQualifier::CpsUri(uri) => {
+                seq.serialize_element(&ObjectIdentifierAsn1::from(oids::qt_cps()))?;
+                seq.serialize_element(&IA5StringAsn1::from(uri.clone()))?;
+            }
+            PolicyQualifier::UserNotice(user_notice) => {
+                seq.serialize_element(&ObjectIdentifierAsn1::from(oids::qt_unotice()))?;
+                seq.serialize_element(user_notice)?;
+            }
+            PolicyQualifier::Other { id, qualifier } => {
+                seq.serialize_element(&ObjectIdentifierAsn1::from(id.clone()))?;
+                seq.serialize_element(&RawDerAsn1(qualifier.clone()))?;
+            }
+        }
+        seq.end()
     }
+}
 
-    pub fn iter(&self) -> Iter<ObjectIdentifierAsn1> {
-        (self.0).0.iter()
+impl<'de> de::Deserialize<'de> for PolicyQualifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = PolicyQualifier;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded policy qualifier")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let id: ObjectIdentifierAsn1 = seq_next_element!(seq, PolicyQualifier, "policyQualifierId");
+                let qualifier = match Into::<String>::into(&id.0).as_str() {
+                    oids::QT_CPS => {
+                        let uri: IA5StringAsn1 = seq_next_element!(seq, PolicyQualifier, "cPSuri");
+                        PolicyQualifier::CpsUri(uri.0)
+                    }
+                    oids::QT_UNOTICE => {
+                        PolicyQualifier::UserNotice(seq_next_element!(seq, PolicyQualifier, "userNotice"))
+                    }
+                    _ => {
+                        let qualifier: RawDerAsn1 = seq_next_element!(seq, PolicyQualifier, "qualifier");
+                        PolicyQualifier::Other {
+                            id: id.0,
+                            qualifier: qualifier.0,
+                        }
+                    }
+                };
+                if seq.next_element::<TagPeeker>()?.is_some() {
+                    return Err(serde_invalid_value!(
+                        PolicyQualifier,
+                        "unexpected element",
+                        "policyQualifierId and qualifier"
+                    ));
+                }
+                Ok(qualifier)
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
     }
+}
 
-    pub fn iter_mut(&mut self) -> IterMut<ObjectIdentifierAsn1> {
-        (self.0).0.iter_mut()
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct UserNotice {
+    notice_ref: Option<NoticeReference>,
+    explicit_text: Option<DisplayText>,
+}
+
+impl UserNotice {
+    pub fn notice_ref(&self) -> Option<&NoticeReference> {
+        self.notice_ref.as_ref()
     }
 
-    pub fn contains<C: PartialEq<oid::ObjectIdentifier>>(&self, item: C) -> bool {
-        (self.0).0.iter().any(|id| item.eq(&id.0))
+    pub fn explicit_text(&self) -> Option<&DisplayText> {
+        self.explicit_text.as_ref()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{pem::Pem, x509::private::name::GeneralName};
-    use picky_asn1::restricted_string::IA5String;
+impl ser::Serialize for UserNotice {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
 
-    #[test]
-    fn key_usage() {
-        let encoded: [u8; 4] = [0x03, 0x02, 0x01, 0xA0];
-        let mut key_usage = KeyUsage::new(7);
-        key_usage.set_digital_signature(true);
-        key_usage.set_key_encipherment(true);
-        assert_eq!(key_usage.as_bytes(), &[0xA0]);
-        check_serde!(key_usage: KeyUsage in encoded);
+        let mut seq = serializer.serialize_seq(None)?;
+        if let Some(notice_ref) = &self.notice_ref {
+            seq.serialize_element(notice_ref)?;
+        }
+        if let Some(explicit_text) = &self.explicit_text {
+            seq.serialize_element(explicit_text)?;
+        }
+        seq.end()
     }
+}
 
-    const CSR_PEM: &str = "-----BEGIN CERTIFICATE REQUEST-----\n\
-                           MIIDIjCCAgoCAQAwIDELMAkGA1UEBhMCRlIxETAPBgNVBAMMCERyYXBlYXUhMIIB\n\
-                           IjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEA5GqDEM7AfctJizsFEqtAvXd5\n\
-                           Fl1GtyXDAnx68MUTuSL22t8aBZoCCi3/9AlS75uUqKggHnRuY2MRYPQaUzpE1F1a\n\
-                           aZJNr6tXQy39FtdXrDq2zfwZdDmLW6sPmhvJrBO4yWjuG3wh1paPHy+rBHOjYt+9\n\
-                           Pbl/FmDDjIzF8B2LZDuLdnS94Fs/JhogJL/XF4b6RLW60gEnYFjL+ebYdV/f3JYi\n\
-                           ccQxY4imvbB2URlIO3t+aG9WMmhHZbbOi/HBdFG1fB7Hsa9Ek2FXshULzEDCJcMz\n\
-                           n8HD96XbVBmlaz9nYIcZ83eCOhra67FfFy4pIE1M9saxYJg/OJrMHG12r89yUQID\n\
-                           AQABoIG8MIG5BgkqhkiG9w0BCQ4xgaswgagwCQYDVR0TBAIwADALBgNVHQ8EBAMC\n\
-                           BeAwJwYDVR0lBCAwHgYIKwYBBQUHAwIGCCsGAQUFBwMBBggrBgEFBQcDAzBlBgNV\n\
-                           HREEXjBcghFkZXZlbC5leGFtcGxlLmNvbYIQaXB2Ni5leGFtcGxlLmNvbYIQaXB2\n\
+impl<'de> de::Deserialize<'de> for UserNotice {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = UserNotice;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded user notice")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                // all fields are optional
+                let mut user_notice = UserNotice::default();
+                if let Some(TagPeeker {
+                    next_tag: Tag::SEQUENCE,
+                }) = seq.next_element()?
+                {
+                    user_notice.notice_ref = Some(seq_next_element!(seq, UserNotice, "noticeRef"));
+                }
+                if seq.next_element::<TagPeeker>()?.is_some() {
+                    user_notice.explicit_text = Some(seq_next_element!(seq, UserNotice, "explicitText"));
+                }
+                if seq.next_element::<TagPeeker>()?.is_some() {
+                    return Err(serde_invalid_value!(
+                        UserNotice,
+                        "unexpected element",
+                        "noticeRef or explicitText"
+                    ));
+                }
+                Ok(user_notice)
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NoticeReference {
+    organization: DisplayText,
+    notice_numbers: Vec<u32>,
+}
+
+impl NoticeReference {
+    pub fn new<O: Into<DisplayText>>(organization: O, notice_numbers: Vec<u32>) -> Self {
+        Self {
+            organization: organization.into(),
+            notice_numbers,
+        }
+    }
+
+    pub fn organization(&self) -> &DisplayText {
+        &self.organization
+    }
+
+    pub fn notice_numbers(&self) -> &[u32] {
+        &self.notice_numbers
+    }
+}
+
+impl ser::Serialize for NoticeReference {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let notice_numbers = self
+            .notice_numbers
+            .iter()
+            .map(|number| IntegerAsn1(uint_to_integer_bytes(u64::from(*number))))
+            .collect::<Vec<_>>();
+
+        let mut seq = serializer.serialize_seq(Some(2))?;
+        seq.serialize_element(&self.organization)?;
+        seq.serialize_element(&Asn1SequenceOf(notice_numbers))?;
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for NoticeReference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = NoticeReference;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded notice reference")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let organization: DisplayText = seq_next_element!(seq, NoticeReference, "organization");
+                let notice_numbers: Asn1SequenceOf<IntegerAsn1> =
+                    seq_next_element!(seq, NoticeReference, "noticeNumbers");
+                let notice_numbers = notice_numbers
+                    .0
+                    .iter()
+                    .map(|number| integer_bytes_to_u32(&number.0))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| {
+                        serde_invalid_value!(
+                            NoticeReference,
+                            "notice number out of range",
+                            "notice numbers between 0 and 2^32 - 1"
+                        )
+                    })?;
+                Ok(NoticeReference {
+                    organization,
+                    notice_numbers,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+/// Conforming CAs SHOULD use the UTF8String encoding for explicitText.
+#[derive(Debug, PartialEq, Clone)]
+pub enum DisplayText {
+    IA5String(IA5String),
+    VisibleString(VisibleString),
+    BMPString(BMPString),
+    UTF8String(String),
+}
+
+impl DisplayText {
+    pub fn new_visible_string<S: Into<String>>(text: S) -> Result<Self, CharSetError> {
+        Ok(Self::VisibleString(VisibleString::from_string(text.into())?))
+    }
+
+    /// Characters outside of the Basic Multilingual Plane are rejected.
+    pub fn new_bmp_string(text: &str) -> Result<Self, CharSetError> {
+        let ucs2: Vec<u8> = text.encode_utf16().flat_map(|c| c.to_be_bytes().to_vec()).collect();
+        Ok(Self::BMPString(BMPString::new(ucs2)?))
+    }
+}
+
+impl From<&str> for DisplayText {
+    fn from(text: &str) -> Self {
+        Self::UTF8String(text.to_owned())
+    }
+}
+
+impl From<String> for DisplayText {
+    fn from(text: String) -> Self {
+        Self::UTF8String(text)
+    }
+}
+
+impl fmt::Display for DisplayText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisplayText::IA5String(text) => text.fmt(f),
+            DisplayText::VisibleString(text) => text.fmt(f),
+            DisplayText::BMPString(text) => {
+                let ucs2: Vec<u16> = text
+                    .as_bytes()
+                    .chunks(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16_lossy(&ucs2).fmt(f)
+            }
+            DisplayText::UTF8String(text) => text.fmt(f),
+        }
+    }
+}
+
+impl ser::Serialize for DisplayText {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            DisplayText::IA5String(text) => IA5StringAsn1::from(text.clone()).serialize(serializer),
+            DisplayText::VisibleString(text) => VisibleStringAsn1::from(text.clone()).serialize(serializer),
+            DisplayText::BMPString(text) => BMPStringAsn1::from(text.clone()).serialize(serializer),
+            DisplayText::UTF8String(text) => text.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for DisplayText {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = DisplayText;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded DisplayText")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let tag_peeker: TagPeeker = seq_next_element!(seq, DisplayText, "choice tag");
+                match tag_peeker.next_tag {
+                    Tag::IA5_STRING => {
+                        let text: IA5StringAsn1 = seq_next_element!(seq, DisplayText, "IA5String");
+                        Ok(DisplayText::IA5String(text.0))
+                    }
+                    Tag::VISIBLE_STRING => {
+                        let text: VisibleStringAsn1 = seq_next_element!(seq, DisplayText, "VisibleString");
+                        Ok(DisplayText::VisibleString(text.0))
+                    }
+                    Tag::BMP_STRING => {
+                        let text: BMPStringAsn1 = seq_next_element!(seq, DisplayText, "BMPString");
+                        Ok(DisplayText::BMPString(text.0))
+                    }
+                    Tag::UTF8_STRING => Ok(DisplayText::UTF8String(seq_next_element!(
+                        seq,
+                        DisplayText,
+                        "UTF8String"
+                    ))),
+                    _ => Err(serde_invalid_value!(
+                        DisplayText,
+                        "unknown string type",
+                        "IA5String, VisibleString, BMPString or UTF8String"
+                    )),
+                }
+            }
+        }
+
+        deserializer.deserialize_enum(
+            "DisplayText",
+            &["IA5String", "VisibleString", "BMPString", "UTF8String"],
+            Visitor,
+        )
+    }
+}
+
+/// Minimal two's complement encoding of an unsigned value.
+pub(crate) fn uint_to_integer_bytes(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let first_non_zero = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len() - 1);
+    let mut integer = bytes[first_non_zero..].to_vec();
+    if integer[0] & 0x80 != 0 {
+        integer.insert(0, 0);
+    }
+    integer
+}
+
+/// Returns `None` for negative values or values that don't fit in a `u32`.
+/// SkipCerts values are range checked when decoding and built from `u32`s otherwise: the fallback
+/// is never used but stays on the restrictive side.
+fn skip_certs_value(skip_certs: &IntegerAsn1) -> u32 {
+    integer_bytes_to_u32(&skip_certs.0).unwrap_or(0)
+}
+
+fn integer_bytes_to_u32(integer: &[u8]) -> Option<u32> {
+    match integer {
+        [] => None,
+        [first, ..] if first & 0x80 != 0 => None,
+        _ => {
+            let first_non_zero = integer.iter().position(|byte| *byte != 0).unwrap_or(integer.len() - 1);
+            let magnitude = &integer[first_non_zero..];
+            if magnitude.len() > 4 {
+                None
+            } else {
+                Some(magnitude.iter().fold(0, |value, byte| (value << 8) | u32::from(*byte)))
+            }
+        }
+    }
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.5
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct PolicyMappings(Asn1SequenceOf<PolicyMapping>);
+
+impl From<Vec<PolicyMapping>> for PolicyMappings {
+    fn from(mappings: Vec<PolicyMapping>) -> Self {
+        PolicyMappings::new(mappings)
+    }
+}
+
+impl PolicyMappings {
+    pub fn new(mappings: Vec<PolicyMapping>) -> Self {
+        Self(mappings.into())
+    }
+
+    pub fn iter(&self) -> Iter<'_, PolicyMapping> {
+        (self.0).0.iter()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct PolicyMapping {
+    issuer_domain_policy: ObjectIdentifierAsn1,
+    subject_domain_policy: ObjectIdentifierAsn1,
+}
+
+impl PolicyMapping {
+    pub fn new<I, S>(issuer_domain_policy: I, subject_domain_policy: S) -> Self
+    where
+        I: Into<ObjectIdentifierAsn1>,
+        S: Into<ObjectIdentifierAsn1>,
+    {
+        Self {
+            issuer_domain_policy: issuer_domain_policy.into(),
+            subject_domain_policy: subject_domain_policy.into(),
+        }
+    }
+
+    pub fn issuer_domain_policy(&self) -> &ObjectIdentifier {
+        &self.issuer_domain_policy.0
+    }
+
+    pub fn subject_domain_policy(&self) -> &ObjectIdentifier {
+        &self.subject_domain_policy.0
+    }
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.6
+type SubjectAltName = GeneralNames;
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.7
+type IssuerAltName = GeneralNames;
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.9
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct BasicConstraints {
+    ca: Implicit<Option<bool>>, // default is false
+    path_len_constraint: Implicit<Option<u8>>,
+}
+
+impl BasicConstraints {
+    pub fn ca(&self) -> Option<bool> {
+        self.ca.0
+    }
+
+    pub fn pathlen(&self) -> Option<u8> {
+        self.path_len_constraint.0
+    }
+}
+
+impl<'de> de::Deserialize<'de> for BasicConstraints {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = BasicConstraints;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded basic constraints extension")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                Ok(BasicConstraints {
+                    ca: Implicit(seq.next_element().unwrap_or(Some(None)).unwrap_or(None)),
+                    path_len_constraint: Implicit(seq.next_element().unwrap_or(Some(None)).unwrap_or(None)),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.10
+///
+/// Supported subtree types are DNS names, email addresses (RFC822 names), URIs (matched on their host),
+/// IP address ranges (see `GeneralName::new_ip_range`) and directory names.
+/// The `minimum` and `maximum` fields are not supported (they must be absent as per RFC 5280).
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct NameConstraints {
+    permitted_subtrees: Vec<super::name::GeneralName>,
+    excluded_subtrees: Vec<super::name::GeneralName>,
+}
+
+// NameConstraints ::= SEQUENCE {
+//      permittedSubtrees       [0]     GeneralSubtrees OPTIONAL,
+//      excludedSubtrees        [1]     GeneralSubtrees OPTIONAL }
+//
+// GeneralSubtrees ::= SEQUENCE SIZE (1..MAX) OF GeneralSubtree
+//
+// GeneralSubtree ::= SEQUENCE {
+//      base                    GeneralName,
+//      minimum         [0]     BaseDistance DEFAULT 0,
+//      maximum         [1]     BaseDistance OPTIONAL }
+
+impl NameConstraints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_permitted_subtree<GN: Into<super::name::GeneralName>>(&mut self, base: GN) {
+        self.permitted_subtrees.push(base.into());
+    }
+
+    pub fn add_excluded_subtree<GN: Into<super::name::GeneralName>>(&mut self, base: GN) {
+        self.excluded_subtrees.push(base.into());
+    }
+
+    pub fn permitted_subtrees(&self) -> &[super::name::GeneralName] {
+        &self.permitted_subtrees
+    }
+
+    pub fn excluded_subtrees(&self) -> &[super::name::GeneralName] {
+        &self.excluded_subtrees
+    }
+
+    /// Checks whether `name` satisfies these constraints.
+    ///
+    /// A name is rejected if it falls within any excluded subtree, or if permitted subtrees of the
+    /// same type exist and the name falls within none of them.
+    pub fn is_permitted(&self, name: &super::name::GeneralName) -> bool {
+        if self
+            .excluded_subtrees
+            .iter()
+            .any(|base| name_within_subtree(base, name) == Some(true))
+        {
+            return false;
+        }
+
+        let permitted: Vec<bool> = self
+            .permitted_subtrees
+            .iter()
+            .filter_map(|base| name_within_subtree(base, name))
+            .collect();
+        permitted.is_empty() || permitted.contains(&true)
+    }
+}
+
+impl ser::Serialize for NameConstraints {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(None)?;
+        if !self.permitted_subtrees.is_empty() {
+            seq.serialize_element(&ImplicitContextTag0(general_subtrees(&self.permitted_subtrees)))?;
+        }
+        if !self.excluded_subtrees.is_empty() {
+            seq.serialize_element(&ImplicitContextTag1(general_subtrees(&self.excluded_subtrees)))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for NameConstraints {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = NameConstraints;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded name constraints extension")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                // both fields are optional
+                let permitted_subtrees = match seq.next_element()? {
+                    Some(TagPeeker { next_tag: Tag::APP_0 }) => {
+                        let subtrees: ImplicitContextTag0<Asn1SequenceOf<GeneralSubtree>> =
+                            seq_next_element!(seq, NameConstraints, "permittedSubtrees");
+                        subtree_bases(subtrees.0)
+                    }
+                    _ => Vec::new(),
+                };
+                let excluded_subtrees = match seq.next_element()? {
+                    Some(TagPeeker { next_tag: Tag::APP_1 }) => {
+                        let subtrees: ImplicitContextTag1<Asn1SequenceOf<GeneralSubtree>> =
+                            seq_next_element!(seq, NameConstraints, "excludedSubtrees");
+                        subtree_bases(subtrees.0)
+                    }
+                    _ => Vec::new(),
+                };
+
+                if seq.next_element::<TagPeeker>()?.is_some() {
+                    return Err(serde_invalid_value!(
+                        NameConstraints,
+                        "unexpected element",
+                        "permittedSubtrees or excludedSubtrees"
+                    ));
+                }
+
+                Ok(NameConstraints {
+                    permitted_subtrees,
+                    excluded_subtrees,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+/// Only the base is kept (minimum and maximum are not used).
+#[derive(Serialize, Debug, PartialEq, Clone)]
+struct GeneralSubtree {
+    base: GeneralName,
+}
+
+impl<'de> de::Deserialize<'de> for GeneralSubtree {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = GeneralSubtree;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded general subtree")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let base = seq_next_element!(seq, GeneralSubtree, "base");
+                if let Some(TagPeeker { next_tag: Tag::CTX_0 }) = seq.next_element()? {
+                    let _: ContextTag0<IntegerAsn1> = seq_next_element!(seq, GeneralSubtree, "minimum");
+                }
+                if let Some(TagPeeker { next_tag: Tag::CTX_1 }) = seq.next_element()? {
+                    let _: ContextTag1<IntegerAsn1> = seq_next_element!(seq, GeneralSubtree, "maximum");
+                }
+                if seq.next_element::<TagPeeker>()?.is_some() {
+                    return Err(serde_invalid_value!(
+                        GeneralSubtree,
+                        "unexpected element",
+                        "minimum or maximum"
+                    ));
+                }
+                Ok(GeneralSubtree { base })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+fn general_subtrees(bases: &[super::name::GeneralName]) -> Asn1SequenceOf<GeneralSubtree> {
+    bases
+        .iter()
+        .map(|base| GeneralSubtree {
+            base: base.clone().into(),
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn subtree_bases(subtrees: Asn1SequenceOf<GeneralSubtree>) -> Vec<super::name::GeneralName> {
+    subtrees.0.into_iter().map(|subtree| subtree.base.into()).collect()
+}
+
+fn general_names(names: &[super::name::GeneralName]) -> Asn1SequenceOf<GeneralName> {
+    names.iter().cloned().map(GeneralName::from).collect::<Vec<_>>().into()
+}
+
+/// Returns `None` when `name` and `base` are not of the same (supported) type.
+fn name_within_subtree(base: &super::name::GeneralName, name: &super::name::GeneralName) -> Option<bool> {
+    use super::name::GeneralName;
+
+    match (base, name) {
+        (GeneralName::DNSName(base), GeneralName::DNSName(name)) => {
+            Some(dns_name_within(&base.to_string(), &name.to_string()))
+        }
+        (GeneralName::RFC822Name(base), GeneralName::RFC822Name(name)) => {
+            let (base, name) = (base.to_string(), name.to_string());
+            if base.contains('@') {
+                Some(base.eq_ignore_ascii_case(&name))
+            } else {
+                Some(
+                    name.rsplit('@')
+                        .next()
+                        .map(|host| host_within(&base, host))
+                        .unwrap_or(false),
+                )
+            }
+        }
+        (GeneralName::URI(base), GeneralName::URI(name)) => {
+            let name = name.to_string();
+            Some(
+                uri_host(&name)
+                    .map(|host| host_within(&base.to_string(), host))
+                    .unwrap_or(false),
+            )
+        }
+        (GeneralName::IpAddress(range), GeneralName::IpAddress(address)) => {
+            if range.len() != address.len() * 2 {
+                return Some(false);
+            }
+            let (network, mask) = range.split_at(address.len());
+            Some(
+                network
+                    .iter()
+                    .zip(mask)
+                    .zip(address)
+                    .all(|((network, mask), address)| network & mask == address & mask),
+            )
+        }
+        (GeneralName::DirectoryName(base), GeneralName::DirectoryName(name)) => {
+            let base = name_attributes(base);
+            Some(name_attributes(name).starts_with(&base))
+        }
+        _ => None,
+    }
+}
+
+/// A leading period restricts the constraint to subdomains, otherwise the domain itself also matches.
+fn dns_name_within(base: &str, name: &str) -> bool {
+    let base = base.to_ascii_lowercase();
+    let name = name.to_ascii_lowercase();
+    if base.is_empty() || base.starts_with('.') {
+        name.ends_with(&base)
+    } else {
+        name == base || name.ends_with(&format!(".{}", base))
+    }
+}
+
+/// A leading period restricts the constraint to subdomains, otherwise only the host itself matches.
+fn host_within(base: &str, host: &str) -> bool {
+    if base.starts_with('.') {
+        host.to_ascii_lowercase().ends_with(&base.to_ascii_lowercase())
+    } else {
+        host.eq_ignore_ascii_case(base)
+    }
+}
+
+fn uri_host(uri: &str) -> Option<&str> {
+    let authority = &uri[uri.find("://")? + 3..];
+    let authority = authority.split(&['/', '?', '#'][..]).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    if host.is_empty() {
+        None
+    } else {
+        Some(host)
+    }
+}
+
+/// Attributes of all the relative distinguished names, in order.
+fn name_attributes(name: &DirectoryName) -> Vec<AttributeTypeAndValue> {
+    (Name::from(name.clone()).0).into_iter().flat_map(|rdn| rdn.0).collect()
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.11
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct PolicyConstraints {
+    require_explicit_policy: Option<ContextTag0<IntegerAsn1>>,
+    inhibit_policy_mapping: Option<ContextTag1<IntegerAsn1>>,
+}
+
+impl PolicyConstraints {
+    /// Conforming CAs MUST NOT issue certificates where policy constraints is an empty sequence.
+    pub fn new(require_explicit_policy: Option<u32>, inhibit_policy_mapping: Option<u32>) -> Self {
+        Self {
+            require_explicit_policy: require_explicit_policy
+                .map(|skip_certs| ContextTag0(IntegerAsn1(uint_to_integer_bytes(u64::from(skip_certs))))),
+            inhibit_policy_mapping: inhibit_policy_mapping
+                .map(|skip_certs| ContextTag1(IntegerAsn1(uint_to_integer_bytes(u64::from(skip_certs))))),
+        }
+    }
+
+    pub fn require_explicit_policy(&self) -> Option<u32> {
+        self.require_explicit_policy
+            .as_ref()
+            .map(|skip_certs| skip_certs_value(&skip_certs.0))
+    }
+
+    pub fn inhibit_policy_mapping(&self) -> Option<u32> {
+        self.inhibit_policy_mapping
+            .as_ref()
+            .map(|skip_certs| skip_certs_value(&skip_certs.0))
+    }
+}
+
+impl<'de> de::Deserialize<'de> for PolicyConstraints {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = PolicyConstraints;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded policy constraints extension")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                // both fields are optional
+                let require_explicit_policy = match seq.next_element()? {
+                    Some(TagPeeker { next_tag: Tag::CTX_0 }) => {
+                        Some(seq_next_element!(seq, PolicyConstraints, "requireExplicitPolicy"))
+                    }
+                    _ => None,
+                };
+                let inhibit_policy_mapping = match seq.next_element()? {
+                    Some(TagPeeker { next_tag: Tag::CTX_1 }) => {
+                        Some(seq_next_element!(seq, PolicyConstraints, "inhibitPolicyMapping"))
+                    }
+                    _ => None,
+                };
+                let policy_constraints = PolicyConstraints {
+                    require_explicit_policy,
+                    inhibit_policy_mapping,
+                };
+
+                let require_explicit_policy = policy_constraints.require_explicit_policy.as_ref().map(|v| &v.0);
+                let inhibit_policy_mapping = policy_constraints.inhibit_policy_mapping.as_ref().map(|v| &v.0);
+                if require_explicit_policy
+                    .into_iter()
+                    .chain(inhibit_policy_mapping)
+                    .any(|skip_certs| integer_bytes_to_u32(&skip_certs.0).is_none())
+                {
+                    return Err(serde_invalid_value!(
+                        PolicyConstraints,
+                        "SkipCerts out of range",
+                        "a non-negative 32-bit integer"
+                    ));
+                }
+
+                Ok(policy_constraints)
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.12
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ExtendedKeyUsage(Asn1SequenceOf<ObjectIdentifierAsn1>);
+
+impl<OID: Into<ObjectIdentifierAsn1>> From<Vec<OID>> for ExtendedKeyUsage {
+    fn from(purpose_oids: Vec<OID>) -> Self {
+        ExtendedKeyUsage::new(purpose_oids)
+    }
+}
+
+impl ExtendedKeyUsage {
+    pub fn new<OID: Into<ObjectIdentifierAsn1>>(purpose_oids: Vec<OID>) -> Self {
+        Self(
+            purpose_oids
+                .into_iter()
+                .map(|oid| oid.into())
+                .collect::<Vec<_>>()
+                .into(),
+        )
+    }
+
+    pub fn iter(&self) -> Iter<ObjectIdentifierAsn1> {
+        (self.0).0.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<ObjectIdentifierAsn1> {
+        (self.0).0.iter_mut()
+    }
+
+    pub fn contains<C: PartialEq<oid::ObjectIdentifier>>(&self, item: C) -> bool {
+        (self.0).0.iter().any(|id| item.eq(&id.0))
+    }
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.13
+///
+/// Also used by the Freshest CRL extension (https://tools.ietf.org/html/rfc5280#section-4.2.1.15).
+/// Distribution points named relatively to the CRL issuer (`nameRelativeToCRLIssuer`) are not supported.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct CRLDistributionPoints(Vec<DistributionPoint>);
+
+// CRLDistributionPoints ::= SEQUENCE SIZE (1..MAX) OF DistributionPoint
+//
+// DistributionPoint ::= SEQUENCE {
+//      distributionPoint       [0]     DistributionPointName OPTIONAL,
+//      reasons                 [1]     ReasonFlags OPTIONAL,
+//      cRLIssuer               [2]     GeneralNames OPTIONAL }
+//
+// DistributionPointName ::= CHOICE {
+//      fullName                [0]     GeneralNames,
+//      nameRelativeToCRLIssuer [1]     RelativeDistinguishedName }
+
+impl CRLDistributionPoints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_distribution_point(&mut self, distribution_point: DistributionPoint) {
+        self.0.push(distribution_point);
+    }
+
+    pub fn iter(&self) -> Iter<'_, DistributionPoint> {
+        self.0.iter()
+    }
+
+    /// URIs of all the distribution points, in order.
+    pub fn uris(&self) -> Vec<&IA5String> {
+        self.0
+            .iter()
+            .flat_map(|distribution_point| distribution_point.full_name.iter())
+            .filter_map(|name| match name {
+                super::name::GeneralName::URI(uri) => Some(uri),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Distribution points that wouldn't be encoded back identically are rejected.
+    fn from_der(der: &[u8]) -> Option<Self> {
+        let distribution_points: Self = picky_asn1_der::from_bytes(der).ok()?;
+        if picky_asn1_der::to_vec(&distribution_points).ok()?.as_slice() == der {
+            Some(distribution_points)
+        } else {
+            None
+        }
+    }
+}
+
+impl ser::Serialize for CRLDistributionPoints {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for distribution_point in &self.0 {
+            seq.serialize_element(distribution_point)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for CRLDistributionPoints {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let distribution_points = Asn1SequenceOf::<DistributionPoint>::deserialize(deserializer)?;
+        Ok(Self(distribution_points.0))
+    }
+}
+
+impl From<Vec<DistributionPoint>> for CRLDistributionPoints {
+    fn from(distribution_points: Vec<DistributionPoint>) -> Self {
+        Self(distribution_points)
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct DistributionPoint {
+    full_name: Vec<super::name::GeneralName>,
+    reasons: Option<ReasonFlags>,
+    crl_issuer: Vec<super::name::GeneralName>,
+}
+
+impl DistributionPoint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Distribution point from which the CRL can be fetched over HTTP or LDAP.
+    pub fn new_uri<S: Into<String>>(uri: S) -> Result<Self, CharSetError> {
+        let mut distribution_point = Self::default();
+        distribution_point.add_full_name(super::name::GeneralName::new_uri(uri)?);
+        Ok(distribution_point)
+    }
+
+    pub fn add_full_name<GN: Into<super::name::GeneralName>>(&mut self, name: GN) {
+        self.full_name.push(name.into());
+    }
+
+    /// Restricts the distribution point to the given revocation reasons.
+    pub fn set_reasons(&mut self, reasons: ReasonFlags) {
+        self.reasons = Some(reasons);
+    }
+
+    /// Name of the CRL issuer when it isn't the certificate issuer.
+    pub fn add_crl_issuer<GN: Into<super::name::GeneralName>>(&mut self, name: GN) {
+        self.crl_issuer.push(name.into());
+    }
+
+    pub fn full_name(&self) -> &[super::name::GeneralName] {
+        &self.full_name
+    }
+
+    pub fn reasons(&self) -> Option<&ReasonFlags> {
+        self.reasons.as_ref()
+    }
+
+    pub fn crl_issuer(&self) -> &[super::name::GeneralName] {
+        &self.crl_issuer
+    }
+}
+
+impl ser::Serialize for DistributionPoint {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(None)?;
+        if !self.full_name.is_empty() {
+            // DistributionPointName is a CHOICE, hence the explicit tag
+            seq.serialize_element(&ApplicationTag0(ImplicitContextTag0(general_names(&self.full_name))))?;
+        }
+        if let Some(reasons) = &self.reasons {
+            seq.serialize_element(&ContextTag1(reasons))?;
+        }
+        if !self.crl_issuer.is_empty() {
+            seq.serialize_element(&ImplicitContextTag2(general_names(&self.crl_issuer)))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for DistributionPoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = DistributionPoint;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded distribution point")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                // all fields are optional
+                let mut distribution_point = DistributionPoint::default();
+                if let Some(TagPeeker { next_tag: Tag::APP_0 }) = seq.next_element()? {
+                    // nameRelativeToCRLIssuer is rejected here
+                    let full_name: ApplicationTag0<ImplicitContextTag0<Asn1SequenceOf<GeneralName>>> =
+                        seq_next_element!(seq, DistributionPoint, "distributionPoint");
+                    distribution_point.full_name = ((full_name.0).0).0.into_iter().map(Into::into).collect();
+                }
+                if let Some(TagPeeker { next_tag: Tag::CTX_1 }) = seq.next_element()? {
+                    let reasons: ContextTag1<ReasonFlags> = seq_next_element!(seq, DistributionPoint, "reasons");
+                    distribution_point.reasons = Some(reasons.0);
+                }
+                if let Some(TagPeeker { next_tag: Tag::APP_2 }) = seq.next_element()? {
+                    let crl_issuer: ImplicitContextTag2<Asn1SequenceOf<GeneralName>> =
+                        seq_next_element!(seq, DistributionPoint, "cRLIssuer");
+                    distribution_point.crl_issuer = (crl_issuer.0).0.into_iter().map(Into::into).collect();
+                }
+                if seq.next_element::<TagPeeker>()?.is_some() {
+                    return Err(serde_invalid_value!(
+                        DistributionPoint,
+                        "unexpected element",
+                        "distributionPoint, reasons or cRLIssuer"
+                    ));
+                }
+                Ok(distribution_point)
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.13
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReasonFlags(BitString);
+
+impl Default for ReasonFlags {
+    fn default() -> Self {
+        Self(BitString::with_len(9))
+    }
+}
+
+impl ReasonFlags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    bit_string_get_set! {
+        key_compromise, set_key_compromise, 1;
+        ca_compromise, set_ca_compromise, 2;
+        affiliation_changed, set_affiliation_changed, 3;
+        superseded, set_superseded, 4;
+        cessation_of_operation, set_cessation_of_operation, 5;
+        certificate_hold, set_certificate_hold, 6;
+        privilege_withdrawn, set_privilege_withdrawn, 7;
+        aa_compromise, set_aa_compromise, 8;
+    }
+}
+
+impl ser::Serialize for ReasonFlags {
+    /// Trailing zero bits are removed as required by DER.
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        let num_bits = (0..self.0.get_num_bits())
+            .rev()
+            .find(|idx| self.0.is_set(*idx))
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+
+        let mut bits = BitString::with_len(num_bits);
+        for idx in 0..num_bits {
+            bits.set(idx, self.0.is_set(idx));
+        }
+
+        BitStringAsn1(bits).serialize(serializer)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for ReasonFlags {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let bits = BitStringAsn1::deserialize(deserializer)?.0;
+        let mut reasons = Self::default();
+        for idx in 0..bits.get_num_bits().min(reasons.0.get_num_bits()) {
+            reasons.0.set(idx, bits.is_set(idx));
+        }
+        Ok(reasons)
+    }
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.1.14
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct InhibitAnyPolicy(IntegerAsn1);
+
+impl<'de> de::Deserialize<'de> for InhibitAnyPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let skip_certs = IntegerAsn1::deserialize(deserializer)?;
+        if integer_bytes_to_u32(&skip_certs.0).is_none() {
+            return Err(serde_invalid_value!(
+                InhibitAnyPolicy,
+                "SkipCerts out of range",
+                "a non-negative 32-bit integer"
+            ));
+        }
+        Ok(Self(skip_certs))
+    }
+}
+
+impl InhibitAnyPolicy {
+    pub fn new(skip_certs: u32) -> Self {
+        Self(IntegerAsn1(uint_to_integer_bytes(u64::from(skip_certs))))
+    }
+
+    /// Number of additional non-self-issued certificates that may appear in the path before
+    /// anyPolicy is no longer permitted.
+    pub fn skip_certs(&self) -> u32 {
+        skip_certs_value(&self.0)
+    }
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-4.2.2.1
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct AuthorityInfoAccess(Asn1SequenceOf<AccessDescription>);
+
+impl Default for AuthorityInfoAccess {
+    fn default() -> Self {
+        Self(Asn1SequenceOf(Vec::new()))
+    }
+}
+
+impl AuthorityInfoAccess {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_access_description(&mut self, access_description: AccessDescription) {
+        (self.0).0.push(access_description);
+    }
+
+    pub fn iter(&self) -> Iter<'_, AccessDescription> {
+        (self.0).0.iter()
+    }
+
+    /// URIs of the OCSP responders, in order.
+    pub fn ocsp_uris(&self) -> Vec<&IA5String> {
+        self.uris(&oids::ad_ocsp())
+    }
+
+    /// URIs from which the issuing CA certificate can be fetched, in order.
+    pub fn ca_issuers_uris(&self) -> Vec<&IA5String> {
+        self.uris(&oids::ad_ca_issuers())
+    }
+
+    fn uris(&self, access_method: &ObjectIdentifier) -> Vec<&IA5String> {
+        (self.0)
+            .0
+            .iter()
+            .filter(|access_description| &access_description.access_method.0 == access_method)
+            .filter_map(|access_description| match &access_description.access_location {
+                GeneralName::URI(uri) => Some(&uri.0),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<AccessDescription>> for AuthorityInfoAccess {
+    fn from(access_descriptions: Vec<AccessDescription>) -> Self {
+        Self(access_descriptions.into())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct AccessDescription {
+    access_method: ObjectIdentifierAsn1,
+    access_location: GeneralName,
+}
+
+impl AccessDescription {
+    pub fn new<M, L>(access_method: M, access_location: L) -> Self
+    where
+        M: Into<ObjectIdentifierAsn1>,
+        L: Into<super::name::GeneralName>,
+    {
+        Self {
+            access_method: access_method.into(),
+            access_location: access_location.into().into(),
+        }
+    }
+
+    /// Location of an OCSP responder for the certificate.
+    pub fn new_ocsp<S: Into<String>>(uri: S) -> Result<Self, CharSetError> {
+        Ok(Self::new(oids::ad_ocsp(), super::name::GeneralName::new_uri(uri)?))
+    }
+
+    /// Location of the certificate of the CA that issued the certificate.
+    pub fn new_ca_issuers<S: Into<String>>(uri: S) -> Result<Self, CharSetError> {
+        Ok(Self::new(
+            oids::ad_ca_issuers(),
+            super::name::GeneralName::new_uri(uri)?,
+        ))
+    }
+
+    pub fn access_method(&self) -> &ObjectIdentifier {
+        &self.access_method.0
+    }
+
+    pub fn access_location(&self) -> super::name::GeneralName {
+        self.access_location.clone().into()
+    }
+}
+
+/// https://tools.ietf.org/html/rfc5280#section-5.2.3
+pub type CRLNumber = IntegerAsn1;
+
+/// https://tools.ietf.org/html/rfc5280#section-5.3.1
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u8)]
+pub enum CRLReason {
+    Unspecified = 0,
+    KeyCompromise = 1,
+    CACompromise = 2,
+    AffiliationChanged = 3,
+    Superseded = 4,
+    CessationOfOperation = 5,
+    CertificateHold = 6,
+    RemoveFromCRL = 8,
+    PrivilegeWithdrawn = 9,
+    AACompromise = 10,
+}
+
+// CRLReason ::= ENUMERATED { ... }
+
+impl CRLReason {
+    pub fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            0 => Some(Self::Unspecified),
+            1 => Some(Self::KeyCompromise),
+            2 => Some(Self::CACompromise),
+            3 => Some(Self::AffiliationChanged),
+            4 => Some(Self::Superseded),
+            5 => Some(Self::CessationOfOperation),
+            6 => Some(Self::CertificateHold),
+            8 => Some(Self::RemoveFromCRL),
+            9 => Some(Self::PrivilegeWithdrawn),
+            10 => Some(Self::AACompromise),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_enumerated(self) -> EnumeratedAsn1 {
+        vec![self as u8].into()
+    }
+
+    pub(crate) fn from_enumerated(value: &EnumeratedAsn1) -> Option<Self> {
+        match value.0.as_slice() {
+            [v] => Self::from_u8(*v),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pem::Pem, x509::private::name::GeneralName};
+    use picky_asn1::restricted_string::IA5String;
+
+    #[test]
+    fn key_usage() {
+        let encoded: [u8; 4] = [0x03, 0x02, 0x01, 0xA0];
+        let mut key_usage = KeyUsage::new(7);
+        key_usage.set_digital_signature(true);
+        key_usage.set_key_encipherment(true);
+        assert_eq!(key_usage.as_bytes(), &[0xA0]);
+        check_serde!(key_usage: KeyUsage in encoded);
+    }
+
+    const CSR_PEM: &str = "-----BEGIN CERTIFICATE REQUEST-----\n\
+                           MIIDIjCCAgoCAQAwIDELMAkGA1UEBhMCRlIxETAPBgNVBAMMCERyYXBlYXUhMIIB\n\
+                           IjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEA5GqDEM7AfctJizsFEqtAvXd5\n\
+                           Fl1GtyXDAnx68MUTuSL22t8aBZoCCi3/9AlS75uUqKggHnRuY2MRYPQaUzpE1F1a\n\
+                           aZJNr6tXQy39FtdXrDq2zfwZdDmLW6sPmhvJrBO4yWjuG3wh1paPHy+rBHOjYt+9\n\
+                           Pbl/FmDDjIzF8B2LZDuLdnS94Fs/JhogJL/XF4b6RLW60gEnYFjL+ebYdV/f3JYi\n\
+                           ccQxY4imvbB2URlIO3t+aG9WMmhHZbbOi/HBdFG1fB7Hsa9Ek2FXshULzEDCJcMz\n\
+                           n8HD96XbVBmlaz9nYIcZ83eCOhra67FfFy4pIE1M9saxYJg/OJrMHG12r89yUQID\n\
+                           AQABoIG8MIG5BgkqhkiG9w0BCQ4xgaswgagwCQYDVR0TBAIwADALBgNVHQ8EBAMC\n\
+                           BeAwJwYDVR0lBCAwHgYIKwYBBQUHAwIGCCsGAQUFBwMBBggrBgEFBQcDAzBlBgNV\n\
+                           HREEXjBcghFkZXZlbC5leGFtcGxlLmNvbYIQaXB2Ni5leGFtcGxlLmNvbYIQaXB2\n\
                            NC5leGFtcGxlLmNvbYIQdGVzdC5leGFtcGxlLmNvbYIRcGFydHkuZXhhbXBsZS5j\n\
                            b20wDQYJKoZIhvcNAQELBQADggEBANaSDnpQUGcGypAaafJKAGME2Od8F4pvKjKF\n\
                            lREoWC7JFGIGE/pUrnvrE7qIFmCM3mnFWXEHvResFsdPmEWar+1jMdFinxBg0+J+\n\
@@ -572,4 +2262,299 @@ mod tests {
 
         check_serde!(extensions: Extensions in encoded);
     }
+
+    #[test]
+    fn name_constraints() {
+        use crate::x509::name::GeneralName;
+        use std::net::{IpAddr, Ipv4Addr};
+
+        // generated by openssl with
+        // nameConstraints=critical,permitted;DNS:example.com,permitted;email:.example.com,
+        //     permitted;IP:192.168.0.0/255.255.0.0,permitted;URI:.example.com,
+        //     permitted;dirName:dir_sect,excluded;DNS:bad.example.com
+        let encoded = hex::decode(
+            "30760603551D1E0101FF046C306AA053300D820B6578616D706C652E636F6D300E810C2E6578616D706C652E636F6D300A8708C0\
+             A80000FFFF0000300E860C2E6578616D706C652E636F6D3016A41430123110300E06035504030C074578616D706C65A11330118\
+             20F6261642E6578616D706C652E636F6D",
+        )
+        .unwrap();
+
+        let mut name_constraints = NameConstraints::new();
+        name_constraints.add_permitted_subtree(GeneralName::new_dns_name("example.com").unwrap());
+        name_constraints.add_permitted_subtree(GeneralName::new_rfc822_name(".example.com").unwrap());
+        name_constraints
+            .add_permitted_subtree(GeneralName::new_ip_range(IpAddr::V4(Ipv4Addr::new(192, 168, 0, 0)), 16));
+        name_constraints.add_permitted_subtree(GeneralName::new_uri(".example.com").unwrap());
+        name_constraints.add_permitted_subtree(GeneralName::new_directory_name(DirectoryName::new_common_name(
+            "Example",
+        )));
+        name_constraints.add_excluded_subtree(GeneralName::new_dns_name("bad.example.com").unwrap());
+
+        let extension = Extension::new_name_constraints(name_constraints.clone());
+        check_serde!(extension: Extension in encoded);
+
+        for (name, permitted) in vec![
+            (GeneralName::new_dns_name("example.com").unwrap(), true),
+            (GeneralName::new_dns_name("www.EXAMPLE.com").unwrap(), true),
+            (GeneralName::new_dns_name("badexample.com").unwrap(), false),
+            (GeneralName::new_dns_name("bad.example.com").unwrap(), false),
+            (GeneralName::new_dns_name("www.bad.example.com").unwrap(), false),
+            (GeneralName::new_rfc822_name("john@mail.example.com").unwrap(), true),
+            (GeneralName::new_rfc822_name("john@example.com").unwrap(), false),
+            (GeneralName::new_ip_address(vec![192, 168, 12, 1]), true),
+            (GeneralName::new_ip_address(vec![192, 169, 0, 1]), false),
+            (GeneralName::new_ip_address(vec![0; 16]), false),
+            (
+                GeneralName::new_uri("https://user@www.example.com:8080/path").unwrap(),
+                true,
+            ),
+            (GeneralName::new_uri("https://example.com/path").unwrap(), false),
+            (GeneralName::new_uri("urn:example").unwrap(), false),
+            (
+                GeneralName::new_directory_name(DirectoryName::new_common_name("Example")),
+                true,
+            ),
+            (
+                GeneralName::new_directory_name(DirectoryName::new_common_name("Other")),
+                false,
+            ),
+            (GeneralName::new_registered_id(oids::kp_server_auth()), true),
+        ] {
+            assert_eq!(name_constraints.is_permitted(&name), permitted, "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn policy_extensions() {
+        use std::convert::TryFrom;
+
+        // generated by openssl with
+        // certificatePolicies=@pol1,1.2.3.5
+        // policyMappings=critical,1.2.3.4:1.2.3.5
+        // policyConstraints=critical,requireExplicitPolicy:0,inhibitPolicyMapping:1
+        // inhibitAnyPolicy=critical,2
+        // [pol1]
+        // policyIdentifier=1.2.3.4
+        // CPS.1="http://cps.example.com"
+        // userNotice.1=@notice
+        // [notice]
+        // explicitText="Example notice"
+        // organization="Example Org"
+        // noticeNumbers=1,2
+        let encoded = hex::decode(
+            "3081B130720603551D20046B3069306006032A03043059302206082B060105050702011616687474703A2F2F6370732E6578616D\
+             706C652E636F6D303306082B06010505070202302730151A0B4578616D706C65204F726730060201010201021A0E4578616D706C\
+             65206E6F74696365300506032A030530180603551D210101FF040E300C300A06032A030406032A030530120603551D240101FF04\
+             083006800100810101300D0603551D360101FF0403020102",
+        )
+        .unwrap();
+
+        let oid = |oid: &str| ObjectIdentifier::try_from(oid).unwrap();
+
+        let mut policy = PolicyInformation::new(oid("1.2.3.4"));
+        policy.add_qualifier(PolicyQualifier::new_cps_uri("http://cps.example.com").unwrap());
+        policy.add_qualifier(PolicyQualifier::new_user_notice(
+            Some(NoticeReference::new(
+                DisplayText::new_visible_string("Example Org").unwrap(),
+                vec![1, 2],
+            )),
+            Some(DisplayText::new_visible_string("Example notice").unwrap()),
+        ));
+
+        let extensions = Extensions(vec![
+            Extension::new_certificate_policies(vec![policy, PolicyInformation::new(oid("1.2.3.5"))]),
+            Extension::new_policy_mappings(vec![PolicyMapping::new(oid("1.2.3.4"), oid("1.2.3.5"))]),
+            Extension::new_policy_constraints(PolicyConstraints::new(Some(0), Some(1))),
+            Extension::new_inhibit_any_policy(2),
+        ]);
+
+        check_serde!(extensions: Extensions in encoded);
+
+        match extensions.0[0].extn_value() {
+            ExtensionView::CertificatePolicies(policies) => {
+                assert!(policies.contains(oid("1.2.3.5")));
+                match &policies.iter().next().unwrap().policy_qualifiers()[1] {
+                    PolicyQualifier::UserNotice(notice) => {
+                        assert_eq!(notice.explicit_text().unwrap().to_string(), "Example notice");
+                        assert_eq!(notice.notice_ref().unwrap().notice_numbers(), &[1, 2]);
+                    }
+                    other => panic!("unexpected qualifier: {:?}", other),
+                }
+            }
+            other => panic!("unexpected extension: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn out_of_range_skip_certs() {
+        let inhibit_any_policy = [0x30, 0x0A, 0x06, 0x03, 0x55, 0x1D, 0x36, 0x04, 0x03, 0x02, 0x01, 0x02];
+        let extension: Extension = picky_asn1_der::from_bytes(&inhibit_any_policy).expect("inhibit any policy");
+        match extension.extn_value() {
+            ExtensionView::InhibitAnyPolicy(inhibit) => assert_eq!(inhibit.skip_certs(), 2),
+            other => panic!("unexpected extension: {:?}", other),
+        }
+
+        let inhibit_policy_mapping = [
+            0x30, 0x0C, 0x06, 0x03, 0x55, 0x1D, 0x24, 0x04, 0x05, 0x30, 0x03, 0x81, 0x01, 0x03,
+        ];
+        let extension: Extension = picky_asn1_der::from_bytes(&inhibit_policy_mapping).expect("policy constraints");
+        match extension.extn_value() {
+            ExtensionView::PolicyConstraints(constraints) => {
+                assert_eq!(constraints.require_explicit_policy(), None);
+                assert_eq!(constraints.inhibit_policy_mapping(), Some(3));
+            }
+            other => panic!("unexpected extension: {:?}", other),
+        }
+
+        for encoded in &[
+            // negative inhibitAnyPolicy
+            &[0x30, 0x0A, 0x06, 0x03, 0x55, 0x1D, 0x36, 0x04, 0x03, 0x02, 0x01, 0xFF][..],
+            // negative requireExplicitPolicy
+            &[
+                0x30, 0x0C, 0x06, 0x03, 0x55, 0x1D, 0x24, 0x04, 0x05, 0x30, 0x03, 0x80, 0x01, 0xFF,
+            ][..],
+            // inhibitPolicyMapping larger than 2^32 - 1
+            &[
+                0x30, 0x10, 0x06, 0x03, 0x55, 0x1D, 0x24, 0x04, 0x09, 0x30, 0x07, 0x81, 0x05, 0x01, 0x00, 0x00, 0x00,
+                0x00,
+            ][..],
+        ] {
+            picky_asn1_der::from_bytes::<Extension>(encoded).expect_err("out of range SkipCerts");
+        }
+    }
+
+    #[test]
+    fn display_text_encodings() {
+        for (display_text, encoded, text) in [
+            (
+                DisplayText::IA5String(IA5String::from_string("ia5".to_owned()).unwrap()),
+                &[0x16, 0x03, 0x69, 0x61, 0x35][..],
+                "ia5",
+            ),
+            (
+                DisplayText::new_visible_string("vis").unwrap(),
+                &[0x1A, 0x03, 0x76, 0x69, 0x73][..],
+                "vis",
+            ),
+            (
+                DisplayText::new_bmp_string("bé").unwrap(),
+                &[0x1E, 0x04, 0x00, 0x62, 0x00, 0xE9][..],
+                "bé",
+            ),
+            (
+                DisplayText::from("u é"),
+                &[0x0C, 0x04, 0x75, 0x20, 0xC3, 0xA9][..],
+                "u é",
+            ),
+        ] {
+            check_serde!(display_text: DisplayText in encoded);
+            assert_eq!(display_text.to_string(), text);
+        }
+
+        DisplayText::new_visible_string("é").expect_err("not a VisibleString");
+        DisplayText::new_bmp_string("🦀").expect_err("outside of the Basic Multilingual Plane");
+        picky_asn1_der::from_bytes::<DisplayText>(&[0x1A, 0x02, 0xC3, 0xA9]).expect_err("invalid VisibleString");
+        assert_eq!(integer_bytes_to_u32(&uint_to_integer_bytes(0x80)), Some(0x80));
+        assert_eq!(uint_to_integer_bytes(0x80), vec![0x00, 0x80]);
+        assert_eq!(integer_bytes_to_u32(&[0x01, 0x00, 0x00, 0x00, 0x00]), None);
+    }
+
+    #[test]
+    fn revocation_information_extensions() {
+        use crate::x509::name::GeneralName;
+
+        // generated by openssl with
+        // crlDistributionPoints=dp1,dp2
+        // freshestCRL=URI:http://crl.example.com/delta.crl
+        // authorityInfoAccess=OCSP;URI:http://ocsp.example.com,caIssuers;URI:http://ca.example.com/ca.crt
+        // [dp1]
+        // fullname=URI:http://crl.example.com/ca.crl
+        // [dp2]
+        // fullname=URI:ldap://ldap.example.com/cn=CA
+        // reasons=keyCompromise,CACompromise
+        // CRLissuer=dirName:issuer_sect
+        let encoded = hex::decode(
+            "3082010630720603551D1F046B30693023A021A01F861D687474703A2F2F63726C2E6578616D706C652E636F6D2F63612E63726C\
+             3042A021A01F861D6C6461703A2F2F6C6461702E6578616D706C652E636F6D2F636E3D434181020560A219A41730153113301106\
+             035504030C0A43524C2049737375657230310603551D2E042A30283026A024A0228620687474703A2F2F63726C2E6578616D706C\
+             652E636F6D2F64656C74612E63726C305D06082B060105050701010451304F302306082B060105050730018617687474703A2F2F\
+             6F6373702E6578616D706C652E636F6D302806082B06010505073002861C687474703A2F2F63612E6578616D706C652E636F6D2F\
+             63612E637274",
+        )
+        .unwrap();
+
+        let mut reasons = ReasonFlags::new();
+        reasons.set_key_compromise(true);
+        reasons.set_ca_compromise(true);
+        let mut ldap_distribution_point = DistributionPoint::new_uri("ldap://ldap.example.com/cn=CA").unwrap();
+        ldap_distribution_point.set_reasons(reasons);
+        ldap_distribution_point.add_crl_issuer(GeneralName::new_directory_name(DirectoryName::new_common_name(
+            "CRL Issuer",
+        )));
+
+        let extensions = Extensions(vec![
+            Extension::new_crl_distribution_points(vec![
+                DistributionPoint::new_uri("http://crl.example.com/ca.crl").unwrap(),
+                ldap_distribution_point,
+            ]),
+            Extension::new_freshest_crl(vec![
+                DistributionPoint::new_uri("http://crl.example.com/delta.crl").unwrap()
+            ]),
+            Extension::new_authority_info_access(vec![
+                AccessDescription::new_ocsp("http://ocsp.example.com").unwrap(),
+                AccessDescription::new_ca_issuers("http://ca.example.com/ca.crt").unwrap(),
+            ]),
+        ]);
+
+        check_serde!(extensions: Extensions in encoded);
+
+        match extensions.0[0].extn_value() {
+            ExtensionView::CRLDistributionPoints(distribution_points) => {
+                let uris: Vec<String> = distribution_points.uris().iter().map(|uri| uri.to_string()).collect();
+                assert_eq!(
+                    uris,
+                    vec!["http://crl.example.com/ca.crl", "ldap://ldap.example.com/cn=CA"]
+                );
+
+                let reasons = distribution_points.iter().nth(1).unwrap().reasons().unwrap();
+                assert!(reasons.key_compromise());
+                assert!(reasons.ca_compromise());
+                assert!(!reasons.superseded());
+            }
+            other => panic!("unexpected extension: {:?}", other),
+        }
+
+        match extensions.0[2].extn_value() {
+            ExtensionView::AuthorityInfoAccess(aia) => {
+                assert_eq!(aia.ocsp_uris()[0].to_string(), "http://ocsp.example.com");
+                assert_eq!(aia.ca_issuers_uris()[0].to_string(), "http://ca.example.com/ca.crt");
+            }
+            other => panic!("unexpected extension: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unsupported_distribution_point_name() {
+        // distribution point named relatively to the CRL issuer (CN=CRL1)
+        let encoded = hex::decode("301C0603551D1F041530133011A00FA10D300B06035504030C0443524C31").unwrap();
+        let extension: Extension = picky_asn1_der::from_bytes(&encoded).expect("deserialization");
+        match extension.extn_value() {
+            ExtensionView::Generic(_) => {}
+            other => panic!("unexpected extension: {:?}", other),
+        }
+        assert_eq!(picky_asn1_der::to_vec(&extension).expect("serialization"), encoded);
+    }
+
+    #[test]
+    fn unsupported_access_location() {
+        // OCSP responder identified by an otherName
+        let encoded =
+            hex::decode("302606082B06010505070101041A3018301606082B06010505073001A00A06032A0304A003020101").unwrap();
+        let extension: Extension = picky_asn1_der::from_bytes(&encoded).expect("deserialization");
+        match extension.extn_value() {
+            ExtensionView::Generic(_) => {}
+            other => panic!("unexpected extension: {:?}", other),
+        }
+        assert_eq!(picky_asn1_der::to_vec(&extension).expect("serialization"), encoded);
+    }
 }
@@ -44,9 +44,7 @@ pub enum KeyIdGenMethod {
 
 macro_rules! hash {
     ( @ $algorithm:ident, $input:ident) => {{
-        let mut digest = $algorithm::new();
-        digest.input($input);
-        digest.result().as_slice().to_vec()
+        $algorithm::digest($input).to_vec()
     }};
     ($hash_algo:ident, $input:ident) => {
         match $hash_algo {
@@ -70,7 +68,7 @@ impl KeyIdGenMethod {
                     })?;
                     Ok(hash!(hash_algo, der)[..20].to_vec())
                 }
-                InnerPublicKey::EC(bitstring) => {
+                InnerPublicKey::EC(bitstring) | InnerPublicKey::ED(bitstring) => {
                     let der = bitstring.0.payload_view();
                     Ok(hash!(hash_algo, der)[..20].to_vec())
                 }
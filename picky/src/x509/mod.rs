@@ -1,15 +1,28 @@
 mod private;
 
 pub mod certificate;
+pub mod chain;
+pub mod crl;
 pub mod csr;
 pub mod date;
 pub mod directory_string;
 pub mod extension;
 pub mod key_id_gen_method;
 pub mod name;
+pub mod ocsp;
+pub mod policy;
+pub mod purpose;
+pub mod revocation;
+pub mod trust_store;
 
 pub use certificate::Cert;
+pub use chain::{ChainBuilder, ChainValidationSettings};
+pub use crl::Crl;
 pub use csr::Csr;
 pub use directory_string::DirectoryString;
 pub use extension::{Extension, Extensions};
 pub use key_id_gen_method::KeyIdGenMethod;
+pub use policy::PolicySettings;
+pub use purpose::KeyPurpose;
+pub use revocation::RevocationSettings;
+pub use trust_store::TrustStore;
@@ -11,7 +11,7 @@ use picky_asn1::{
     restricted_string::{CharSetError, IA5String},
     wrapper::{Asn1SequenceOf, Asn1SetOf},
 };
-use std::fmt;
+use std::{fmt, net::IpAddr};
 
 // === DirectoryName ===
 
@@ -60,6 +60,24 @@ impl DirectoryName {
         None
     }
 
+    /// Returns the email addresses (deprecated emailAddress attribute) contained in this `Name`
+    pub fn email_addresses(&self) -> Vec<&IA5String> {
+        let mut addresses = Vec::new();
+        for relative_distinguished_name in &((self.0).0) {
+            for attr_ty_val in &relative_distinguished_name.0 {
+                if let AttributeTypeAndValueParameters::EmailAddress(address) = &attr_ty_val.value {
+                    addresses.push(&address.0);
+                }
+            }
+        }
+        addresses
+    }
+
+    pub fn add_email_address<S: Into<IA5String>>(&mut self, address: S) {
+        let ty_val = AttributeTypeAndValue::new_email_address(address.into());
+        ((self.0).0)[0].0.push(ty_val);
+    }
+
     pub fn add_attr<S: Into<DirectoryString>>(&mut self, attr: NameAttr, value: S) {
         let ty_val = match attr {
             NameAttr::CommonName => AttributeTypeAndValue::new_common_name(value),
@@ -142,6 +160,25 @@ impl GeneralName {
         Self::IpAddress(ip_address.into())
     }
 
+    /// IP address range as used in name constraints: the network address followed by its mask.
+    pub fn new_ip_range(network: IpAddr, prefix_len: u8) -> Self {
+        let address = match network {
+            IpAddr::V4(addr) => addr.octets().to_vec(),
+            IpAddr::V6(addr) => addr.octets().to_vec(),
+        };
+
+        let mask = (0..address.len())
+            .map(|idx| {
+                let bits = usize::from(prefix_len).saturating_sub(idx * 8).min(8);
+                (0xFF00u16 >> bits) as u8
+            })
+            .collect::<Vec<u8>>();
+
+        let mut range = address;
+        range.extend(mask);
+        Self::IpAddress(range)
+    }
+
     pub fn new_registered_id<OID: Into<ObjectIdentifier>>(oid: OID) -> Self {
         Self::RegisteredId(oid.into())
     }
@@ -0,0 +1,1298 @@
+//! Online Certificate Status Protocol (https://tools.ietf.org/html/rfc6960)
+
+use crate::{
+    algorithm_identifier::HashAlgorithm,
+    key::PublicKey,
+    oids,
+    signature::{SignatureError, SignatureHashType, Signer},
+    x509::{
+        certificate::{Cert, CertError},
+        date::UTCDate,
+        extension::{CRLReason, ExtensionView},
+        name::DirectoryName,
+        private::{
+            ocsp::{
+                BasicOCSPResponse, OCSPRequest, OCSPResponse, Request, ResponseBytes, ResponseData, RevokedInfo,
+                TBSRequest,
+            },
+            Certificate, Name,
+        },
+        Extension, Extensions,
+    },
+    AlgorithmIdentifier,
+};
+use picky_asn1::{
+    bit_string::BitString,
+    date::GeneralizedTime,
+    tag::{Tag, TagPeeker},
+    wrapper::{
+        ApplicationTag0, ApplicationTag1, ApplicationTag2, ContextTag0, ContextTag2, EnumeratedAsn1,
+        GeneralizedTimeAsn1, ImplicitContextTag1, IntegerAsn1, OctetStringAsn1, RawDerAsn1,
+    },
+};
+use picky_asn1_der::Asn1DerError;
+use serde::{de, ser, Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{cell::RefCell, fmt};
+
+#[derive(Debug, Snafu)]
+pub enum OcspError {
+    /// couldn't generate OCSP response
+    #[snafu(display("couldn't generate OCSP response: {}", source))]
+    ResponseGeneration {
+        #[snafu(source(from(OcspError, Box::new)))]
+        source: Box<OcspError>,
+    },
+
+    /// asn1 serialization error
+    #[snafu(display("(asn1) couldn't serialize {}: {}", element, source))]
+    Asn1Serialization {
+        element: &'static str,
+        source: Asn1DerError,
+    },
+
+    /// asn1 deserialization error
+    #[snafu(display("(asn1) couldn't deserialize {}: {}", element, source))]
+    Asn1Deserialization {
+        element: &'static str,
+        source: Asn1DerError,
+    },
+
+    /// invalid or non-DER encoding
+    #[snafu(display("invalid {} encoding", element))]
+    InvalidEncoding { element: &'static str },
+
+    /// signature error
+    #[snafu(display("signature error: {}", source))]
+    Signature { source: SignatureError },
+
+    /// invalid certificate
+    #[snafu(display("invalid certificate: {}", source))]
+    InvalidCertificate { source: CertError },
+
+    /// missing required builder argument
+    #[snafu(display("missing required builder argument `{}`", arg))]
+    MissingBuilderArgument { arg: &'static str },
+
+    /// unsupported hash algorithm
+    #[snafu(display("unsupported hash algorithm: {}", algorithm))]
+    UnsupportedHashAlgorithm { algorithm: String },
+
+    /// OCSP response is not successful
+    #[snafu(display("OCSP response is not successful: {:?}", status))]
+    UnsuccessfulResponse { status: OcspResponseStatus },
+
+    /// unsupported OCSP response type
+    #[snafu(display("unsupported OCSP response type: {}", response_type))]
+    UnsupportedResponseType { response_type: String },
+
+    /// responder certificate not found
+    #[snafu(display("responder certificate not found ({})", responder_id))]
+    ResponderNotFound { responder_id: String },
+
+    /// responder is not authorized to sign OCSP responses for the issuer
+    #[snafu(display(
+        "responder '{}' is not authorized to sign OCSP responses for '{}': {}",
+        responder_id,
+        issuer_id,
+        reason
+    ))]
+    ResponderNotAuthorized {
+        responder_id: String,
+        issuer_id: String,
+        reason: &'static str,
+    },
+}
+
+/// Hash of the value of the BIT STRING subjectPublicKey (excluding the tag, length, and number of unused bits).
+fn public_key_hash(public_key: &PublicKey, hash_algorithm: HashAlgorithm) -> Result<Vec<u8>, OcspError> {
+    use crate::private::subject_public_key_info::PublicKey as InnerPublicKey;
+    use picky_asn1::wrapper::BitStringAsn1Container;
+
+    match &public_key.as_inner().subject_public_key {
+        InnerPublicKey::RSA(BitStringAsn1Container(rsa_pk)) => {
+            let der = picky_asn1_der::to_vec(rsa_pk).context(Asn1Serialization {
+                element: "RSA public key",
+            })?;
+            Ok(hash_algorithm.digest(&der))
+        }
+        InnerPublicKey::EC(bitstring) | InnerPublicKey::ED(bitstring) => {
+            Ok(hash_algorithm.digest(bitstring.0.payload_view()))
+        }
+    }
+}
+
+/// Identifies a certificate by its issuer and serial number.
+///
+/// https://tools.ietf.org/html/rfc6960#section-4.1.1
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct CertId {
+    hash_algorithm: AlgorithmIdentifier,
+    issuer_name_hash: OctetStringAsn1,
+    issuer_key_hash: OctetStringAsn1,
+    serial_number: IntegerAsn1,
+}
+
+impl CertId {
+    pub fn new(cert: &Cert, issuer: &Cert, hash_algorithm: HashAlgorithm) -> Result<Self, OcspError> {
+        Self::from_serial_number(cert.serial_number().clone(), issuer, hash_algorithm)
+    }
+
+    pub fn from_serial_number(
+        serial_number: IntegerAsn1,
+        issuer: &Cert,
+        hash_algorithm: HashAlgorithm,
+    ) -> Result<Self, OcspError> {
+        let issuer_name = picky_asn1_der::to_vec(&Name::from(issuer.subject_name()))
+            .context(Asn1Serialization { element: "issuer name" })?;
+
+        Ok(Self {
+            hash_algorithm: AlgorithmIdentifier::new_sha(hash_algorithm),
+            issuer_name_hash: hash_algorithm.digest(&issuer_name).into(),
+            issuer_key_hash: public_key_hash(issuer.public_key(), hash_algorithm)?.into(),
+            serial_number,
+        })
+    }
+
+    pub fn hash_algorithm(&self) -> &AlgorithmIdentifier {
+        &self.hash_algorithm
+    }
+
+    pub fn issuer_name_hash(&self) -> &[u8] {
+        &self.issuer_name_hash.0
+    }
+
+    pub fn issuer_key_hash(&self) -> &[u8] {
+        &self.issuer_key_hash.0
+    }
+
+    pub fn serial_number(&self) -> &IntegerAsn1 {
+        &self.serial_number
+    }
+
+    /// Whether this identifies the given certificate (hashes are computed using this identifier hash algorithm).
+    pub fn matches(&self, cert: &Cert, issuer: &Cert) -> Result<bool, OcspError> {
+        let hash_algorithm =
+            HashAlgorithm::from_oid(self.hash_algorithm.oid()).ok_or_else(|| OcspError::UnsupportedHashAlgorithm {
+                algorithm: Into::<String>::into(self.hash_algorithm.oid()),
+            })?;
+        let expected = Self::new(cert, issuer, hash_algorithm)?;
+
+        Ok(self.issuer_name_hash == expected.issuer_name_hash
+            && self.issuer_key_hash == expected.issuer_key_hash
+            && self.serial_number.as_bytes_be() == expected.serial_number.as_bytes_be())
+    }
+}
+
+fn find_nonce(extensions: &[Extension]) -> Option<&[u8]> {
+    extensions.iter().find_map(|extension| match extension.extn_value() {
+        ExtensionView::OcspNonce(nonce) => Some(nonce.0.as_slice()),
+        _ => None,
+    })
+}
+
+/// OCSP request
+///
+/// https://tools.ietf.org/html/rfc6960#section-4.1
+#[derive(Clone, Debug, PartialEq)]
+pub struct OcspRequest(OCSPRequest);
+
+impl OcspRequest {
+    /// Unsigned request for the status of the given certificates.
+    pub fn new(cert_ids: Vec<CertId>) -> Self {
+        Self(OCSPRequest {
+            tbs_request: TBSRequest {
+                requestor_name: None,
+                request_list: cert_ids
+                    .into_iter()
+                    .map(|req_cert| Request {
+                        req_cert,
+                        single_request_extensions: None,
+                    })
+                    .collect::<Vec<_>>()
+                    .into(),
+                request_extensions: None,
+            },
+            optional_signature: None,
+        })
+    }
+
+    /// Adds a nonce to the request (https://tools.ietf.org/html/rfc8954).
+    ///
+    /// Responders supporting it will echo the nonce, preventing replay attacks.
+    pub fn with_nonce<V: Into<Vec<u8>>>(mut self, nonce: V) -> Self {
+        let extension = Extension::new_ocsp_nonce(nonce);
+        let extensions = &mut self.0.tbs_request.request_extensions;
+        match extensions {
+            Some(extensions) => {
+                (extensions.0).0.retain(|ext| ext.extn_id() != &oids::ocsp_nonce());
+                (extensions.0).0.push(extension);
+            }
+            None => *extensions = Some(Extensions(vec![extension]).into()),
+        }
+        self
+    }
+
+    pub fn from_der<T: ?Sized + AsRef<[u8]>>(der: &T) -> Result<Self, OcspError> {
+        Ok(Self(picky_asn1_der::from_bytes(der.as_ref()).context(
+            Asn1Deserialization {
+                element: "OCSP request",
+            },
+        )?))
+    }
+
+    pub fn to_der(&self) -> Result<Vec<u8>, OcspError> {
+        picky_asn1_der::to_vec(&self.0).context(Asn1Serialization {
+            element: "OCSP request",
+        })
+    }
+
+    pub fn cert_ids(&self) -> impl Iterator<Item = &CertId> {
+        (self.0.tbs_request.request_list)
+            .0
+            .iter()
+            .map(|request| &request.req_cert)
+    }
+
+    pub fn extensions(&self) -> &[Extension] {
+        match &self.0.tbs_request.request_extensions {
+            Some(extensions) => (extensions.0).0.as_slice(),
+            None => &[],
+        }
+    }
+
+    pub fn nonce(&self) -> Option<&[u8]> {
+        find_nonce(self.extensions())
+    }
+}
+
+/// https://tools.ietf.org/html/rfc6960#section-4.2.1
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u8)]
+pub enum OcspResponseStatus {
+    Successful = 0,
+    MalformedRequest = 1,
+    InternalError = 2,
+    TryLater = 3,
+    SigRequired = 5,
+    Unauthorized = 6,
+}
+
+impl OcspResponseStatus {
+    pub fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            0 => Some(Self::Successful),
+            1 => Some(Self::MalformedRequest),
+            2 => Some(Self::InternalError),
+            3 => Some(Self::TryLater),
+            5 => Some(Self::SigRequired),
+            6 => Some(Self::Unauthorized),
+            _ => None,
+        }
+    }
+
+    fn from_enumerated(value: &EnumeratedAsn1) -> Option<Self> {
+        match value.0.as_slice() {
+            [v] => Self::from_u8(*v),
+            _ => None,
+        }
+    }
+
+    fn to_enumerated(self) -> EnumeratedAsn1 {
+        vec![self as u8].into()
+    }
+}
+
+/// OCSP response
+///
+/// Only basic responses (`id-pkix-ocsp-basic`) are supported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OcspResponse(OCSPResponse);
+
+impl OcspResponse {
+    /// Successful response wrapping the given basic response.
+    pub fn successful(basic_response: &BasicOcspResponse) -> Result<Self, OcspError> {
+        Ok(Self(OCSPResponse {
+            response_status: OcspResponseStatus::Successful.to_enumerated(),
+            response_bytes: Some(ApplicationTag0(ResponseBytes {
+                response_type: oids::ocsp_basic().into(),
+                response: basic_response.to_der()?.into(),
+            })),
+        }))
+    }
+
+    /// Error response (no response bytes).
+    pub fn error(status: OcspResponseStatus) -> Self {
+        Self(OCSPResponse {
+            response_status: status.to_enumerated(),
+            response_bytes: None,
+        })
+    }
+
+    pub fn from_der<T: ?Sized + AsRef<[u8]>>(der: &T) -> Result<Self, OcspError> {
+        let response: OCSPResponse = picky_asn1_der::from_bytes(der.as_ref()).context(Asn1Deserialization {
+            element: "OCSP response",
+        })?;
+
+        if OcspResponseStatus::from_enumerated(&response.response_status).is_none() {
+            return Err(OcspError::InvalidEncoding {
+                element: "OCSP response status",
+            });
+        }
+
+        Ok(Self(response))
+    }
+
+    pub fn to_der(&self) -> Result<Vec<u8>, OcspError> {
+        picky_asn1_der::to_vec(&self.0).context(Asn1Serialization {
+            element: "OCSP response",
+        })
+    }
+
+    pub fn status(&self) -> OcspResponseStatus {
+        OcspResponseStatus::from_enumerated(&self.0.response_status).expect("checked at construction")
+    }
+
+    /// Decodes the basic response of a successful OCSP response.
+    pub fn basic_response(&self) -> Result<BasicOcspResponse, OcspError> {
+        let status = self.status();
+        if status != OcspResponseStatus::Successful {
+            return Err(OcspError::UnsuccessfulResponse { status });
+        }
+
+        let response_bytes = &self
+            .0
+            .response_bytes
+            .as_ref()
+            .ok_or(OcspError::InvalidEncoding {
+                element: "OCSP response bytes",
+            })?
+            .0;
+
+        if response_bytes.response_type != oids::ocsp_basic() {
+            return Err(OcspError::UnsupportedResponseType {
+                response_type: Into::<String>::into(&response_bytes.response_type.0),
+            });
+        }
+
+        BasicOcspResponse::from_der(&response_bytes.response.0)
+    }
+}
+
+/// https://tools.ietf.org/html/rfc6960#section-4.2.1
+#[derive(Debug, PartialEq, Clone)]
+pub enum ResponderId {
+    ByName(DirectoryName),
+    /// SHA-1 hash of the responder's public key
+    ByKey(Vec<u8>),
+}
+
+// ResponderID ::= CHOICE {
+//      byName   [1] Name,
+//      byKey    [2] KeyHash }
+//
+// KeyHash ::= OCTET STRING
+
+impl ResponderId {
+    /// Whether this identifies the given certificate.
+    pub fn matches(&self, cert: &Cert) -> Result<bool, OcspError> {
+        match self {
+            ResponderId::ByName(name) => Ok(*name == cert.subject_name()),
+            ResponderId::ByKey(key_hash) => Ok(*key_hash == public_key_hash(cert.public_key(), HashAlgorithm::Sha1)?),
+        }
+    }
+}
+
+impl ser::Serialize for ResponderId {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            ResponderId::ByName(name) => ApplicationTag1(Name::from(name.clone())).serialize(serializer),
+            ResponderId::ByKey(key_hash) => ApplicationTag2(OctetStringAsn1(key_hash.clone())).serialize(serializer),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for ResponderId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = ResponderId;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded ResponderID")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let tag_peeker: TagPeeker = seq_next_element!(seq, ResponderId, "choice tag");
+                match tag_peeker.next_tag {
+                    Tag::APP_1 => Ok(ResponderId::ByName(
+                        seq_next_element!(seq, ApplicationTag1<Name>, ResponderId, "ByName")
+                            .0
+                            .into(),
+                    )),
+                    Tag::APP_2 => Ok(ResponderId::ByKey(
+                        seq_next_element!(seq, ApplicationTag2<OctetStringAsn1>, ResponderId, "ByKey")
+                            .0
+                             .0,
+                    )),
+                    _ => Err(serde_invalid_value!(
+                        ResponderId,
+                        "unknown choice value",
+                        "a supported ResponderID choice"
+                    )),
+                }
+            }
+        }
+
+        deserializer.deserialize_enum("ResponderId", &["ByName", "ByKey"], Visitor)
+    }
+}
+
+impl fmt::Display for ResponderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponderId::ByName(name) => write!(f, "by name: {}", name),
+            ResponderId::ByKey(key_hash) => {
+                write!(f, "by key: ")?;
+                for byte in key_hash {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// https://tools.ietf.org/html/rfc6960#section-4.2.1
+#[derive(Debug, PartialEq, Clone)]
+pub enum CertStatus {
+    Good,
+    Revoked {
+        revocation_time: UTCDate,
+        reason: Option<CRLReason>,
+    },
+    Unknown,
+}
+
+// CertStatus ::= CHOICE {
+//      good        [0]     IMPLICIT NULL,
+//      revoked     [1]     IMPLICIT RevokedInfo,
+//      unknown     [2]     IMPLICIT UnknownInfo }
+//
+// UnknownInfo ::= NULL
+
+impl ser::Serialize for CertStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            CertStatus::Good => ContextTag0(()).serialize(serializer),
+            CertStatus::Revoked {
+                revocation_time,
+                reason,
+            } => ImplicitContextTag1(RevokedInfo {
+                revocation_time: generalized_time(revocation_time),
+                revocation_reason: reason.map(|reason| ApplicationTag0(reason.to_enumerated())),
+            })
+            .serialize(serializer),
+            CertStatus::Unknown => ContextTag2(()).serialize(serializer),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for CertStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = CertStatus;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid DER-encoded CertStatus")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let tag_peeker: TagPeeker = seq_next_element!(seq, CertStatus, "choice tag");
+                match tag_peeker.next_tag {
+                    Tag::CTX_0 => {
+                        let _: ContextTag0<()> = seq_next_element!(seq, CertStatus, "Good");
+                        Ok(CertStatus::Good)
+                    }
+                    Tag::APP_1 => {
+                        let revoked_info: ImplicitContextTag1<RevokedInfo> =
+                            seq_next_element!(seq, CertStatus, "Revoked");
+                        let reason = match revoked_info.0.revocation_reason {
+                            Some(reason) => Some(CRLReason::from_enumerated(&reason.0).ok_or_else(|| {
+                                serde_invalid_value!(CertStatus, "unknown revocation reason", "a valid CRLReason")
+                            })?),
+                            None => None,
+                        };
+                        Ok(CertStatus::Revoked {
+                            revocation_time: revoked_info.0.revocation_time.0.into(),
+                            reason,
+                        })
+                    }
+                    Tag::CTX_2 => {
+                        let _: ContextTag2<()> = seq_next_element!(seq, CertStatus, "Unknown");
+                        Ok(CertStatus::Unknown)
+                    }
+                    _ => Err(serde_invalid_value!(
+                        CertStatus,
+                        "unknown choice value",
+                        "a supported CertStatus choice"
+                    )),
+                }
+            }
+        }
+
+        deserializer.deserialize_enum("CertStatus", &["Good", "Revoked", "Unknown"], Visitor)
+    }
+}
+
+fn generalized_time(date: &UTCDate) -> GeneralizedTimeAsn1 {
+    let time: GeneralizedTime = date.clone().into();
+    time.into()
+}
+
+/// Status of a single certificate
+///
+/// https://tools.ietf.org/html/rfc6960#section-4.2.1
+#[derive(Debug, PartialEq, Clone)]
+pub struct SingleResponse {
+    cert_id: CertId,
+    cert_status: CertStatus,
+    this_update: UTCDate,
+    next_update: Option<UTCDate>,
+    single_extensions: Option<Extensions>,
+}
+
+// SingleResponse ::= SEQUENCE {
+//      certID                       CertID,
+//      certStatus                   CertStatus,
+//      thisUpdate                   GeneralizedTime,
+//      nextUpdate         [0]       EXPLICIT GeneralizedTime OPTIONAL,
+//      singleExtensions   [1]       EXPLICIT Extensions OPTIONAL }
+
+impl SingleResponse {
+    pub fn new(cert_id: CertId, cert_status: CertStatus, this_update: UTCDate, next_update: Option<UTCDate>) -> Self {
+        Self {
+            cert_id,
+            cert_status,
+            this_update,
+            next_update,
+            single_extensions: None,
+        }
+    }
+
+    pub fn cert_id(&self) -> &CertId {
+        &self.cert_id
+    }
+
+    pub fn cert_status(&self) -> &CertStatus {
+        &self.cert_status
+    }
+
+    pub fn this_update(&self) -> &UTCDate {
+        &self.this_update
+    }
+
+    pub fn next_update(&self) -> Option<&UTCDate> {
+        self.next_update.as_ref()
+    }
+
+    pub fn extensions(&self) -> &[Extension] {
+        match &self.single_extensions {
+            Some(extensions) => extensions.0.as_slice(),
+            None => &[],
+        }
+    }
+}
+
+impl ser::Serialize for SingleResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as ser::Serializer>::Ok, <S as ser::Serializer>::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element(&self.cert_id)?;
+        seq.serialize_element(&self.cert_status)?;
+        seq.serialize_element(&generalized_time(&self.this_update))?;
+        if let Some(next_update) = &self.next_update {
+            seq.serialize_element(&ApplicationTag0(generalized_time(next_update)))?;
+        }
+        if let Some(extensions) = &self.single_extensions {
+            seq.serialize_element(&ApplicationTag1(extensions))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for SingleResponse {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as de::Deserializer<'de>>::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = SingleResponse;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct SingleResponse")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let cert_id = seq_next_element!(seq, SingleResponse, "cert id");
+                let cert_status = seq_next_element!(seq, SingleResponse, "cert status");
+                let this_update: GeneralizedTimeAsn1 = seq_next_element!(seq, SingleResponse, "this update");
+
+                let next_update = if let Some(TagPeeker { next_tag: Tag::APP_0 }) = seq.next_element()? {
+                    let next_update: ApplicationTag0<GeneralizedTimeAsn1> =
+                        seq_next_element!(seq, SingleResponse, "next update");
+                    Some((next_update.0).0.into())
+                } else {
+                    None
+                };
+
+                let single_extensions: Option<ApplicationTag1<Extensions>> = seq.next_element()?;
+
+                Ok(SingleResponse {
+                    cert_id,
+                    cert_status,
+                    this_update: this_update.0.into(),
+                    next_update,
+                    single_extensions: single_extensions.map(|extensions| extensions.0),
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+/// Basic OCSP response
+///
+/// https://tools.ietf.org/html/rfc6960#section-4.2.1
+#[derive(Clone, Debug, PartialEq)]
+pub struct BasicOcspResponse {
+    basic_response: BasicOCSPResponse,
+    // decoded from `basic_response.tbs_response_data` whose original encoding is kept for signature verification
+    response_data: ResponseData,
+}
+
+impl BasicOcspResponse {
+    pub fn from_der<T: ?Sized + AsRef<[u8]>>(der: &T) -> Result<Self, OcspError> {
+        let basic_response: BasicOCSPResponse =
+            picky_asn1_der::from_bytes(der.as_ref()).context(Asn1Deserialization {
+                element: "basic OCSP response",
+            })?;
+        let response_data =
+            picky_asn1_der::from_bytes(&basic_response.tbs_response_data.0).context(Asn1Deserialization {
+                element: "OCSP response data",
+            })?;
+
+        Ok(Self {
+            basic_response,
+            response_data,
+        })
+    }
+
+    pub fn to_der(&self) -> Result<Vec<u8>, OcspError> {
+        picky_asn1_der::to_vec(&self.basic_response).context(Asn1Serialization {
+            element: "basic OCSP response",
+        })
+    }
+
+    pub fn responder_id(&self) -> &ResponderId {
+        &self.response_data.responder_id
+    }
+
+    pub fn produced_at(&self) -> UTCDate {
+        self.response_data.produced_at.0.clone().into()
+    }
+
+    pub fn responses(&self) -> &[SingleResponse] {
+        &self.response_data.responses.0
+    }
+
+    /// Finds the response matching the given certificate identifier.
+    pub fn find_response(&self, cert_id: &CertId) -> Option<&SingleResponse> {
+        self.responses().iter().find(|response| {
+            let response_id = response.cert_id();
+            response_id.hash_algorithm.oid() == cert_id.hash_algorithm.oid()
+                && response_id.issuer_name_hash == cert_id.issuer_name_hash
+                && response_id.issuer_key_hash == cert_id.issuer_key_hash
+                && response_id.serial_number.as_bytes_be() == cert_id.serial_number.as_bytes_be()
+        })
+    }
+
+    pub fn extensions(&self) -> &[Extension] {
+        match &self.response_data.response_extensions {
+            Some(extensions) => (extensions.0).0.as_slice(),
+            None => &[],
+        }
+    }
+
+    pub fn nonce(&self) -> Option<&[u8]> {
+        find_nonce(self.extensions())
+    }
+
+    pub fn signature_algorithm(&self) -> &AlgorithmIdentifier {
+        &self.basic_response.signature_algorithm
+    }
+
+    /// Certificates included by the responder (e.g. a delegated responder certificate).
+    pub fn certs(&self) -> Vec<Cert> {
+        self.basic_response
+            .certs
+            .iter()
+            .flat_map(|certs| (certs.0).0.iter())
+            .cloned()
+            .map(Cert::from)
+            .collect()
+    }
+
+    /// Checks the response has been signed by the given issuer, or by a responder the issuer
+    /// delegated OCSP signing to (https://tools.ietf.org/html/rfc6960#section-4.2.2.2).
+    ///
+    /// A delegated responder certificate must be included in the response, be directly issued by the
+    /// issuer, include `id-kp-OCSPSigning` in its extended key usage and be valid when the
+    /// response was produced.
+    pub fn verify(&self, issuer: &Cert) -> Result<(), OcspError> {
+        let responder_id = self.responder_id();
+
+        let responder = if responder_id.matches(issuer)? {
+            issuer.clone()
+        } else {
+            let mut responder = None;
+            for cert in self.certs() {
+                if responder_id.matches(&cert)? {
+                    responder = Some(cert);
+                    break;
+                }
+            }
+            let responder = responder.ok_or_else(|| OcspError::ResponderNotFound {
+                responder_id: responder_id.to_string(),
+            })?;
+
+            let not_authorized = |reason| OcspError::ResponderNotAuthorized {
+                responder_id: responder.subject_name().to_string(),
+                issuer_id: issuer.subject_name().to_string(),
+                reason,
+            };
+
+            if responder.issuer_name() != issuer.subject_name()
+                || responder.verify_signature(issuer.public_key()).is_err()
+            {
+                return Err(not_authorized("not issued by the issuer"));
+            }
+
+            match responder.extended_key_usage() {
+                Ok(eku) if eku.contains(oids::kp_ocsp_signing()) => {}
+                _ => return Err(not_authorized("missing OCSP signing extended key usage")),
+            }
+
+            responder.verify(&self.produced_at()).context(InvalidCertificate)?;
+
+            responder
+        };
+
+        let hash_type = SignatureHashType::from_algorithm_identifier(&self.basic_response.signature_algorithm)
+            .context(Signature)?;
+        let msg = &self.basic_response.tbs_response_data.0;
+        hash_type
+            .verify(
+                responder.public_key(),
+                msg,
+                self.basic_response.signature.0.payload_view(),
+            )
+            .context(Signature)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct ResponderInfos<'a> {
+    cert: Cert,
+    key: &'a dyn Signer,
+}
+
+impl fmt::Debug for ResponderInfos<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponderInfos")
+            .field("cert", &self.cert)
+            .field("key", &"<signer>")
+            .finish()
+    }
+}
+
+// Statically checks the field actually exists and returns a &'static str of the field name
+macro_rules! field_str {
+    ($field:ident) => {{
+        ::static_assertions::assert_fields!(BasicOcspResponseBuilderInner: $field);
+        stringify!($field)
+    }};
+}
+
+#[derive(Default, Clone, Debug)]
+struct BasicOcspResponseBuilderInner<'a> {
+    produced_at: Option<UTCDate>,
+    responder_infos: Option<ResponderInfos<'a>>,
+    signature_hash_type: Option<SignatureHashType>,
+    responses: Vec<SingleResponse>,
+    nonce: Option<Vec<u8>>,
+    certs: Vec<Cert>,
+}
+
+/// Responder-side builder for basic OCSP responses
+#[derive(Default, Clone, Debug)]
+pub struct BasicOcspResponseBuilder<'a> {
+    inner: RefCell<BasicOcspResponseBuilderInner<'a>>,
+}
+
+impl<'a> BasicOcspResponseBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Required
+    #[inline]
+    pub fn produced_at(&self, produced_at: UTCDate) -> &Self {
+        self.inner.borrow_mut().produced_at = Some(produced_at);
+        self
+    }
+
+    /// Required
+    ///
+    /// Either the issuer certificate or a delegated responder certificate (which should then be
+    /// added with `cert`). The responder is identified by key hash.
+    #[inline]
+    pub fn responder(&self, responder_cert: &Cert, responder_key: &'a dyn Signer) -> &Self {
+        self.inner.borrow_mut().responder_infos = Some(ResponderInfos {
+            cert: responder_cert.clone(),
+            key: responder_key,
+        });
+        self
+    }
+
+    /// Optional
+    #[inline]
+    pub fn signature_hash_type(&self, signature_hash_type: SignatureHashType) -> &Self {
+        self.inner.borrow_mut().signature_hash_type = Some(signature_hash_type);
+        self
+    }
+
+    /// Optional, may be called multiple times
+    #[inline]
+    pub fn response(&self, response: SingleResponse) -> &Self {
+        self.inner.borrow_mut().responses.push(response);
+        self
+    }
+
+    /// Optional (echo the request nonce)
+    #[inline]
+    pub fn nonce<V: Into<Vec<u8>>>(&self, nonce: V) -> &Self {
+        self.inner.borrow_mut().nonce = Some(nonce.into());
+        self
+    }
+
+    /// Optional, may be called multiple times
+    #[inline]
+    pub fn cert(&self, cert: Cert) -> &Self {
+        self.inner.borrow_mut().certs.push(cert);
+        self
+    }
+
+    pub fn build(&self) -> Result<BasicOcspResponse, OcspError> {
+        let mut inner = self.inner.borrow_mut();
+
+        let produced_at = inner.produced_at.take().ok_or(OcspError::MissingBuilderArgument {
+            arg: field_str!(produced_at),
+        })?;
+        let responder_infos = inner.responder_infos.take().ok_or(OcspError::MissingBuilderArgument {
+            arg: field_str!(responder_infos),
+        })?;
+        let signature_hash_type = inner
+            .signature_hash_type
+            .take()
+            .unwrap_or_else(|| responder_infos.key.signature_hash_type());
+        let responses = std::mem::take(&mut inner.responses);
+        let nonce_opt = inner.nonce.take();
+        let certs = std::mem::take(&mut inner.certs);
+
+        drop(inner);
+
+        let responder_key_hash =
+            public_key_hash(responder_infos.cert.public_key(), HashAlgorithm::Sha1).context(ResponseGeneration)?;
+
+        let response_data = ResponseData {
+            responder_id: ResponderId::ByKey(responder_key_hash),
+            produced_at: generalized_time(&produced_at),
+            responses: responses.into(),
+            response_extensions: nonce_opt
+                .map(|nonce| ApplicationTag1(Extensions(vec![Extension::new_ocsp_nonce(nonce)]))),
+        };
+
+        let tbs_der = picky_asn1_der::to_vec(&response_data)
+            .context(Asn1Serialization {
+                element: "OCSP response data",
+            })
+            .context(ResponseGeneration)?;
+        let signature = BitString::with_bytes(
+            responder_infos
+                .key
+                .sign(&tbs_der, signature_hash_type)
+                .context(Signature)
+                .context(ResponseGeneration)?,
+        );
+
+        Ok(BasicOcspResponse {
+            basic_response: BasicOCSPResponse {
+                tbs_response_data: RawDerAsn1(tbs_der),
+                signature_algorithm: signature_hash_type.into(),
+                signature: signature.into(),
+                certs: if certs.is_empty() {
+                    None
+                } else {
+                    Some(ApplicationTag0(
+                        certs.into_iter().map(Certificate::from).collect::<Vec<_>>().into(),
+                    ))
+                },
+            },
+            response_data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        key::PrivateKey,
+        pem::parse_pem,
+        x509::{certificate::CertificateBuilder, extension::ExtendedKeyUsage},
+    };
+
+    fn parse_cert(pem_str: &str) -> Cert {
+        Cert::from_pem(&parse_pem(pem_str).unwrap()).unwrap()
+    }
+
+    fn parse_key(pem_str: &str) -> PrivateKey {
+        PrivateKey::from_pem(&parse_pem(pem_str).unwrap()).unwrap()
+    }
+
+    const NONCE: [u8; 16] = [
+        0x63, 0xFE, 0xBA, 0x13, 0x66, 0x2E, 0x4C, 0x9C, 0x92, 0xF0, 0xE3, 0xB0, 0x60, 0x8B, 0xB5, 0x6C,
+    ];
+
+    #[test]
+    fn openssl_request() {
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let revoked_leaf = parse_cert(crate::test_files::CRL_REVOKED_LEAF);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+
+        let request = OcspRequest::from_der(crate::test_files::OCSP_REQUEST).expect("ocsp request");
+        assert_eq!(request.to_der().unwrap(), crate::test_files::OCSP_REQUEST);
+        assert_eq!(request.nonce(), Some(&NONCE[..]));
+
+        let cert_ids: Vec<&CertId> = request.cert_ids().collect();
+        assert_eq!(cert_ids.len(), 2);
+        assert!(cert_ids[0].matches(&revoked_leaf, &ca).unwrap());
+        assert!(!cert_ids[0].matches(&valid_leaf, &ca).unwrap());
+        assert!(cert_ids[1].matches(&valid_leaf, &ca).unwrap());
+        assert_eq!(
+            cert_ids[0].issuer_key_hash(),
+            ca.subject_key_identifier().unwrap(),
+            "the test CA uses the RFC 5280 method (1) for its key identifier"
+        );
+
+        let generated = OcspRequest::new(vec![
+            CertId::new(&revoked_leaf, &ca, HashAlgorithm::Sha1).unwrap(),
+            CertId::new(&valid_leaf, &ca, HashAlgorithm::Sha1).unwrap(),
+        ])
+        .with_nonce(NONCE.to_vec());
+        assert_eq!(generated, request);
+        assert_eq!(generated.to_der().unwrap(), crate::test_files::OCSP_REQUEST);
+    }
+
+    #[test]
+    fn openssl_response() {
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let revoked_leaf = parse_cert(crate::test_files::CRL_REVOKED_LEAF);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+
+        let response = OcspResponse::from_der(crate::test_files::OCSP_RESPONSE).expect("ocsp response");
+        assert_eq!(response.status(), OcspResponseStatus::Successful);
+        assert_eq!(response.to_der().unwrap(), crate::test_files::OCSP_RESPONSE);
+
+        let basic_response = response.basic_response().expect("basic response");
+        basic_response.verify(&ca).expect("verify response");
+
+        assert_eq!(basic_response.responder_id(), &ResponderId::ByName(ca.subject_name()));
+        assert_eq!(
+            basic_response.produced_at(),
+            UTCDate::new(2026, 10, 18, 13, 6, 27).unwrap()
+        );
+        assert_eq!(basic_response.nonce(), Some(&NONCE[..]));
+        assert_eq!(basic_response.certs(), vec![ca.clone()]);
+
+        let revoked = basic_response
+            .find_response(&CertId::new(&revoked_leaf, &ca, HashAlgorithm::Sha1).unwrap())
+            .expect("revoked leaf response");
+        assert_eq!(
+            revoked.cert_status(),
+            &CertStatus::Revoked {
+                revocation_time: UTCDate::new(2026, 10, 18, 12, 58, 7).unwrap(),
+                reason: Some(CRLReason::KeyCompromise),
+            }
+        );
+        assert_eq!(revoked.this_update(), &basic_response.produced_at());
+        assert_eq!(
+            revoked.next_update(),
+            Some(&UTCDate::new(2026, 11, 17, 13, 6, 27).unwrap())
+        );
+
+        let valid = basic_response
+            .find_response(&CertId::new(&valid_leaf, &ca, HashAlgorithm::Sha1).unwrap())
+            .expect("valid leaf response");
+        assert_eq!(valid.cert_status(), &CertStatus::Good);
+
+        assert!(basic_response
+            .find_response(&CertId::new(&valid_leaf, &ca, HashAlgorithm::Sha256).unwrap())
+            .is_none());
+
+        let err = basic_response.verify(&revoked_leaf).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "responder 'CN=CRL Test CA' is not authorized to sign OCSP responses for 'CN=leaf1': not issued by the \
+             issuer"
+        );
+    }
+
+    #[test]
+    fn error_response() {
+        let response = OcspResponse::error(OcspResponseStatus::TryLater);
+        let der = response.to_der().unwrap();
+        assert_eq!(der, [0x30, 0x03, 0x0A, 0x01, 0x03]);
+
+        let response = OcspResponse::from_der(&der).unwrap();
+        assert_eq!(response.status(), OcspResponseStatus::TryLater);
+        let err = response.basic_response().err().unwrap();
+        assert_eq!(err.to_string(), "OCSP response is not successful: TryLater");
+
+        assert!(OcspResponse::from_der(&[0x30, 0x03, 0x0A, 0x01, 0x04]).is_err());
+    }
+
+    #[test]
+    fn response_generation() {
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let ca_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let revoked_leaf = parse_cert(crate::test_files::CRL_REVOKED_LEAF);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+
+        let revoked_id = CertId::new(&revoked_leaf, &ca, HashAlgorithm::Sha256).unwrap();
+        let valid_id = CertId::new(&valid_leaf, &ca, HashAlgorithm::Sha256).unwrap();
+        let request = OcspRequest::new(vec![revoked_id.clone(), valid_id.clone()]).with_nonce(NONCE.to_vec());
+        let request = OcspRequest::from_der(&request.to_der().unwrap()).unwrap();
+
+        let now = UTCDate::ymd(2027, 1, 1).unwrap();
+        let builder = BasicOcspResponseBuilder::new();
+        builder.produced_at(now.clone()).responder(&ca, &ca_key);
+        for cert_id in request.cert_ids() {
+            let cert_status = if cert_id == &revoked_id {
+                CertStatus::Revoked {
+                    revocation_time: UTCDate::ymd(2026, 12, 24).unwrap(),
+                    reason: None,
+                }
+            } else {
+                CertStatus::Good
+            };
+            builder.response(SingleResponse::new(cert_id.clone(), cert_status, now.clone(), None));
+        }
+        if let Some(nonce) = request.nonce() {
+            builder.nonce(nonce);
+        }
+        let basic_response = builder.build().expect("couldn't build basic response");
+
+        let response = OcspResponse::successful(&basic_response).unwrap();
+        let response = OcspResponse::from_der(&response.to_der().unwrap()).unwrap();
+        let basic_response = response.basic_response().unwrap();
+        assert_eq!(
+            basic_response.responder_id(),
+            &ResponderId::ByKey(ca.subject_key_identifier().unwrap().to_vec())
+        );
+        assert_eq!(basic_response.nonce(), request.nonce());
+        assert!(basic_response.certs().is_empty());
+        basic_response.verify(&ca).expect("verify response");
+
+        let revoked = basic_response.find_response(&revoked_id).unwrap();
+        assert!(revoked.cert_id().matches(&revoked_leaf, &ca).unwrap());
+        assert_eq!(
+            revoked.cert_status(),
+            &CertStatus::Revoked {
+                revocation_time: UTCDate::ymd(2026, 12, 24).unwrap(),
+                reason: None,
+            }
+        );
+        assert_eq!(revoked.next_update(), None);
+        assert_eq!(
+            basic_response.find_response(&valid_id).unwrap().cert_status(),
+            &CertStatus::Good
+        );
+
+        // signed with another key
+        let other_key = parse_key(crate::test_files::EC_P256_PK_2);
+        let forged = BasicOcspResponseBuilder::new()
+            .produced_at(now)
+            .responder(&ca, &other_key)
+            .build()
+            .unwrap();
+        let err = forged.verify(&ca).err().unwrap();
+        assert_eq!(err.to_string(), "signature error: invalid signature");
+    }
+
+    #[test]
+    fn signature_over_received_response_data() {
+        // Explicitly encoded DEFAULT version: not DER, but emitted by some responders
+        #[derive(Serialize)]
+        struct VersionedResponseData {
+            version: ApplicationTag0<IntegerAsn1>,
+            responder_id: ResponderId,
+            produced_at: GeneralizedTimeAsn1,
+            responses: Vec<SingleResponse>,
+        }
+
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let ca_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+
+        let now = UTCDate::ymd(2027, 1, 1).unwrap();
+        let cert_id = CertId::new(&valid_leaf, &ca, HashAlgorithm::Sha256).unwrap();
+        let tbs_der = picky_asn1_der::to_vec(&VersionedResponseData {
+            version: ApplicationTag0(vec![0].into()),
+            responder_id: ResponderId::ByKey(public_key_hash(ca.public_key(), HashAlgorithm::Sha1).unwrap()),
+            produced_at: generalized_time(&now),
+            responses: vec![SingleResponse::new(cert_id.clone(), CertStatus::Good, now, None)],
+        })
+        .unwrap();
+        let signature_hash_type = ca_key.signature_hash_type();
+        let signature = ca_key.sign(&tbs_der, signature_hash_type).unwrap();
+        let der = picky_asn1_der::to_vec(&BasicOCSPResponse {
+            tbs_response_data: RawDerAsn1(tbs_der),
+            signature_algorithm: signature_hash_type.into(),
+            signature: BitString::with_bytes(signature).into(),
+            certs: None,
+        })
+        .unwrap();
+
+        let basic_response = BasicOcspResponse::from_der(&der).expect("basic response");
+        assert_eq!(
+            basic_response.find_response(&cert_id).unwrap().cert_status(),
+            &CertStatus::Good
+        );
+        basic_response.verify(&ca).expect("verify response");
+        assert_eq!(basic_response.to_der().unwrap(), der);
+    }
+
+    #[test]
+    fn delegated_responder() {
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let ca_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let responder_key = parse_key(crate::test_files::EC_P256_PK_2);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+
+        let build_responder_cert = |eku: Option<ExtendedKeyUsage>| {
+            let builder = CertificateBuilder::new();
+            builder
+                .valididy(UTCDate::ymd(2026, 1, 1).unwrap(), UTCDate::ymd(2028, 1, 1).unwrap())
+                .subject(
+                    DirectoryName::new_common_name("OCSP Responder"),
+                    responder_key.to_public_key(),
+                )
+                .issuer_cert(&ca, &ca_key);
+            if let Some(eku) = eku {
+                builder.extended_key_usage(eku);
+            }
+            builder.build().expect("couldn't build responder certificate")
+        };
+
+        let build_response = |responder_cert: &Cert, include_cert: bool| {
+            let builder = BasicOcspResponseBuilder::new();
+            builder
+                .produced_at(UTCDate::ymd(2027, 1, 1).unwrap())
+                .responder(responder_cert, &responder_key)
+                .response(SingleResponse::new(
+                    CertId::new(&valid_leaf, &ca, HashAlgorithm::Sha1).unwrap(),
+                    CertStatus::Good,
+                    UTCDate::ymd(2027, 1, 1).unwrap(),
+                    UTCDate::ymd(2027, 1, 2),
+                ));
+            if include_cert {
+                builder.cert(responder_cert.clone());
+            }
+            builder.build().expect("couldn't build basic response")
+        };
+
+        let responder_cert = build_responder_cert(Some(ExtendedKeyUsage::new(vec![oids::kp_ocsp_signing()])));
+        let response = build_response(&responder_cert, true);
+        let response = BasicOcspResponse::from_der(&response.to_der().unwrap()).unwrap();
+        assert_eq!(response.certs(), vec![responder_cert.clone()]);
+        response.verify(&ca).expect("verify delegated response");
+
+        let err = build_response(&responder_cert, false).verify(&ca).err().unwrap();
+        assert!(err.to_string().starts_with("responder certificate not found (by key: "));
+
+        let not_delegated_cert = build_responder_cert(Some(ExtendedKeyUsage::new(vec![oids::kp_server_auth()])));
+        let err = build_response(&not_delegated_cert, true).verify(&ca).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "responder 'CN=OCSP Responder' is not authorized to sign OCSP responses for 'CN=CRL Test CA': missing \
+             OCSP signing extended key usage"
+        );
+
+        let err = build_response(&build_responder_cert(None), true)
+            .verify(&ca)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "responder 'CN=OCSP Responder' is not authorized to sign OCSP responses for 'CN=CRL Test CA': missing \
+             OCSP signing extended key usage"
+        );
+
+        // responder certificate issued by another CA
+        let other_ca = parse_cert(crate::test_files::ROOT_CA);
+        let err = response.verify(&other_ca).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "responder 'CN=OCSP Responder' is not authorized to sign OCSP responses for '{}': not issued by the \
+                 issuer",
+                other_ca.subject_name()
+            )
+        );
+    }
+}
@@ -0,0 +1,780 @@
+//! Certificate policies processing of a certification path.
+//!
+//! https://tools.ietf.org/html/rfc5280#section-6.1
+
+use crate::{
+    oids,
+    x509::{
+        certificate::{CaChainError, Cert, CertError},
+        extension::{CertificatePolicies, PolicyMappings},
+    },
+};
+use oid::ObjectIdentifier;
+
+/// Policy processing inputs (user-initial-policy-set, initial-explicit-policy,
+/// initial-policy-mapping-inhibit and initial-any-policy-inhibit).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicySettings {
+    acceptable_policies: Vec<ObjectIdentifier>,
+    require_explicit_policy: bool,
+    inhibit_policy_mapping: bool,
+    inhibit_any_policy: bool,
+}
+
+impl Default for PolicySettings {
+    /// Any policy is acceptable and none is required.
+    fn default() -> Self {
+        Self::new(vec![oids::any_policy()])
+    }
+}
+
+impl PolicySettings {
+    /// `oids::any_policy()` may be used to accept any policy.
+    pub fn new<I: IntoIterator<Item = ObjectIdentifier>>(acceptable_policies: I) -> Self {
+        Self {
+            acceptable_policies: acceptable_policies.into_iter().collect(),
+            require_explicit_policy: false,
+            inhibit_policy_mapping: false,
+            inhibit_any_policy: false,
+        }
+    }
+
+    /// Requires the path to be valid for at least one of the acceptable policies.
+    pub fn require_explicit_policy(mut self, require_explicit_policy: bool) -> Self {
+        self.require_explicit_policy = require_explicit_policy;
+        self
+    }
+
+    pub fn inhibit_policy_mapping(mut self, inhibit_policy_mapping: bool) -> Self {
+        self.inhibit_policy_mapping = inhibit_policy_mapping;
+        self
+    }
+
+    pub fn inhibit_any_policy(mut self, inhibit_any_policy: bool) -> Self {
+        self.inhibit_any_policy = inhibit_any_policy;
+        self
+    }
+
+    pub fn acceptable_policies(&self) -> &[ObjectIdentifier] {
+        &self.acceptable_policies
+    }
+
+    /// Processes the policies of a certification path ordered from the leaf to the trust anchor
+    /// (as returned by `ChainBuilder::build`). The trust anchor itself is not processed.
+    ///
+    /// Returns the policies the path is valid for, restricted to the acceptable ones and expressed
+    /// in the trust anchor's domain (before any policy mapping).
+    /// The result is empty if no policy is valid but explicit policy isn't required.
+    pub fn validate(&self, path: &[&Cert]) -> Result<Vec<ObjectIdentifier>, CertError> {
+        let certs: Vec<&Cert> = path.iter().rev().skip(1).copied().collect();
+        let n = certs.len();
+
+        if n == 0 {
+            return Ok(self.acceptable_policies.clone());
+        }
+
+        let initial_value = |inhibited: bool| if inhibited { 0 } else { n + 1 };
+        let mut explicit_policy = initial_value(self.require_explicit_policy);
+        let mut inhibit_any_policy = initial_value(self.inhibit_any_policy);
+        let mut policy_mapping = initial_value(self.inhibit_policy_mapping);
+
+        let mut tree = PolicyTree::new();
+
+        for (idx, cert) in certs.iter().enumerate() {
+            let depth = idx + 1;
+            let self_issued = cert.subject_name() == cert.issuer_name();
+
+            if !tree.is_null() {
+                match cert.certificate_policies() {
+                    Ok(policies) => {
+                        let any_policy_allowed = inhibit_any_policy > 0 || (depth < n && self_issued);
+                        tree.add_policies(depth, policies, any_policy_allowed)
+                            .map_err(|_| policy_tree_too_large(cert))?;
+                        tree.prune(depth);
+                    }
+                    Err(_) => tree.delete(0),
+                }
+            }
+
+            if explicit_policy == 0 && tree.is_null() {
+                return Err(no_valid_policy(cert));
+            }
+
+            if depth == n {
+                break;
+            }
+
+            // preparation for the next certificate
+            if let Ok(mappings) = cert.policy_mappings() {
+                let any_policy = oids::any_policy();
+                if mappings.iter().any(|mapping| {
+                    mapping.issuer_domain_policy() == &any_policy || mapping.subject_domain_policy() == &any_policy
+                }) {
+                    return Err(CertError::InvalidChain {
+                        source: CaChainError::AnyPolicyMapping {
+                            cert_id: cert.subject_name().to_string(),
+                        },
+                    });
+                }
+
+                if !tree.is_null() {
+                    tree.apply_mappings(depth, mappings, policy_mapping > 0)
+                        .map_err(|_| policy_tree_too_large(cert))?;
+                }
+            }
+
+            if !self_issued {
+                explicit_policy = explicit_policy.saturating_sub(1);
+                policy_mapping = policy_mapping.saturating_sub(1);
+                inhibit_any_policy = inhibit_any_policy.saturating_sub(1);
+            }
+
+            if let Ok(policy_constraints) = cert.policy_constraints() {
+                if let Some(skip_certs) = policy_constraints.require_explicit_policy() {
+                    explicit_policy = explicit_policy.min(skip_certs as usize);
+                }
+                if let Some(skip_certs) = policy_constraints.inhibit_policy_mapping() {
+                    policy_mapping = policy_mapping.min(skip_certs as usize);
+                }
+            }
+
+            if let Ok(inhibit) = cert.inhibit_any_policy() {
+                inhibit_any_policy = inhibit_any_policy.min(inhibit.skip_certs() as usize);
+            }
+        }
+
+        // wrap-up
+        let leaf = certs[n - 1];
+
+        explicit_policy = explicit_policy.saturating_sub(1);
+        if let Ok(policy_constraints) = leaf.policy_constraints() {
+            if policy_constraints.require_explicit_policy() == Some(0) {
+                explicit_policy = 0;
+            }
+        }
+
+        let valid_policies = tree
+            .intersect(n, &self.acceptable_policies)
+            .map_err(|_| policy_tree_too_large(leaf))?;
+        if explicit_policy == 0 && valid_policies.is_empty() {
+            return Err(no_valid_policy(leaf));
+        }
+
+        Ok(valid_policies)
+    }
+}
+
+fn policy_tree_too_large(cert: &Cert) -> CertError {
+    CertError::InvalidChain {
+        source: CaChainError::PolicyTreeTooLarge {
+            cert_id: cert.subject_name().to_string(),
+        },
+    }
+}
+
+fn no_valid_policy(cert: &Cert) -> CertError {
+    CertError::InvalidChain {
+        source: CaChainError::NoValidPolicy {
+            cert_id: cert.subject_name().to_string(),
+        },
+    }
+}
+
+/// Maximum number of nodes of the valid_policy_tree.
+///
+/// The tree may grow exponentially with the path length when policy mappings are used
+/// (CVE-2023-0464), so processing is aborted past this limit.
+const MAX_POLICY_NODES: usize = 1000;
+
+/// The valid_policy_tree reached `MAX_POLICY_NODES` nodes
+struct PolicyTreeTooLarge;
+
+struct PolicyNode {
+    depth: usize,
+    valid_policy: ObjectIdentifier,
+    expected_policy_set: Vec<ObjectIdentifier>,
+    parent: Option<usize>,
+    deleted: bool,
+}
+
+/// valid_policy_tree stored as an arena (a parent is always stored before its children).
+/// Policy qualifiers are not tracked.
+struct PolicyTree {
+    nodes: Vec<PolicyNode>,
+}
+
+impl PolicyTree {
+    fn new() -> Self {
+        Self {
+            nodes: vec![PolicyNode {
+                depth: 0,
+                valid_policy: oids::any_policy(),
+                expected_policy_set: vec![oids::any_policy()],
+                parent: None,
+                deleted: false,
+            }],
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        self.nodes[0].deleted
+    }
+
+    fn nodes_at(&self, depth: usize) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|&idx| !self.nodes[idx].deleted && self.nodes[idx].depth == depth)
+            .collect()
+    }
+
+    fn children(&self, parent: usize) -> impl Iterator<Item = &PolicyNode> {
+        self.nodes
+            .iter()
+            .filter(move |node| !node.deleted && node.parent == Some(parent))
+    }
+
+    fn add_child(
+        &mut self,
+        parent: usize,
+        valid_policy: ObjectIdentifier,
+        expected_policy_set: Vec<ObjectIdentifier>,
+    ) -> Result<(), PolicyTreeTooLarge> {
+        // deleted nodes are counted as well since they are never freed
+        if self.nodes.len() >= MAX_POLICY_NODES {
+            return Err(PolicyTreeTooLarge);
+        }
+
+        self.nodes.push(PolicyNode {
+            depth: self.nodes[parent].depth + 1,
+            valid_policy,
+            expected_policy_set,
+            parent: Some(parent),
+            deleted: false,
+        });
+        Ok(())
+    }
+
+    /// Deletes a node and all its descendants.
+    fn delete(&mut self, idx: usize) {
+        self.nodes[idx].deleted = true;
+        for descendant in idx + 1..self.nodes.len() {
+            if let Some(parent) = self.nodes[descendant].parent {
+                if self.nodes[parent].deleted {
+                    self.nodes[descendant].deleted = true;
+                }
+            }
+        }
+    }
+
+    /// Deletes nodes of depth lower than `depth` without any child.
+    fn prune(&mut self, depth: usize) {
+        for depth in (0..depth).rev() {
+            for idx in self.nodes_at(depth) {
+                if self.children(idx).next().is_none() {
+                    self.nodes[idx].deleted = true;
+                }
+            }
+        }
+    }
+
+    /// Step (d) of certificate processing.
+    fn add_policies(
+        &mut self,
+        depth: usize,
+        policies: &CertificatePolicies,
+        any_policy_allowed: bool,
+    ) -> Result<(), PolicyTreeTooLarge> {
+        let any_policy = oids::any_policy();
+        let parents = self.nodes_at(depth - 1);
+
+        for policy in policies.iter().map(|policy| policy.policy_identifier()) {
+            if policy == &any_policy {
+                continue;
+            }
+
+            let matching_parents: Vec<usize> = parents
+                .iter()
+                .copied()
+                .filter(|&parent| self.nodes[parent].expected_policy_set.contains(policy))
+                .collect();
+
+            if !matching_parents.is_empty() {
+                for parent in matching_parents {
+                    self.add_child(parent, policy.clone(), vec![policy.clone()])?;
+                }
+            } else if let Some(&parent) = parents
+                .iter()
+                .find(|&&parent| self.nodes[parent].valid_policy == any_policy)
+            {
+                self.add_child(parent, policy.clone(), vec![policy.clone()])?;
+            }
+        }
+
+        if any_policy_allowed && policies.contains(any_policy.clone()) {
+            for parent in parents {
+                for expected_policy in self.nodes[parent].expected_policy_set.clone() {
+                    if self.children(parent).all(|child| child.valid_policy != expected_policy) {
+                        self.add_child(parent, expected_policy.clone(), vec![expected_policy])?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Step (b) of the preparation for the next certificate.
+    fn apply_mappings(
+        &mut self,
+        depth: usize,
+        mappings: &PolicyMappings,
+        mapping_allowed: bool,
+    ) -> Result<(), PolicyTreeTooLarge> {
+        let any_policy = oids::any_policy();
+
+        let mut issuer_domain_policies: Vec<&ObjectIdentifier> = Vec::new();
+        for mapping in mappings.iter() {
+            if !issuer_domain_policies.contains(&mapping.issuer_domain_policy()) {
+                issuer_domain_policies.push(mapping.issuer_domain_policy());
+            }
+        }
+
+        for issuer_domain_policy in issuer_domain_policies {
+            let nodes: Vec<usize> = self
+                .nodes_at(depth)
+                .into_iter()
+                .filter(|&idx| &self.nodes[idx].valid_policy == issuer_domain_policy)
+                .collect();
+
+            if !mapping_allowed {
+                for idx in nodes {
+                    self.delete(idx);
+                }
+                continue;
+            }
+
+            let subject_domain_policies: Vec<ObjectIdentifier> = mappings
+                .iter()
+                .filter(|mapping| mapping.issuer_domain_policy() == issuer_domain_policy)
+                .map(|mapping| mapping.subject_domain_policy().clone())
+                .collect();
+
+            if !nodes.is_empty() {
+                for idx in nodes {
+                    self.nodes[idx].expected_policy_set = subject_domain_policies.clone();
+                }
+            } else if self
+                .nodes_at(depth)
+                .iter()
+                .any(|&idx| self.nodes[idx].valid_policy == any_policy)
+            {
+                if let Some(parent) = self
+                    .nodes_at(depth - 1)
+                    .into_iter()
+                    .find(|&idx| self.nodes[idx].valid_policy == any_policy)
+                {
+                    self.add_child(parent, issuer_domain_policy.clone(), subject_domain_policies)?;
+                }
+            }
+        }
+
+        if !mapping_allowed {
+            self.prune(depth);
+        }
+
+        Ok(())
+    }
+
+    /// Step (g) of the wrap-up procedure: intersection with the user-initial-policy-set.
+    ///
+    /// Returns the valid policies of the nodes of depth `n`.
+    fn intersect(
+        &mut self,
+        n: usize,
+        acceptable_policies: &[ObjectIdentifier],
+    ) -> Result<Vec<ObjectIdentifier>, PolicyTreeTooLarge> {
+        let any_policy = oids::any_policy();
+
+        if !self.is_null() && !acceptable_policies.contains(&any_policy) {
+            let valid_policy_node_set: Vec<usize> = (0..self.nodes.len())
+                .filter(|&idx| {
+                    let node = &self.nodes[idx];
+                    !node.deleted
+                        && node
+                            .parent
+                            .map(|parent| self.nodes[parent].valid_policy == any_policy)
+                            .unwrap_or(false)
+                })
+                .collect();
+
+            for &idx in &valid_policy_node_set {
+                let valid_policy = &self.nodes[idx].valid_policy;
+                if valid_policy != &any_policy && !acceptable_policies.contains(valid_policy) {
+                    self.delete(idx);
+                }
+            }
+
+            let leaf_any_policy = self
+                .nodes_at(n)
+                .into_iter()
+                .find(|&idx| self.nodes[idx].valid_policy == any_policy);
+            if let Some(leaf_any_policy) = leaf_any_policy {
+                if let Some(parent) = self.nodes[leaf_any_policy].parent {
+                    for policy in acceptable_policies {
+                        if valid_policy_node_set
+                            .iter()
+                            .all(|&idx| &self.nodes[idx].valid_policy != policy)
+                        {
+                            self.add_child(parent, policy.clone(), vec![policy.clone()])?;
+                        }
+                    }
+                }
+                self.delete(leaf_any_policy);
+            }
+
+            self.prune(n);
+        }
+
+        let mut valid_policies = Vec::new();
+        if !self.is_null() {
+            for idx in self.nodes_at(n) {
+                let policy = self.issuer_domain_policy(idx, &any_policy);
+                if !valid_policies.contains(policy) {
+                    valid_policies.push(policy.clone());
+                }
+            }
+        }
+        Ok(valid_policies)
+    }
+
+    /// Policy of the topmost ancestor which isn't anyPolicy, that is the policy as known by the trust anchor
+    /// before any mapping.
+    fn issuer_domain_policy(&self, idx: usize, any_policy: &ObjectIdentifier) -> &ObjectIdentifier {
+        let mut policy = &self.nodes[idx].valid_policy;
+        let mut current = self.nodes[idx].parent;
+        while let Some(parent) = current {
+            if &self.nodes[parent].valid_policy != any_policy {
+                policy = &self.nodes[parent].valid_policy;
+            }
+            current = self.nodes[parent].parent;
+        }
+        policy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        key::PrivateKey,
+        pem::Pem,
+        x509::{
+            certificate::CertificateBuilder,
+            chain::{ChainBuilder, ChainValidationSettings},
+            date::UTCDate,
+            extension::{PolicyConstraints, PolicyInformation, PolicyMapping},
+            name::DirectoryName,
+        },
+    };
+    use std::convert::TryFrom;
+
+    const POLICY_1: &str = "1.2.3.4.1";
+    const POLICY_2: &str = "1.2.3.4.2";
+
+    fn parse_key(pem_str: &str) -> PrivateKey {
+        let pem = pem_str.parse::<Pem>().unwrap();
+        PrivateKey::from_pem(&pem).unwrap()
+    }
+
+    fn date(year: u16) -> UTCDate {
+        UTCDate::ymd(year, 1, 1).unwrap()
+    }
+
+    fn policy(oid: &str) -> ObjectIdentifier {
+        ObjectIdentifier::try_from(oid).unwrap()
+    }
+
+    fn policies(oids: &[&str]) -> CertificatePolicies {
+        oids.iter()
+            .map(|oid| PolicyInformation::new(policy(oid)))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Returns a path ordered from the leaf to the root
+    fn build_path<F: FnOnce(&CertificateBuilder)>(configure_intermediate: F, leaf_policies: &[&str]) -> Vec<Cert> {
+        let root_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let intermediate_key = parse_key(crate::test_files::EC_P384_PK_1);
+        let leaf_key = parse_key(crate::test_files::EC_P256_PK_2);
+
+        let root = CertificateBuilder::new()
+            .valididy(date(2060), date(2080))
+            .self_signed(DirectoryName::new_common_name("Policy Root CA"), &root_key)
+            .ca(true)
+            .build()
+            .expect("root");
+
+        let intermediate_builder = CertificateBuilder::new();
+        intermediate_builder
+            .valididy(date(2060), date(2080))
+            .subject(
+                DirectoryName::new_common_name("Policy Authority"),
+                intermediate_key.to_public_key(),
+            )
+            .issuer_cert(&root, &root_key)
+            .ca(true);
+        configure_intermediate(&intermediate_builder);
+        let intermediate = intermediate_builder.build().expect("intermediate");
+
+        let leaf_builder = CertificateBuilder::new();
+        leaf_builder
+            .valididy(date(2060), date(2080))
+            .subject(DirectoryName::new_common_name("Policy Leaf"), leaf_key.to_public_key())
+            .issuer_cert(&intermediate, &intermediate_key);
+        if !leaf_policies.is_empty() {
+            leaf_builder.certificate_policies(policies(leaf_policies));
+        }
+        let leaf = leaf_builder.build().expect("leaf");
+
+        vec![leaf, intermediate, root]
+            .into_iter()
+            .map(|cert| Cert::from_der(&cert.to_der().expect("to der")).expect("from der"))
+            .collect()
+    }
+
+    fn validate(settings: PolicySettings, path: &[Cert]) -> Result<Vec<ObjectIdentifier>, CertError> {
+        settings.validate(&path.iter().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn explicit_policies() {
+        let path = build_path(
+            |intermediate| {
+                intermediate.certificate_policies(policies(&[POLICY_1, POLICY_2]));
+            },
+            &[POLICY_1],
+        );
+
+        assert_eq!(
+            validate(PolicySettings::default(), &path).expect("any policy"),
+            vec![policy(POLICY_1)]
+        );
+        assert_eq!(
+            validate(
+                PolicySettings::new(vec![policy(POLICY_1)]).require_explicit_policy(true),
+                &path
+            )
+            .expect("acceptable policy"),
+            vec![policy(POLICY_1)]
+        );
+        assert_eq!(
+            validate(PolicySettings::new(vec![policy(POLICY_2)]), &path).expect("explicit policy not required"),
+            Vec::<ObjectIdentifier>::new()
+        );
+
+        let err = validate(
+            PolicySettings::new(vec![policy(POLICY_2)]).require_explicit_policy(true),
+            &path,
+        )
+        .expect_err("policy not acceptable");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: no valid certificate policy for 'CN=Policy Leaf'"
+        );
+
+        let path = build_path(
+            |intermediate| {
+                intermediate.certificate_policies(policies(&[POLICY_1]));
+            },
+            &[],
+        );
+        let err = validate(PolicySettings::default().require_explicit_policy(true), &path)
+            .expect_err("leaf without policies");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: no valid certificate policy for 'CN=Policy Leaf'"
+        );
+    }
+
+    #[test]
+    fn policy_mappings() {
+        let path = build_path(
+            |intermediate| {
+                intermediate
+                    .certificate_policies(policies(&[POLICY_1]))
+                    .policy_mappings(vec![PolicyMapping::new(policy(POLICY_1), policy(POLICY_2))].into());
+            },
+            &[POLICY_2],
+        );
+
+        let settings = PolicySettings::new(vec![policy(POLICY_1)]).require_explicit_policy(true);
+        assert_eq!(
+            validate(settings.clone(), &path).expect("mapped policy"),
+            vec![policy(POLICY_1)]
+        );
+        validate(settings.inhibit_policy_mapping(true), &path).expect_err("policy mapping inhibited");
+
+        let path = build_path(
+            |intermediate| {
+                intermediate
+                    .certificate_policies(policies(&[POLICY_1]))
+                    .policy_mappings(vec![PolicyMapping::new(oids::any_policy(), policy(POLICY_2))].into());
+            },
+            &[POLICY_2],
+        );
+        let err = validate(PolicySettings::default(), &path).expect_err("anyPolicy mapping");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: certificate 'CN=Policy Authority' maps a policy to or from anyPolicy"
+        );
+    }
+
+    #[test]
+    fn policy_tree_size_limit() {
+        let leaf_policies: Vec<String> = (0..MAX_POLICY_NODES)
+            .map(|i| format!("1.2.3.5.{}.{}", i / 100, i % 100))
+            .collect();
+        let leaf_policies: Vec<&str> = leaf_policies.iter().map(String::as_str).collect();
+        let path = build_path(
+            |intermediate| {
+                intermediate.certificate_policies(policies(&[oids::ANY_POLICY]));
+            },
+            &leaf_policies,
+        );
+
+        let err = validate(PolicySettings::default(), &path).expect_err("too many policies");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: too many certificate policies to process for 'CN=Policy Leaf'"
+        );
+
+        let path = build_path(
+            |intermediate| {
+                intermediate.certificate_policies(policies(&[oids::ANY_POLICY]));
+            },
+            &leaf_policies[..MAX_POLICY_NODES - 2],
+        );
+        assert_eq!(
+            validate(PolicySettings::default(), &path)
+                .expect("policies under the limit")
+                .len(),
+            MAX_POLICY_NODES - 2
+        );
+    }
+
+    #[test]
+    fn any_policy() {
+        let path = build_path(
+            |intermediate| {
+                intermediate.certificate_policies(policies(&[oids::ANY_POLICY]));
+            },
+            &[POLICY_1],
+        );
+
+        let settings = PolicySettings::new(vec![policy(POLICY_1)]).require_explicit_policy(true);
+        assert_eq!(
+            validate(settings.clone(), &path).expect("any policy"),
+            vec![policy(POLICY_1)]
+        );
+        validate(settings.inhibit_any_policy(true), &path).expect_err("any policy inhibited");
+
+        let path = build_path(
+            |intermediate| {
+                intermediate
+                    .certificate_policies(policies(&[oids::ANY_POLICY]))
+                    .inhibit_any_policy(0);
+            },
+            &[oids::ANY_POLICY],
+        );
+        assert_eq!(
+            validate(PolicySettings::default(), &path).expect("explicit policy not required"),
+            Vec::<ObjectIdentifier>::new()
+        );
+    }
+
+    #[test]
+    fn policy_constraints() {
+        let path = build_path(
+            |intermediate| {
+                intermediate
+                    .certificate_policies(policies(&[POLICY_1]))
+                    .policy_constraints(PolicyConstraints::new(Some(0), None));
+            },
+            &[],
+        );
+
+        let err = validate(PolicySettings::default(), &path).expect_err("explicit policy required by CA");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: no valid certificate policy for 'CN=Policy Leaf'"
+        );
+    }
+
+    #[test]
+    fn chain_builder_with_policy_settings() {
+        let path = build_path(
+            |intermediate| {
+                intermediate.certificate_policies(policies(&[POLICY_1]));
+            },
+            &[POLICY_1],
+        );
+
+        let (built_path, valid_policies) = ChainBuilder::new()
+            .leaf(&path[0])
+            .intermediates(vec![&path[1]])
+            .trust_anchors(vec![&path[2]])
+            .now(date(2070))
+            .policy_settings(PolicySettings::new(vec![policy(POLICY_1)]).require_explicit_policy(true))
+            .build_with_policies()
+            .expect("path");
+        assert_eq!(built_path, path.iter().collect::<Vec<_>>());
+        assert_eq!(valid_policies, Some(vec![policy(POLICY_1)]));
+
+        let (_, valid_policies) = ChainBuilder::new()
+            .leaf(&path[0])
+            .intermediates(vec![&path[1]])
+            .trust_anchors(vec![&path[2]])
+            .now(date(2070))
+            .build_with_policies()
+            .expect("path");
+        assert_eq!(valid_policies, None);
+
+        let err = ChainBuilder::new()
+            .leaf(&path[0])
+            .intermediates(vec![&path[1]])
+            .trust_anchors(vec![&path[2]])
+            .now(date(2070))
+            .policy_settings(PolicySettings::new(vec![policy(POLICY_2)]).require_explicit_policy(true))
+            .build()
+            .expect_err("policy not acceptable");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: no valid certificate policy for 'CN=Policy Leaf'"
+        );
+    }
+
+    #[test]
+    fn verify_chain_with_policy_settings() {
+        let path = build_path(
+            |intermediate| {
+                intermediate.certificate_policies(policies(&[POLICY_1]));
+            },
+            &[POLICY_1],
+        );
+
+        let settings = ChainValidationSettings::new()
+            .policy_settings(PolicySettings::new(vec![policy(POLICY_1)]).require_explicit_policy(true));
+        path[0]
+            .verify_chain_with_settings(path[1..].iter(), &date(2070), &settings)
+            .expect("acceptable policy");
+
+        let settings = ChainValidationSettings::new()
+            .policy_settings(PolicySettings::new(vec![policy(POLICY_2)]).require_explicit_policy(true));
+        let err = path[0]
+            .verify_chain_with_settings(path[1..].iter(), &date(2070), &settings)
+            .expect_err("policy not acceptable");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: no valid certificate policy for 'CN=Policy Leaf'"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{oids, x509::DirectoryString};
-use picky_asn1::wrapper::ObjectIdentifierAsn1;
+use picky_asn1::wrapper::{IA5StringAsn1, ObjectIdentifierAsn1};
 use serde::{de, ser};
 use std::fmt;
 
@@ -14,6 +14,7 @@ pub(crate) enum AttributeTypeAndValueParameters {
     StreetName(DirectoryString),
     OrganisationName(DirectoryString),
     OrganisationalUnitName(DirectoryString),
+    EmailAddress(IA5StringAsn1),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -85,6 +86,13 @@ impl AttributeTypeAndValue {
             value: AttributeTypeAndValueParameters::OrganisationalUnitName(name.into()),
         }
     }
+
+    pub fn new_email_address<S: Into<IA5StringAsn1>>(address: S) -> Self {
+        Self {
+            ty: oids::email_address().into(),
+            value: AttributeTypeAndValueParameters::EmailAddress(address.into()),
+        }
+    }
 }
 
 impl ser::Serialize for AttributeTypeAndValue {
@@ -123,6 +131,9 @@ impl ser::Serialize for AttributeTypeAndValue {
             AttributeTypeAndValueParameters::OrganisationalUnitName(name) => {
                 seq.serialize_element(name)?;
             }
+            AttributeTypeAndValueParameters::EmailAddress(address) => {
+                seq.serialize_element(address)?;
+            }
         }
         seq.end()
     }
@@ -189,14 +200,12 @@ impl<'de> de::Deserialize<'de> for AttributeTypeAndValue {
                         oids::AT_ORGANISATIONAL_UNIT_NAME => AttributeTypeAndValueParameters::OrganisationalUnitName(
                             seq_next_element!(seq, AttributeTypeAndValue, "at organisational unit name"),
                         ),
-                        oids::EMAIL_ADDRESS => {
-                            return Err(serde_invalid_value!(
-                                AttributeTypeAndValue,
-                                "1.2.840.113549.1.9.1 (e-mailAddress) \
-                                 attribute is deprecated and won't be supported",
-                                "a supported type"
-                            ));
-                        }
+                        // deprecated, but still found in the wild and subject to name constraints
+                        oids::EMAIL_ADDRESS => AttributeTypeAndValueParameters::EmailAddress(seq_next_element!(
+                            seq,
+                            AttributeTypeAndValue,
+                            "at email address"
+                        )),
                         _ => {
                             return Err(serde_invalid_value!(
                                 AttributeTypeAndValue,
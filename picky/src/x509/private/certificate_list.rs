@@ -0,0 +1,158 @@
+use crate::{
+    x509::{
+        private::{validity::Time, Name, Version},
+        Extensions,
+    },
+    AlgorithmIdentifier,
+};
+use picky_asn1::{
+    tag::{Tag, TagPeeker},
+    wrapper::{ApplicationTag0, Asn1SequenceOf, BitStringAsn1, IntegerAsn1, RawDerAsn1},
+};
+use serde::{de, Deserialize, Serialize};
+use std::fmt;
+
+/// https://tools.ietf.org/html/rfc5280#section-5.1
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct CertificateList {
+    pub tbs_cert_list: TBSCertList,
+    pub signature_algorithm: AlgorithmIdentifier,
+    pub signature_value: BitStringAsn1,
+}
+
+// Same as `CertificateList` with the TBS cert list kept as received since the signature is computed over its encoding.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct RawCertificateList {
+    pub tbs_cert_list: RawDerAsn1,
+    pub signature_algorithm: AlgorithmIdentifier,
+    pub signature_value: BitStringAsn1,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct TBSCertList {
+    pub version: Option<Version>,
+    pub signature: AlgorithmIdentifier,
+    pub issuer: Name,
+    pub this_update: Time,
+    pub next_update: Option<Time>,
+    pub revoked_certificates: Option<Asn1SequenceOf<RevokedCertificate>>,
+    pub crl_extensions: Option<ApplicationTag0<Extensions>>,
+}
+
+// Implement Deserialize manually because of the optional fields interleaved in the sequence.
+impl<'de> de::Deserialize<'de> for TBSCertList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = TBSCertList;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct TBSCertList")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                let version = if let Some(TagPeeker { next_tag: Tag::INTEGER }) = seq.next_element()? {
+                    let version: u8 = seq_next_element!(seq, TBSCertList, "version");
+                    match Version::from_u8(version) {
+                        Some(Version::V2) => Some(Version::V2),
+                        _ => {
+                            return Err(serde_invalid_value!(
+                                TBSCertList,
+                                "unsupported CRL version",
+                                "V2 (or absent for V1)"
+                            ))
+                        }
+                    }
+                } else {
+                    None
+                };
+
+                let signature = seq_next_element!(seq, TBSCertList, "signature algorithm");
+                let issuer = seq_next_element!(seq, TBSCertList, "issuer");
+                let this_update = seq_next_element!(seq, TBSCertList, "this update");
+
+                let mut next_update = None;
+                let mut revoked_certificates = None;
+                let mut crl_extensions = None;
+
+                if let Some(TagPeeker {
+                    next_tag: Tag::UTC_TIME,
+                })
+                | Some(TagPeeker {
+                    next_tag: Tag::GENERALIZED_TIME,
+                }) = seq.next_element()?
+                {
+                    next_update = Some(seq_next_element!(seq, TBSCertList, "next update"));
+                }
+
+                if let Some(TagPeeker {
+                    next_tag: Tag::SEQUENCE,
+                }) = seq.next_element()?
+                {
+                    revoked_certificates = Some(seq_next_element!(seq, TBSCertList, "revoked certificates"));
+                }
+
+                if let Some(TagPeeker { next_tag: Tag::APP_0 }) = seq.next_element()? {
+                    crl_extensions = Some(seq_next_element!(seq, TBSCertList, "CRL extensions"));
+                }
+
+                Ok(TBSCertList {
+                    version,
+                    signature,
+                    issuer,
+                    this_update,
+                    next_update,
+                    revoked_certificates,
+                    crl_extensions,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct RevokedCertificate {
+    pub user_certificate: IntegerAsn1,
+    pub revocation_date: Time,
+    pub crl_entry_extensions: Option<Extensions>,
+}
+
+// Trailing optional field
+impl<'de> de::Deserialize<'de> for RevokedCertificate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = RevokedCertificate;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct RevokedCertificate")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                Ok(RevokedCertificate {
+                    user_certificate: seq_next_element!(seq, RevokedCertificate, "user certificate"),
+                    revocation_date: seq_next_element!(seq, RevokedCertificate, "revocation date"),
+                    crl_entry_extensions: seq.next_element()?,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
@@ -1,12 +1,15 @@
 pub(crate) mod attribute_type_and_value;
 pub(crate) mod certificate;
+pub(crate) mod certificate_list;
 pub(crate) mod certification_request;
 pub(crate) mod name;
+pub(crate) mod ocsp;
 pub(crate) mod validity;
 pub(crate) mod version;
 
 pub(crate) use attribute_type_and_value::AttributeTypeAndValue;
 pub(crate) use certificate::Certificate;
+pub(crate) use certificate_list::{CertificateList, RawCertificateList, RevokedCertificate, TBSCertList};
 pub(crate) use certification_request::CertificationRequest;
 pub(crate) use name::Name;
 pub(crate) use validity::Validity;
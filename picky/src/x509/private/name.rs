@@ -61,6 +61,9 @@ impl fmt::Display for NamePrettyFormatter<'_> {
                     AttributeTypeAndValueParameters::OrganisationalUnitName(name) => {
                         write!(f, "OU={}", name)?;
                     }
+                    AttributeTypeAndValueParameters::EmailAddress(address) => {
+                        write!(f, "E={}", address.0)?;
+                    }
                 }
             }
         }
@@ -122,7 +125,8 @@ impl ser::Serialize for GeneralName {
         match &self {
             GeneralName::RFC822Name(name) => ContextTag1(name).serialize(serializer),
             GeneralName::DNSName(name) => ContextTag2(name).serialize(serializer),
-            GeneralName::DirectoryName(name) => ContextTag4(name).serialize(serializer),
+            // Name is a CHOICE, hence the explicit tag
+            GeneralName::DirectoryName(name) => ApplicationTag4(name).serialize(serializer),
             GeneralName::EDIPartyName(name) => ContextTag5(name).serialize(serializer),
             GeneralName::URI(name) => ContextTag6(name).serialize(serializer),
             GeneralName::IpAddress(name) => ContextTag7(name).serialize(serializer),
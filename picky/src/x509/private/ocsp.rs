@@ -0,0 +1,388 @@
+//! https://tools.ietf.org/html/rfc6960#section-4
+
+use crate::{
+    x509::{
+        ocsp::{CertId, ResponderId, SingleResponse},
+        private::{name::GeneralName, Certificate},
+        Extensions,
+    },
+    AlgorithmIdentifier,
+};
+use picky_asn1::{
+    tag::{Tag, TagPeeker},
+    wrapper::{
+        ApplicationTag0, ApplicationTag1, ApplicationTag2, Asn1SequenceOf, BitStringAsn1, EnumeratedAsn1,
+        GeneralizedTimeAsn1, ObjectIdentifierAsn1, OctetStringAsn1, RawDerAsn1,
+    },
+};
+use serde::{de, Deserialize, Serialize};
+use std::fmt;
+
+// OCSPRequest ::= SEQUENCE {
+//      tbsRequest                  TBSRequest,
+//      optionalSignature   [0]     EXPLICIT Signature OPTIONAL }
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct OCSPRequest {
+    pub tbs_request: TBSRequest,
+    pub optional_signature: Option<ApplicationTag0<Signature>>,
+}
+
+// Trailing optional field
+impl<'de> de::Deserialize<'de> for OCSPRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = OCSPRequest;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct OCSPRequest")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                Ok(OCSPRequest {
+                    tbs_request: seq_next_element!(seq, OCSPRequest, "tbs request"),
+                    optional_signature: seq.next_element()?,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// TBSRequest ::= SEQUENCE {
+//      version             [0]     EXPLICIT Version DEFAULT v1,
+//      requestorName       [1]     EXPLICIT GeneralName OPTIONAL,
+//      requestList                 SEQUENCE OF Request,
+//      requestExtensions   [2]     EXPLICIT Extensions OPTIONAL }
+//
+// Only v1 exists: the version is never encoded (DEFAULT).
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct TBSRequest {
+    pub requestor_name: Option<ApplicationTag1<GeneralName>>,
+    pub request_list: Asn1SequenceOf<Request>,
+    pub request_extensions: Option<ApplicationTag2<Extensions>>,
+}
+
+impl<'de> de::Deserialize<'de> for TBSRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = TBSRequest;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct TBSRequest")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                if let Some(TagPeeker { next_tag: Tag::APP_0 }) = seq.next_element()? {
+                    let version: ApplicationTag0<u8> = seq_next_element!(seq, TBSRequest, "version");
+                    if version.0 != 0 {
+                        return Err(serde_invalid_value!(TBSRequest, "unsupported OCSP version", "v1"));
+                    }
+                }
+
+                let requestor_name = if let Some(TagPeeker { next_tag: Tag::APP_1 }) = seq.next_element()? {
+                    Some(seq_next_element!(seq, TBSRequest, "requestor name"))
+                } else {
+                    None
+                };
+
+                Ok(TBSRequest {
+                    requestor_name,
+                    request_list: seq_next_element!(seq, TBSRequest, "request list"),
+                    request_extensions: seq.next_element()?,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// Request ::= SEQUENCE {
+//      reqCert                     CertID,
+//      singleRequestExtensions     [0] EXPLICIT Extensions OPTIONAL }
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct Request {
+    pub req_cert: CertId,
+    pub single_request_extensions: Option<ApplicationTag0<Extensions>>,
+}
+
+// Trailing optional field
+impl<'de> de::Deserialize<'de> for Request {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Request;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Request")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                Ok(Request {
+                    req_cert: seq_next_element!(seq, Request, "request certificate id"),
+                    single_request_extensions: seq.next_element()?,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// Signature ::= SEQUENCE {
+//      signatureAlgorithm      AlgorithmIdentifier,
+//      signature               BIT STRING,
+//      certs               [0] EXPLICIT SEQUENCE OF Certificate OPTIONAL }
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct Signature {
+    pub signature_algorithm: AlgorithmIdentifier,
+    pub signature: BitStringAsn1,
+    pub certs: Option<ApplicationTag0<Asn1SequenceOf<Certificate>>>,
+}
+
+// Trailing optional field
+impl<'de> de::Deserialize<'de> for Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Signature;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Signature")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                Ok(Signature {
+                    signature_algorithm: seq_next_element!(seq, Signature, "signature algorithm"),
+                    signature: seq_next_element!(seq, Signature, "signature"),
+                    certs: seq.next_element()?,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// OCSPResponse ::= SEQUENCE {
+//      responseStatus         OCSPResponseStatus,
+//      responseBytes          [0] EXPLICIT ResponseBytes OPTIONAL }
+//
+// OCSPResponseStatus ::= ENUMERATED { ... }
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct OCSPResponse {
+    pub response_status: EnumeratedAsn1,
+    pub response_bytes: Option<ApplicationTag0<ResponseBytes>>,
+}
+
+// Trailing optional field
+impl<'de> de::Deserialize<'de> for OCSPResponse {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = OCSPResponse;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct OCSPResponse")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                Ok(OCSPResponse {
+                    response_status: seq_next_element!(seq, OCSPResponse, "response status"),
+                    response_bytes: seq.next_element()?,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// ResponseBytes ::=       SEQUENCE {
+//      responseType   OBJECT IDENTIFIER,
+//      response       OCTET STRING }
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct ResponseBytes {
+    pub response_type: ObjectIdentifierAsn1,
+    pub response: OctetStringAsn1,
+}
+
+// BasicOCSPResponse       ::= SEQUENCE {
+//      tbsResponseData      ResponseData,
+//      signatureAlgorithm   AlgorithmIdentifier,
+//      signature            BIT STRING,
+//      certs            [0] EXPLICIT SEQUENCE OF Certificate OPTIONAL }
+//
+// The response data is kept as received since the signature is computed over its encoding.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct BasicOCSPResponse {
+    pub tbs_response_data: RawDerAsn1,
+    pub signature_algorithm: AlgorithmIdentifier,
+    pub signature: BitStringAsn1,
+    pub certs: Option<ApplicationTag0<Asn1SequenceOf<Certificate>>>,
+}
+
+// Trailing optional field
+impl<'de> de::Deserialize<'de> for BasicOCSPResponse {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = BasicOCSPResponse;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct BasicOCSPResponse")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                Ok(BasicOCSPResponse {
+                    tbs_response_data: seq_next_element!(seq, BasicOCSPResponse, "tbs response data"),
+                    signature_algorithm: seq_next_element!(seq, BasicOCSPResponse, "signature algorithm"),
+                    signature: seq_next_element!(seq, BasicOCSPResponse, "signature"),
+                    certs: seq.next_element()?,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// ResponseData ::= SEQUENCE {
+//      version              [0] EXPLICIT Version DEFAULT v1,
+//      responderID              ResponderID,
+//      producedAt               GeneralizedTime,
+//      responses                SEQUENCE OF SingleResponse,
+//      responseExtensions   [1] EXPLICIT Extensions OPTIONAL }
+//
+// Only v1 exists: the version is never encoded (DEFAULT).
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct ResponseData {
+    pub responder_id: ResponderId,
+    pub produced_at: GeneralizedTimeAsn1,
+    pub responses: Asn1SequenceOf<SingleResponse>,
+    pub response_extensions: Option<ApplicationTag1<Extensions>>,
+}
+
+impl<'de> de::Deserialize<'de> for ResponseData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = ResponseData;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct ResponseData")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                if let Some(TagPeeker { next_tag: Tag::APP_0 }) = seq.next_element()? {
+                    let version: ApplicationTag0<u8> = seq_next_element!(seq, ResponseData, "version");
+                    if version.0 != 0 {
+                        return Err(serde_invalid_value!(ResponseData, "unsupported OCSP version", "v1"));
+                    }
+                }
+
+                Ok(ResponseData {
+                    responder_id: seq_next_element!(seq, ResponseData, "responder id"),
+                    produced_at: seq_next_element!(seq, ResponseData, "produced at"),
+                    responses: seq_next_element!(seq, ResponseData, "responses"),
+                    response_extensions: seq.next_element()?,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+// RevokedInfo ::= SEQUENCE {
+//      revocationTime              GeneralizedTime,
+//      revocationReason    [0]     EXPLICIT CRLReason OPTIONAL }
+//
+// CRLReason ::= ENUMERATED { ... }
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct RevokedInfo {
+    pub revocation_time: GeneralizedTimeAsn1,
+    pub revocation_reason: Option<ApplicationTag0<EnumeratedAsn1>>,
+}
+
+// Trailing optional field
+impl<'de> de::Deserialize<'de> for RevokedInfo {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = RevokedInfo;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct RevokedInfo")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                Ok(RevokedInfo {
+                    revocation_time: seq_next_element!(seq, RevokedInfo, "revocation time"),
+                    revocation_reason: seq.next_element()?,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
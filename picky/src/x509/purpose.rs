@@ -0,0 +1,380 @@
+//! Intended usage validation of a certification path.
+//!
+//! https://tools.ietf.org/html/rfc5280#section-4.2.1.3
+//! https://tools.ietf.org/html/rfc5280#section-4.2.1.12
+
+use crate::{
+    oids,
+    x509::{
+        certificate::{CaChainError, Cert, CertError},
+        extension::KeyUsage,
+    },
+};
+use oid::ObjectIdentifier;
+use std::fmt;
+
+/// Key usage bit getter and name
+type KeyUsageBit = (fn(&KeyUsage) -> bool, &'static str);
+
+/// What the leaf certificate of a certification path will be used for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyPurpose {
+    TlsServer,
+    TlsClient,
+    CodeSigning,
+    EmailProtection,
+    TimeStamping,
+    OcspSigning,
+    /// Any other extended key usage purpose (key usage is not checked)
+    Other(ObjectIdentifier),
+}
+
+impl fmt::Display for KeyPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyPurpose::TlsServer => write!(f, "TLS server authentication"),
+            KeyPurpose::TlsClient => write!(f, "TLS client authentication"),
+            KeyPurpose::CodeSigning => write!(f, "code signing"),
+            KeyPurpose::EmailProtection => write!(f, "email protection"),
+            KeyPurpose::TimeStamping => write!(f, "time stamping"),
+            KeyPurpose::OcspSigning => write!(f, "OCSP signing"),
+            KeyPurpose::Other(oid) => write!(f, "{}", Into::<String>::into(oid)),
+        }
+    }
+}
+
+impl KeyPurpose {
+    /// Extended key usage purpose identifier (id-kp-*)
+    pub fn oid(&self) -> ObjectIdentifier {
+        match self {
+            KeyPurpose::TlsServer => oids::kp_server_auth(),
+            KeyPurpose::TlsClient => oids::kp_client_auth(),
+            KeyPurpose::CodeSigning => oids::kp_code_signing(),
+            KeyPurpose::EmailProtection => oids::kp_email_protection(),
+            KeyPurpose::TimeStamping => oids::kp_time_stamping(),
+            KeyPurpose::OcspSigning => oids::kp_ocsp_signing(),
+            KeyPurpose::Other(oid) => oid.clone(),
+        }
+    }
+
+    /// Key usage bits consistent with the purpose (one of them is required) as listed in RFC 5280.
+    fn allowed_key_usages(&self) -> &'static [KeyUsageBit] {
+        match self {
+            KeyPurpose::TlsServer => &[
+                (KeyUsage::digital_signature, "digitalSignature"),
+                (KeyUsage::key_encipherment, "keyEncipherment"),
+                (KeyUsage::key_agreement, "keyAgreement"),
+            ],
+            KeyPurpose::TlsClient => &[
+                (KeyUsage::digital_signature, "digitalSignature"),
+                (KeyUsage::key_agreement, "keyAgreement"),
+            ],
+            KeyPurpose::CodeSigning => &[(KeyUsage::digital_signature, "digitalSignature")],
+            KeyPurpose::EmailProtection => &[
+                (KeyUsage::digital_signature, "digitalSignature"),
+                (KeyUsage::content_commitment, "nonRepudiation"),
+                (KeyUsage::key_encipherment, "keyEncipherment"),
+                (KeyUsage::key_agreement, "keyAgreement"),
+            ],
+            KeyPurpose::TimeStamping | KeyPurpose::OcspSigning => &[
+                (KeyUsage::digital_signature, "digitalSignature"),
+                (KeyUsage::content_commitment, "nonRepudiation"),
+            ],
+            KeyPurpose::Other(_) => &[],
+        }
+    }
+
+    /// Checks the key usage and extended key usage extensions of a certification path ordered from
+    /// the leaf to the trust anchor (as returned by `ChainBuilder::build`).
+    ///
+    /// - the leaf key usage, if present, must be consistent with the purpose;
+    /// - the leaf extended key usage, if present, must contain the purpose or anyExtendedKeyUsage.
+    ///   OCSP signing is an exception: the leaf extended key usage is required and must contain
+    ///   id-kp-OCSPSigning (see RFC 6960 section 4.2.2.2);
+    /// - the extended key usage of intermediate CAs, if present, must contain the purpose or
+    ///   anyExtendedKeyUsage. The trust anchor itself is not processed.
+    ///
+    /// `keyCertSign` of issuers is checked by `Cert::verify_chain`.
+    pub fn validate(&self, path: &[&Cert]) -> Result<(), CertError> {
+        let leaf = match path.first() {
+            Some(leaf) => *leaf,
+            None => return Ok(()),
+        };
+
+        if let Ok(key_usage) = leaf.key_usage() {
+            let allowed_key_usages = self.allowed_key_usages();
+            if !allowed_key_usages.is_empty() && !allowed_key_usages.iter().any(|(is_set, _)| is_set(key_usage)) {
+                return Err(CertError::InvalidChain {
+                    source: CaChainError::KeyUsageNotAllowed {
+                        cert_id: leaf.subject_name().to_string(),
+                        purpose: self.to_string(),
+                        required: allowed_key_usages
+                            .iter()
+                            .map(|(_, name)| *name)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    },
+                });
+            }
+        }
+
+        let oid = self.oid();
+        let delegated_ocsp_signer = *self == KeyPurpose::OcspSigning;
+        self.check_extended_key_usage(leaf, &oid, !delegated_ocsp_signer, delegated_ocsp_signer)?;
+
+        if path.len() > 2 {
+            for intermediate in &path[1..path.len() - 1] {
+                self.check_extended_key_usage(intermediate, &oid, true, false)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_extended_key_usage(
+        &self,
+        cert: &Cert,
+        oid: &ObjectIdentifier,
+        any_allowed: bool,
+        required: bool,
+    ) -> Result<(), CertError> {
+        let allowed = match cert.extended_key_usage() {
+            Ok(extended_key_usage) => {
+                extended_key_usage.contains(oid.clone())
+                    || (any_allowed && extended_key_usage.contains(oids::kp_any_extended_key_usage()))
+            }
+            Err(_) => !required,
+        };
+
+        if !allowed {
+            return Err(CertError::InvalidChain {
+                source: CaChainError::ExtendedKeyUsageNotAllowed {
+                    cert_id: cert.subject_name().to_string(),
+                    purpose: self.to_string(),
+                },
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        key::PrivateKey,
+        pem::parse_pem,
+        x509::{
+            certificate::CertificateBuilder,
+            chain::{ChainBuilder, ChainValidationSettings},
+            date::UTCDate,
+            extension::ExtendedKeyUsage,
+            name::DirectoryName,
+        },
+    };
+
+    fn parse_key(pem_str: &str) -> PrivateKey {
+        PrivateKey::from_pem(&parse_pem(pem_str).unwrap()).unwrap()
+    }
+
+    fn date(year: u16) -> UTCDate {
+        UTCDate::ymd(year, 1, 1).unwrap()
+    }
+
+    struct Pki {
+        root: Cert,
+        intermediate: Cert,
+        intermediate_key: PrivateKey,
+        leaf_key: PrivateKey,
+    }
+
+    impl Pki {
+        fn new(intermediate_eku: Option<ExtendedKeyUsage>) -> Self {
+            let root_key = parse_key(crate::test_files::EC_P256_PK_1);
+            let intermediate_key = parse_key(crate::test_files::EC_P384_PK_1);
+            let leaf_key = parse_key(crate::test_files::EC_P256_PK_2);
+
+            let mut ca_key_usage = KeyUsage::default();
+            ca_key_usage.set_key_cert_sign(true);
+
+            let root = CertificateBuilder::new()
+                .valididy(date(2060), date(2080))
+                .self_signed(DirectoryName::new_common_name("Purpose Root CA"), &root_key)
+                .ca(true)
+                .key_usage(ca_key_usage.clone())
+                .build()
+                .expect("root");
+
+            let intermediate_builder = CertificateBuilder::new();
+            intermediate_builder
+                .valididy(date(2060), date(2080))
+                .subject(
+                    DirectoryName::new_common_name("Purpose Authority"),
+                    intermediate_key.to_public_key(),
+                )
+                .issuer_cert(&root, &root_key)
+                .ca(true)
+                .key_usage(ca_key_usage);
+            if let Some(eku) = intermediate_eku {
+                intermediate_builder.extended_key_usage(eku);
+            }
+            let intermediate = intermediate_builder.build().expect("intermediate");
+
+            Self {
+                root,
+                intermediate,
+                intermediate_key,
+                leaf_key,
+            }
+        }
+
+        fn leaf(&self, key_usage: Option<KeyUsage>, eku: Option<ExtendedKeyUsage>) -> Cert {
+            let builder = CertificateBuilder::new();
+            builder
+                .valididy(date(2060), date(2080))
+                .subject(
+                    DirectoryName::new_common_name("purpose.example"),
+                    self.leaf_key.to_public_key(),
+                )
+                .issuer_cert(&self.intermediate, &self.intermediate_key);
+            if let Some(key_usage) = key_usage {
+                builder.key_usage(key_usage);
+            }
+            if let Some(eku) = eku {
+                builder.extended_key_usage(eku);
+            }
+            builder.build().expect("leaf")
+        }
+
+        fn validate(&self, leaf: &Cert, purpose: KeyPurpose) -> Result<(), CertError> {
+            ChainBuilder::new()
+                .leaf(leaf)
+                .intermediates(vec![&self.intermediate])
+                .trust_anchors(vec![&self.root])
+                .purpose(purpose)
+                .now(date(2070))
+                .build()
+                .map(|_| ())
+        }
+    }
+
+    fn digital_signature() -> Option<KeyUsage> {
+        let mut key_usage = KeyUsage::default();
+        key_usage.set_digital_signature(true);
+        Some(key_usage)
+    }
+
+    #[test]
+    fn leaf_extended_key_usage() {
+        let pki = Pki::new(None);
+        let leaf = pki.leaf(
+            digital_signature(),
+            Some(ExtendedKeyUsage::new(vec![oids::kp_server_auth()])),
+        );
+
+        pki.validate(&leaf, KeyPurpose::TlsServer).expect("TLS server");
+        let err = pki.validate(&leaf, KeyPurpose::TlsClient).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: extended key usage of certificate 'CN=purpose.example' doesn't allow TLS client \
+             authentication"
+        );
+
+        // without extended key usage, any purpose but OCSP signing is allowed
+        let leaf = pki.leaf(digital_signature(), None);
+        pki.validate(&leaf, KeyPurpose::TlsClient).expect("TLS client");
+        let err = pki.validate(&leaf, KeyPurpose::OcspSigning).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: extended key usage of certificate 'CN=purpose.example' doesn't allow OCSP signing"
+        );
+
+        let leaf = pki.leaf(
+            digital_signature(),
+            Some(ExtendedKeyUsage::new(vec![oids::kp_ocsp_signing()])),
+        );
+        pki.validate(&leaf, KeyPurpose::OcspSigning).expect("OCSP signing");
+
+        // anyExtendedKeyUsage isn't enough for OCSP signing
+        let leaf = pki.leaf(
+            None,
+            Some(ExtendedKeyUsage::new(vec![oids::kp_any_extended_key_usage()])),
+        );
+        pki.validate(&leaf, KeyPurpose::CodeSigning).expect("code signing");
+        let err = pki.validate(&leaf, KeyPurpose::OcspSigning).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: extended key usage of certificate 'CN=purpose.example' doesn't allow OCSP signing"
+        );
+    }
+
+    #[test]
+    fn leaf_key_usage() {
+        let pki = Pki::new(None);
+
+        let mut key_encipherment = KeyUsage::default();
+        key_encipherment.set_key_encipherment(true);
+        let leaf = pki.leaf(Some(key_encipherment), None);
+
+        pki.validate(&leaf, KeyPurpose::TlsServer).expect("TLS server");
+        pki.validate(&leaf, KeyPurpose::Other(oids::kp_ipsec_user()))
+            .expect("custom purpose");
+        let err = pki.validate(&leaf, KeyPurpose::CodeSigning).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: key usage of certificate 'CN=purpose.example' doesn't allow code signing (one of \
+             digitalSignature is required)"
+        );
+        let err = pki.validate(&leaf, KeyPurpose::TlsClient).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: key usage of certificate 'CN=purpose.example' doesn't allow TLS client authentication \
+             (one of digitalSignature, keyAgreement is required)"
+        );
+    }
+
+    #[test]
+    fn extended_key_usage_chaining() {
+        let pki = Pki::new(Some(ExtendedKeyUsage::new(vec![oids::kp_server_auth()])));
+        let leaf = pki.leaf(digital_signature(), None);
+
+        pki.validate(&leaf, KeyPurpose::TlsServer).expect("TLS server");
+        let err = pki.validate(&leaf, KeyPurpose::TlsClient).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: extended key usage of certificate 'CN=Purpose Authority' doesn't allow TLS client \
+             authentication"
+        );
+
+        let pki = Pki::new(Some(ExtendedKeyUsage::new(vec![oids::kp_any_extended_key_usage()])));
+        let leaf = pki.leaf(
+            digital_signature(),
+            Some(ExtendedKeyUsage::new(vec![oids::kp_code_signing()])),
+        );
+        pki.validate(&leaf, KeyPurpose::CodeSigning).expect("code signing");
+    }
+
+    #[test]
+    fn verify_chain_with_purpose() {
+        let pki = Pki::new(None);
+        let leaf = pki.leaf(
+            digital_signature(),
+            Some(ExtendedKeyUsage::new(vec![oids::kp_server_auth()])),
+        );
+        let chain = [&pki.intermediate, &pki.root];
+
+        let settings = ChainValidationSettings::new().purpose(KeyPurpose::TlsServer);
+        leaf.verify_chain_with_settings(chain.iter().copied(), &date(2070), &settings)
+            .expect("TLS server");
+
+        let settings = ChainValidationSettings::new().purpose(KeyPurpose::CodeSigning);
+        let err = leaf
+            .verify_chain_with_settings(chain.iter().copied(), &date(2070), &settings)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: extended key usage of certificate 'CN=purpose.example' doesn't allow code signing"
+        );
+    }
+}
@@ -0,0 +1,677 @@
+//! Revocation checking of a certification path using CRLs or OCSP.
+
+use crate::{
+    algorithm_identifier::HashAlgorithm,
+    x509::{
+        certificate::{CaChainError, Cert, CertError},
+        crl::{Crl, CrlError},
+        date::UTCDate,
+        ocsp::{CertId, CertStatus, OcspError, OcspRequest, OcspResponse},
+    },
+};
+use rand_core::RngCore;
+use rsa::BigUint;
+use snafu::Snafu;
+use std::fmt;
+
+#[derive(Debug, Snafu)]
+pub enum RevocationError {
+    /// CRL error
+    #[snafu(display("CRL error: {}", source))]
+    InvalidCrl { source: CrlError },
+
+    /// OCSP error
+    #[snafu(display("OCSP error: {}", source))]
+    InvalidOcspResponse { source: OcspError },
+
+    /// couldn't fetch revocation information
+    #[snafu(display("couldn't fetch revocation information: {}", reason))]
+    Fetch { reason: String },
+
+    /// revocation information is not yet valid
+    #[snafu(display(
+        "revocation information is not yet valid (this update: {}, now: {})",
+        this_update,
+        now
+    ))]
+    RevocationInfoNotYetValid { this_update: UTCDate, now: UTCDate },
+
+    /// revocation information expired
+    #[snafu(display("revocation information expired (next update: {}, now: {})", next_update, now))]
+    RevocationInfoExpired { next_update: UTCDate, now: UTCDate },
+
+    /// OCSP response nonce doesn't match the request
+    #[snafu(display("OCSP response nonce doesn't match the request"))]
+    NonceMismatch,
+
+    /// OCSP response doesn't contain the status of the certificate
+    #[snafu(display("OCSP response doesn't contain the status of the certificate"))]
+    NoMatchingResponse,
+}
+
+fn check_freshness(this_update: &UTCDate, next_update: Option<&UTCDate>, now: &UTCDate) -> Result<(), RevocationError> {
+    if this_update > now {
+        return Err(RevocationError::RevocationInfoNotYetValid {
+            this_update: this_update.clone(),
+            now: now.clone(),
+        });
+    }
+
+    match next_update {
+        Some(next_update) if next_update < now => Err(RevocationError::RevocationInfoExpired {
+            next_update: next_update.clone(),
+            now: now.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// CRL number (absent or negative numbers rank first) and this update date
+type CrlRank = (Option<BigUint>, UTCDate);
+
+fn crl_rank(crl: &Crl) -> CrlRank {
+    let crl_number = crl
+        .crl_number()
+        .ok()
+        .filter(|crl_number| !crl_number.is_negative())
+        .map(|crl_number| BigUint::from_bytes_be(crl_number.as_bytes_be()));
+    (crl_number, crl.this_update())
+}
+
+/// Source of revocation information consulted during chain validation.
+pub trait RevocationChecker {
+    /// Returns the revocation status of `cert` issued by `issuer`.
+    ///
+    /// `CertStatus::Unknown` should be returned when no revocation information is available
+    /// for the certificate.
+    fn check(&self, cert: &Cert, issuer: &Cert, now: &UTCDate) -> Result<CertStatus, RevocationError>;
+}
+
+/// Checks revocation status against a set of in-memory CRLs.
+///
+/// Only the CRLs issued by the issuer of the certificate are considered. Among them, the most
+/// recent one which is correctly signed is used: highest CRL number first, then latest
+/// `this_update`. CRLs with unsupported critical extensions (e.g. delta CRLs) are ignored.
+///
+/// The selected CRL must be current: an outdated CRL is never replaced by an older one since
+/// neither of them lists the certificates revoked in the meantime.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct CrlRevocationChecker {
+    crls: Vec<Crl>,
+}
+
+impl CrlRevocationChecker {
+    pub fn new<I: IntoIterator<Item = Crl>>(crls: I) -> Self {
+        Self {
+            crls: crls.into_iter().collect(),
+        }
+    }
+
+    pub fn add_crl(&mut self, crl: Crl) {
+        self.crls.push(crl);
+    }
+
+    pub fn crls(&self) -> &[Crl] {
+        &self.crls
+    }
+}
+
+impl RevocationChecker for CrlRevocationChecker {
+    fn check(&self, cert: &Cert, issuer: &Cert, now: &UTCDate) -> Result<CertStatus, RevocationError> {
+        let issuer_name = cert.issuer_name();
+
+        let mut selected: Option<(&Crl, CrlRank)> = None;
+        let mut first_error = None;
+
+        for crl in self.crls.iter().filter(|crl| crl.issuer_name() == issuer_name) {
+            // CRLs issued after `now` are irrelevant (e.g. when validating at a past date)
+            let result = crl
+                .verify(issuer)
+                .map_err(|source| RevocationError::InvalidCrl { source })
+                .and_then(|()| check_freshness(&crl.this_update(), None, now));
+
+            match result {
+                Ok(()) => {
+                    let rank = crl_rank(crl);
+                    if selected.as_ref().map(|(_, s)| *s < rank).unwrap_or(true) {
+                        selected = Some((crl, rank));
+                    }
+                }
+                Err(e) => {
+                    if first_error.is_none() {
+                        first_error = Some(e);
+                    }
+                }
+            }
+        }
+
+        match (selected, first_error) {
+            (Some((crl, _)), _) => {
+                check_freshness(&crl.this_update(), crl.next_update().as_ref(), now)?;
+                Ok(match crl.find_revoked(cert.serial_number()) {
+                    Some(revoked) => CertStatus::Revoked {
+                        revocation_time: revoked.revocation_date(),
+                        reason: revoked.reason(),
+                    },
+                    None => CertStatus::Good,
+                })
+            }
+            (None, Some(e)) => Err(e),
+            (None, None) => Ok(CertStatus::Unknown),
+        }
+    }
+}
+
+/// Transport used to query an OCSP responder.
+pub trait OcspFetcher {
+    /// Sends `request` to the OCSP responder in charge of `cert` and returns its response.
+    fn fetch(&self, cert: &Cert, request: &OcspRequest) -> Result<OcspResponse, RevocationError>;
+}
+
+/// Checks revocation status by querying an OCSP responder through an `OcspFetcher`.
+///
+/// The response must be signed by the issuer (or by a responder it delegated) and be current.
+/// A random nonce is sent with each request unless disabled; responders which don't echo it
+/// are tolerated, but a different nonce is rejected.
+#[derive(Clone, Debug)]
+pub struct OcspRevocationChecker<F> {
+    fetcher: F,
+    hash_algorithm: HashAlgorithm,
+    use_nonce: bool,
+}
+
+impl<F: OcspFetcher> OcspRevocationChecker<F> {
+    pub fn new(fetcher: F) -> Self {
+        Self {
+            fetcher,
+            hash_algorithm: HashAlgorithm::Sha1,
+            use_nonce: true,
+        }
+    }
+
+    /// Hash algorithm used to identify certificates in requests (default is SHA-1).
+    pub fn hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    pub fn use_nonce(mut self, use_nonce: bool) -> Self {
+        self.use_nonce = use_nonce;
+        self
+    }
+
+    pub fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+}
+
+impl<F: OcspFetcher> RevocationChecker for OcspRevocationChecker<F> {
+    fn check(&self, cert: &Cert, issuer: &Cert, now: &UTCDate) -> Result<CertStatus, RevocationError> {
+        let cert_id = CertId::new(cert, issuer, self.hash_algorithm)
+            .map_err(|source| RevocationError::InvalidOcspResponse { source })?;
+
+        let mut request = OcspRequest::new(vec![cert_id.clone()]);
+        if self.use_nonce {
+            let mut nonce = [0u8; 16];
+            rand_core::OsRng.fill_bytes(&mut nonce);
+            request = request.with_nonce(nonce.to_vec());
+        }
+
+        let basic_response = self
+            .fetcher
+            .fetch(cert, &request)?
+            .basic_response()
+            .and_then(|basic_response| basic_response.verify(issuer).map(|()| basic_response))
+            .map_err(|source| RevocationError::InvalidOcspResponse { source })?;
+
+        if let (Some(expected), Some(actual)) = (request.nonce(), basic_response.nonce()) {
+            if expected != actual {
+                return Err(RevocationError::NonceMismatch);
+            }
+        }
+
+        let single_response = basic_response
+            .find_response(&cert_id)
+            .ok_or(RevocationError::NoMatchingResponse)?;
+
+        check_freshness(single_response.this_update(), single_response.next_update(), now)?;
+
+        Ok(single_response.cert_status().clone())
+    }
+}
+
+/// Behavior when the revocation status of a certificate can't be determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevocationPolicy {
+    /// Unknown status and revocation checking errors invalidate the path.
+    HardFail,
+    /// Only certificates known to be revoked invalidate the path.
+    SoftFail,
+}
+
+/// Revocation checking inputs for certification path validation.
+#[derive(Clone)]
+pub struct RevocationSettings<'a> {
+    checker: &'a dyn RevocationChecker,
+    policy: RevocationPolicy,
+}
+
+impl fmt::Debug for RevocationSettings<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RevocationSettings")
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<'a> RevocationSettings<'a> {
+    /// Hard-fail policy is used by default.
+    pub fn new(checker: &'a dyn RevocationChecker) -> Self {
+        Self {
+            checker,
+            policy: RevocationPolicy::HardFail,
+        }
+    }
+
+    pub fn policy(mut self, policy: RevocationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Checks the revocation status of each certificate of a certification path ordered from
+    /// the leaf to the trust anchor (as returned by `ChainBuilder::build`).
+    /// The trust anchor itself is not checked.
+    pub fn validate(&self, path: &[&Cert], now: &UTCDate) -> Result<(), CertError> {
+        for pair in path.windows(2) {
+            let (cert, issuer) = (pair[0], pair[1]);
+
+            let err = match self.checker.check(cert, issuer, now) {
+                Ok(CertStatus::Good) => continue,
+                Ok(CertStatus::Revoked {
+                    revocation_time,
+                    reason,
+                }) => {
+                    return Err(CertError::InvalidChain {
+                        source: CaChainError::CertificateRevoked {
+                            cert_id: cert.subject_name().to_string(),
+                            revocation_time,
+                            reason,
+                        },
+                    });
+                }
+                Ok(CertStatus::Unknown) => CaChainError::RevocationStatusUnknown {
+                    cert_id: cert.subject_name().to_string(),
+                },
+                Err(source) => CaChainError::RevocationCheckFailed {
+                    cert_id: cert.subject_name().to_string(),
+                    source: Box::new(source),
+                },
+            };
+
+            if self.policy == RevocationPolicy::HardFail {
+                return Err(CertError::InvalidChain { source: err });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        key::PrivateKey,
+        pem::parse_pem,
+        x509::{
+            chain::{ChainBuilder, ChainValidationSettings},
+            crl::CrlBuilder,
+            extension::CRLReason,
+            ocsp::{BasicOcspResponseBuilder, OcspResponseStatus, SingleResponse},
+        },
+    };
+
+    fn parse_cert(pem_str: &str) -> Cert {
+        Cert::from_pem(&parse_pem(pem_str).unwrap()).unwrap()
+    }
+
+    fn parse_key(pem_str: &str) -> PrivateKey {
+        PrivateKey::from_pem(&parse_pem(pem_str).unwrap()).unwrap()
+    }
+
+    fn date(year: u16, month: u8, day: u8) -> UTCDate {
+        UTCDate::ymd(year, month, day).unwrap()
+    }
+
+    fn check_path(
+        leaf: &Cert,
+        ca: &Cert,
+        revocation_settings: RevocationSettings,
+        now: UTCDate,
+    ) -> Result<(), CertError> {
+        ChainBuilder::new()
+            .leaf(leaf)
+            .trust_anchors(vec![ca])
+            .revocation_settings(revocation_settings)
+            .now(now)
+            .build()
+            .map(|_| ())
+    }
+
+    #[test]
+    fn crl_revocation_checker() {
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let ca_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let revoked_leaf = parse_cert(crate::test_files::CRL_REVOKED_LEAF);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+        let now = date(2027, 1, 1);
+
+        let checker =
+            CrlRevocationChecker::new(vec![Crl::from_pem(&parse_pem(crate::test_files::CRL).unwrap()).unwrap()]);
+        check_path(&valid_leaf, &ca, RevocationSettings::new(&checker), now.clone()).expect("valid leaf");
+        let err = check_path(&revoked_leaf, &ca, RevocationSettings::new(&checker), now.clone())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: certificate 'CN=leaf1' was revoked on 2026-10-18 12:58:07"
+        );
+        assert_eq!(
+            checker.check(&revoked_leaf, &ca, &now).unwrap(),
+            CertStatus::Revoked {
+                revocation_time: UTCDate::new(2026, 10, 18, 12, 58, 7).unwrap(),
+                reason: Some(CRLReason::KeyCompromise),
+            }
+        );
+
+        // the most recent CRL is used
+        let mut checker = checker;
+        checker.add_crl(
+            CrlBuilder::new()
+                .this_update(date(2026, 12, 1))
+                .next_update(date(2027, 2, 1))
+                .issuer_cert(&ca, &ca_key)
+                .crl_number(43)
+                .revoke_cert(&valid_leaf, date(2026, 11, 30), Some(CRLReason::Superseded))
+                .build()
+                .unwrap(),
+        );
+        assert_eq!(checker.check(&revoked_leaf, &ca, &now).unwrap(), CertStatus::Good);
+        let err = check_path(&valid_leaf, &ca, RevocationSettings::new(&checker), now.clone())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: certificate 'CN=leaf2' was revoked on 2026-11-30 00:00:00"
+        );
+
+        // ranked by CRL number rather than by this update date
+        checker.add_crl(
+            CrlBuilder::new()
+                .this_update(date(2026, 12, 15))
+                .next_update(date(2027, 2, 1))
+                .issuer_cert(&ca, &ca_key)
+                .crl_number(41)
+                .build()
+                .unwrap(),
+        );
+        assert!(matches!(
+            checker.check(&valid_leaf, &ca, &now).unwrap(),
+            CertStatus::Revoked { .. }
+        ));
+
+        // an outdated CRL is never superseded by an older one
+        let later = date(2027, 3, 1);
+        let err = checker.check(&valid_leaf, &ca, &later).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "revocation information expired (next update: 2027-02-01 00:00:00, now: 2027-03-01 00:00:00)"
+        );
+
+        // CRLs issued after the validation date are ignored
+        let earlier = date(2026, 11, 1);
+        assert!(matches!(
+            checker.check(&revoked_leaf, &ca, &earlier).unwrap(),
+            CertStatus::Revoked { .. }
+        ));
+        assert_eq!(checker.check(&valid_leaf, &ca, &earlier).unwrap(), CertStatus::Good);
+    }
+
+    #[test]
+    fn verify_chain_revocation() {
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let revoked_leaf = parse_cert(crate::test_files::CRL_REVOKED_LEAF);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+        let now = date(2027, 1, 1);
+
+        let checker =
+            CrlRevocationChecker::new(vec![Crl::from_pem(&parse_pem(crate::test_files::CRL).unwrap()).unwrap()]);
+        let settings = ChainValidationSettings::new().revocation_settings(RevocationSettings::new(&checker));
+
+        revoked_leaf
+            .verify_chain([&ca].iter().copied(), &now)
+            .expect("revocation is not checked by default");
+        valid_leaf
+            .verify_chain_with_settings([&ca].iter().copied(), &now, &settings)
+            .expect("valid leaf");
+        let err = revoked_leaf
+            .verify_chain_with_settings([&ca].iter().copied(), &now, &settings)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: certificate 'CN=leaf1' was revoked on 2026-10-18 12:58:07"
+        );
+    }
+
+    #[test]
+    fn crl_revocation_policy() {
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let ca_key = parse_key(crate::test_files::EC_P256_PK_1);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+        let now = date(2027, 1, 1);
+
+        // no CRL for the issuer
+        let checker = CrlRevocationChecker::default();
+        let err = check_path(&valid_leaf, &ca, RevocationSettings::new(&checker), now.clone())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: revocation status of certificate 'CN=leaf2' is unknown"
+        );
+        check_path(
+            &valid_leaf,
+            &ca,
+            RevocationSettings::new(&checker).policy(RevocationPolicy::SoftFail),
+            now.clone(),
+        )
+        .expect("soft-fail");
+
+        // expired CRL
+        let checker = CrlRevocationChecker::new(vec![CrlBuilder::new()
+            .this_update(date(2026, 11, 1))
+            .next_update(date(2026, 12, 1))
+            .issuer_cert(&ca, &ca_key)
+            .crl_number(1)
+            .build()
+            .unwrap()]);
+        let err = check_path(&valid_leaf, &ca, RevocationSettings::new(&checker), now.clone())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: couldn't check revocation status of certificate 'CN=leaf2': revocation information \
+             expired (next update: 2026-12-01 00:00:00, now: 2027-01-01 00:00:00)"
+        );
+        check_path(
+            &valid_leaf,
+            &ca,
+            RevocationSettings::new(&checker).policy(RevocationPolicy::SoftFail),
+            now.clone(),
+        )
+        .expect("soft-fail");
+
+        // CRL signed with another key
+        let other_key = parse_key(crate::test_files::EC_P256_PK_2);
+        let checker = CrlRevocationChecker::new(vec![CrlBuilder::new()
+            .this_update(date(2026, 11, 1))
+            .issuer_cert(&ca, &other_key)
+            .crl_number(1)
+            .build()
+            .unwrap()]);
+        let err = check_path(&valid_leaf, &ca, RevocationSettings::new(&checker), now)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: couldn't check revocation status of certificate 'CN=leaf2': CRL error: signature \
+             error: invalid signature"
+        );
+    }
+
+    struct MockResponder {
+        issuer: Cert,
+        key: PrivateKey,
+        revoked: Vec<Cert>,
+        error_status: Option<OcspResponseStatus>,
+        nonce: Option<Vec<u8>>,
+    }
+
+    impl MockResponder {
+        fn new(revoked: Vec<Cert>) -> Self {
+            Self {
+                issuer: parse_cert(crate::test_files::CRL_CA),
+                key: parse_key(crate::test_files::EC_P256_PK_1),
+                revoked,
+                error_status: None,
+                nonce: None,
+            }
+        }
+    }
+
+    impl OcspFetcher for MockResponder {
+        fn fetch(&self, _: &Cert, request: &OcspRequest) -> Result<OcspResponse, RevocationError> {
+            if let Some(status) = self.error_status {
+                return Ok(OcspResponse::error(status));
+            }
+
+            let request = OcspRequest::from_der(&request.to_der().unwrap())
+                .map_err(|e| RevocationError::Fetch { reason: e.to_string() })?;
+
+            let builder = BasicOcspResponseBuilder::new();
+            builder.produced_at(date(2027, 1, 1)).responder(&self.issuer, &self.key);
+            for cert_id in request.cert_ids() {
+                let revoked = self
+                    .revoked
+                    .iter()
+                    .find(|cert| cert_id.matches(cert, &self.issuer).unwrap());
+                let cert_status = match revoked {
+                    Some(_) => CertStatus::Revoked {
+                        revocation_time: date(2026, 12, 24),
+                        reason: Some(CRLReason::CessationOfOperation),
+                    },
+                    None => CertStatus::Good,
+                };
+                builder.response(SingleResponse::new(
+                    cert_id.clone(),
+                    cert_status,
+                    date(2027, 1, 1),
+                    Some(date(2027, 1, 8)),
+                ));
+            }
+            if let Some(nonce) = self.nonce.as_deref().or_else(|| request.nonce()) {
+                builder.nonce(nonce);
+            }
+
+            let basic_response = builder.build().unwrap();
+            Ok(OcspResponse::successful(&basic_response).unwrap())
+        }
+    }
+
+    #[test]
+    fn ocsp_revocation_checker() {
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let revoked_leaf = parse_cert(crate::test_files::CRL_REVOKED_LEAF);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+        let now = date(2027, 1, 2);
+
+        let checker = OcspRevocationChecker::new(MockResponder::new(vec![revoked_leaf.clone()]));
+        check_path(&valid_leaf, &ca, RevocationSettings::new(&checker), now.clone()).expect("valid leaf");
+        let err = check_path(&revoked_leaf, &ca, RevocationSettings::new(&checker), now.clone())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: certificate 'CN=leaf1' was revoked on 2026-12-24 00:00:00"
+        );
+
+        let checker = checker.hash_algorithm(HashAlgorithm::Sha256).use_nonce(false);
+        assert_eq!(
+            checker.check(&revoked_leaf, &ca, &now).unwrap(),
+            CertStatus::Revoked {
+                revocation_time: date(2026, 12, 24),
+                reason: Some(CRLReason::CessationOfOperation),
+            }
+        );
+
+        // response is outdated
+        let err = checker.check(&valid_leaf, &ca, &date(2027, 2, 1)).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "revocation information expired (next update: 2027-01-08 00:00:00, now: 2027-02-01 00:00:00)"
+        );
+    }
+
+    #[test]
+    fn ocsp_revocation_policy() {
+        let ca = parse_cert(crate::test_files::CRL_CA);
+        let valid_leaf = parse_cert(crate::test_files::CRL_VALID_LEAF);
+        let now = date(2027, 1, 2);
+
+        let mut responder = MockResponder::new(Vec::new());
+        responder.error_status = Some(OcspResponseStatus::TryLater);
+        let checker = OcspRevocationChecker::new(responder);
+        let err = check_path(&valid_leaf, &ca, RevocationSettings::new(&checker), now.clone())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: couldn't check revocation status of certificate 'CN=leaf2': OCSP error: OCSP response \
+             is not successful: TryLater"
+        );
+        check_path(
+            &valid_leaf,
+            &ca,
+            RevocationSettings::new(&checker).policy(RevocationPolicy::SoftFail),
+            now.clone(),
+        )
+        .expect("soft-fail");
+
+        // replayed response
+        let mut responder = MockResponder::new(Vec::new());
+        responder.nonce = Some(vec![0x42; 16]);
+        let checker = OcspRevocationChecker::new(responder);
+        let err = check_path(&valid_leaf, &ca, RevocationSettings::new(&checker), now.clone())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: couldn't check revocation status of certificate 'CN=leaf2': OCSP response nonce \
+             doesn't match the request"
+        );
+
+        // response signed by a responder not authorized by the issuer
+        let mut responder = MockResponder::new(Vec::new());
+        responder.key = parse_key(crate::test_files::EC_P256_PK_2);
+        let checker = OcspRevocationChecker::new(responder);
+        let err = check_path(&valid_leaf, &ca, RevocationSettings::new(&checker), now)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: couldn't check revocation status of certificate 'CN=leaf2': OCSP error: signature \
+             error: invalid signature"
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use crate::{
+    pem::{parse_pem, parse_pem_iter, Pem, PemError, PemReader},
+    x509::certificate::{Cert, CertError},
+};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufReader},
+    path::Path,
+};
+
+#[derive(Debug, Snafu)]
+pub enum TrustStoreError {
+    /// couldn't read file or directory
+    #[snafu(display("couldn't read {}: {}", path, source))]
+    Io { path: String, source: io::Error },
+
+    /// PEM error
+    #[snafu(display("PEM error: {}", source))]
+    InvalidPem { source: PemError },
+
+    /// certificate error
+    #[snafu(display("certificate error: {}", source))]
+    InvalidCertificate { source: CertError },
+
+    /// no system trust store found
+    #[snafu(display("couldn't find the system trust store"))]
+    SystemTrustStoreNotFound,
+}
+
+const CERT_PEM_LABEL: &str = "CERTIFICATE";
+
+/// Environment variable overriding the system CA bundle file (same as OpenSSL)
+const SSL_CERT_FILE_ENV: &str = "SSL_CERT_FILE";
+
+/// Environment variable overriding the system hashed certificate directory (same as OpenSSL)
+const SSL_CERT_DIR_ENV: &str = "SSL_CERT_DIR";
+
+/// Well-known locations of the system CA bundle
+const SYSTEM_BUNDLE_FILES: &[&str] = &[
+    "/etc/ssl/certs/ca-certificates.crt",     // Debian, Ubuntu, Arch, Gentoo
+    "/etc/pki/tls/certs/ca-bundle.crt",       // Fedora, RHEL
+    "/etc/ssl/ca-bundle.pem",                 // OpenSUSE
+    "/etc/pki/tls/cacert.pem",                // OpenELEC
+    "/etc/ssl/cert.pem",                      // Alpine, macOS, BSDs
+    "/usr/local/share/certs/ca-root-nss.crt", // FreeBSD
+];
+
+/// Well-known locations of the system hashed certificate directory
+const SYSTEM_CERT_DIRS: &[&str] = &["/etc/ssl/certs", "/etc/pki/tls/certs"];
+
+/// Set of trust anchors used as root source for chain validation.
+///
+/// Trust anchors are indexed by subject name and subject key identifier.
+#[derive(Default, Clone, Debug)]
+pub struct TrustStore {
+    anchors: Vec<Cert>,
+    by_subject: HashMap<String, Vec<usize>>,
+    by_ski: HashMap<Vec<u8>, Vec<usize>>,
+}
+
+impl TrustStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_certs<I: IntoIterator<Item = Cert>>(certs: I) -> Self {
+        let mut store = Self::new();
+        for cert in certs {
+            store.add(cert);
+        }
+        store
+    }
+
+    /// Loads all the certificates of a PEM bundle. Other PEM structures are ignored.
+    pub fn from_pem_bundle<T: ?Sized + AsRef<[u8]>>(bundle: &T) -> Result<Self, TrustStoreError> {
+        let mut store = Self::new();
+        for pem in parse_pem_iter(bundle) {
+            let (pem, _) = pem.context(InvalidPem)?;
+            store.add_pem(&pem, false)?;
+        }
+        Ok(store)
+    }
+
+    /// Loads all the certificates of a PEM bundle file (e.g. `/etc/ssl/certs/ca-certificates.crt`).
+    pub fn from_pem_file<P: AsRef<Path>>(path: P) -> Result<Self, TrustStoreError> {
+        let mut store = Self::new();
+        store.load_pem_file(path.as_ref(), false)?;
+        Ok(store)
+    }
+
+    /// Loads an OpenSSL-style hashed certificate directory (as created by `openssl rehash`).
+    ///
+    /// Only files named `<subject name hash>.<n>` are considered.
+    pub fn from_hashed_dir<P: AsRef<Path>>(path: P) -> Result<Self, TrustStoreError> {
+        let mut store = Self::new();
+        store.load_hashed_dir(path.as_ref(), false)?;
+        Ok(store)
+    }
+
+    /// Loads the system trust store.
+    ///
+    /// `SSL_CERT_FILE` and `SSL_CERT_DIR` environment variables are honored, then well-known
+    /// CA bundle files and hashed directories are looked up.
+    /// System stores may contain certificates picky can't parse: those are skipped.
+    pub fn from_system() -> Result<Self, TrustStoreError> {
+        let mut store = Self::new();
+
+        if let Some(file) = std::env::var_os(SSL_CERT_FILE_ENV) {
+            store.load_pem_file(Path::new(&file), true)?;
+        } else if let Some(dir) = std::env::var_os(SSL_CERT_DIR_ENV) {
+            store.load_hashed_dir(Path::new(&dir), true)?;
+        } else if let Some(file) = SYSTEM_BUNDLE_FILES.iter().map(Path::new).find(|path| path.is_file()) {
+            store.load_pem_file(file, true)?;
+        } else if let Some(dir) = SYSTEM_CERT_DIRS.iter().map(Path::new).find(|path| path.is_dir()) {
+            store.load_hashed_dir(dir, true)?;
+        } else {
+            return Err(TrustStoreError::SystemTrustStoreNotFound);
+        }
+
+        Ok(store)
+    }
+
+    /// Adds a trust anchor. Duplicated certificates are ignored.
+    pub fn add(&mut self, cert: Cert) {
+        if self.contains(&cert) {
+            return;
+        }
+
+        let index = self.anchors.len();
+        self.by_subject
+            .entry(cert.subject_name().to_string())
+            .or_default()
+            .push(index);
+        if let Ok(ski) = cert.subject_key_identifier() {
+            self.by_ski.entry(ski.to_vec()).or_default().push(index);
+        }
+        self.anchors.push(cert);
+    }
+
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Cert> {
+        self.anchors.iter()
+    }
+
+    pub fn contains(&self, cert: &Cert) -> bool {
+        self.find_by_subject(&cert.subject_name().to_string())
+            .any(|anchor| anchor == cert)
+    }
+
+    /// Finds trust anchors by subject name (as formatted by `DirectoryName`'s `Display` implementation).
+    pub fn find_by_subject<'a>(&'a self, subject_name: &str) -> impl Iterator<Item = &'a Cert> + 'a {
+        self.lookup(self.by_subject.get(subject_name))
+    }
+
+    /// Finds trust anchors by subject key identifier.
+    pub fn find_by_ski<'a>(&'a self, ski: &[u8]) -> impl Iterator<Item = &'a Cert> + 'a {
+        self.lookup(self.by_ski.get(ski))
+    }
+
+    /// Finds the trust anchors that could have issued `cert` (see `Cert::is_parent_of`).
+    ///
+    /// Anchors are looked up by subject key identifier first, then by subject name (anchors
+    /// may lack a subject key identifier extension).
+    pub fn find_issuers<'a>(&'a self, cert: &Cert) -> Vec<&'a Cert> {
+        let aki = cert
+            .authority_key_identifier()
+            .ok()
+            .and_then(|aki| aki.key_identifier());
+
+        if let Some(aki) = aki {
+            let issuers: Vec<&Cert> = self
+                .find_by_ski(aki)
+                .filter(|anchor| anchor.is_parent_of(cert).is_ok())
+                .collect();
+            if !issuers.is_empty() {
+                return issuers;
+            }
+        }
+
+        self.find_by_subject(&cert.issuer_name().to_string())
+            .filter(|anchor| anchor.is_parent_of(cert).is_ok())
+            .collect()
+    }
+
+    fn lookup<'a>(&'a self, indexes: Option<&'a Vec<usize>>) -> impl Iterator<Item = &'a Cert> + 'a {
+        indexes.into_iter().flatten().map(move |&index| &self.anchors[index])
+    }
+
+    fn add_pem(&mut self, pem: &Pem, skip_invalid: bool) -> Result<(), TrustStoreError> {
+        if pem.label() == CERT_PEM_LABEL {
+            match Cert::from_pem(pem) {
+                Ok(cert) => self.add(cert),
+                Err(_) if skip_invalid => {}
+                Err(e) => return Err(e).context(InvalidCertificate),
+            }
+        }
+        Ok(())
+    }
+
+    fn load_pem_file(&mut self, path: &Path, skip_invalid: bool) -> Result<(), TrustStoreError> {
+        if skip_invalid {
+            // PEM iterators stop at the first malformed structure: each one is parsed on its own instead
+            let bundle = fs::read(path).with_context(|| Io {
+                path: path.display().to_string(),
+            })?;
+            for block in pem_blocks(&bundle) {
+                if let Ok(pem) = parse_pem(block) {
+                    self.add_pem(&pem, true)?;
+                }
+            }
+            return Ok(());
+        }
+
+        let file = File::open(path).with_context(|| Io {
+            path: path.display().to_string(),
+        })?;
+
+        for pem in PemReader::new(BufReader::new(file)) {
+            let (pem, _) = pem.context(InvalidPem)?;
+            self.add_pem(&pem, false)?;
+        }
+
+        Ok(())
+    }
+
+    fn load_hashed_dir(&mut self, path: &Path, skip_invalid: bool) -> Result<(), TrustStoreError> {
+        let entries = fs::read_dir(path).with_context(|| Io {
+            path: path.display().to_string(),
+        })?;
+
+        let mut file_paths = Vec::new();
+        for entry in entries {
+            let entry = entry.with_context(|| Io {
+                path: path.display().to_string(),
+            })?;
+            if entry
+                .file_name()
+                .to_str()
+                .map(is_hashed_cert_file_name)
+                .unwrap_or(false)
+            {
+                file_paths.push(entry.path());
+            }
+        }
+        file_paths.sort();
+
+        for file_path in file_paths {
+            self.load_pem_file(&file_path, skip_invalid)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits a bundle before each PEM header.
+fn pem_blocks(bundle: &[u8]) -> Vec<&[u8]> {
+    const PEM_HEADER_START: &[u8] = b"-----BEGIN";
+
+    let starts: Vec<usize> = bundle
+        .windows(PEM_HEADER_START.len())
+        .enumerate()
+        .filter(|(_, window)| *window == PEM_HEADER_START)
+        .map(|(idx, _)| idx)
+        .collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, start)| &bundle[*start..starts.get(i + 1).copied().unwrap_or(bundle.len())])
+        .collect()
+}
+
+fn is_hashed_cert_file_name(file_name: &str) -> bool {
+    let mut parts = file_name.splitn(2, '.');
+    let hash = parts.next().unwrap_or("");
+    let suffix = parts.next().unwrap_or("");
+    hash.len() == 8
+        && hash.chars().all(|c| c.is_ascii_hexdigit())
+        && !suffix.is_empty()
+        && suffix.chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::x509::{
+        chain::{ChainBuilder, ChainValidationSettings},
+        date::UTCDate,
+    };
+
+    fn parse_cert(pem_str: &str) -> Cert {
+        Cert::from_pem(&pem_str.parse::<Pem>().expect("pem")).expect("cert")
+    }
+
+    #[test]
+    fn pem_bundle() {
+        let bundle = format!(
+            "Picky EC Root CA\n{}\nPicky Ed25519 Root CA\n{}\n{}",
+            crate::test_files::EC_ROOT_CA,
+            crate::test_files::ED25519_ROOT_CA,
+            crate::test_files::EC_ROOT_CA,
+        );
+
+        let store = TrustStore::from_pem_bundle(&bundle).expect("trust store");
+        assert_eq!(store.len(), 2);
+
+        let ec_root = parse_cert(crate::test_files::EC_ROOT_CA);
+        assert!(store.contains(&ec_root));
+        assert_eq!(
+            store.find_by_subject("CN=Picky EC Root CA").collect::<Vec<_>>(),
+            vec![&ec_root]
+        );
+        assert_eq!(
+            store
+                .find_by_ski(ec_root.subject_key_identifier().unwrap())
+                .collect::<Vec<_>>(),
+            vec![&ec_root]
+        );
+
+        let ec_intermediate = parse_cert(crate::test_files::EC_INTERMEDIATE_CA);
+        assert_eq!(store.find_issuers(&ec_intermediate), vec![&ec_root]);
+        assert!(!store.contains(&ec_intermediate));
+        assert!(store.find_issuers(&parse_cert(crate::test_files::EC_LEAF)).is_empty());
+    }
+
+    #[test]
+    fn hashed_dir() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../test_assets/trust_store");
+        let store = TrustStore::from_hashed_dir(dir).expect("trust store");
+        assert_eq!(store.len(), 2);
+        assert!(store.contains(&parse_cert(crate::test_files::EC_ROOT_CA)));
+        assert!(store.contains(&parse_cert(crate::test_files::ED25519_ROOT_CA)));
+
+        let err = TrustStore::from_hashed_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/does_not_exist")).unwrap_err();
+        assert!(err.to_string().starts_with("couldn't read "));
+    }
+
+    #[test]
+    fn verify_chain_with_trust_store() {
+        let leaf = parse_cert(crate::test_files::EC_LEAF);
+        let intermediate = parse_cert(crate::test_files::EC_INTERMEDIATE_CA);
+        let root = parse_cert(crate::test_files::EC_ROOT_CA);
+        let now = UTCDate::ymd(2030, 1, 1).unwrap();
+
+        let store = TrustStore::from_certs(vec![root.clone()]);
+        leaf.verify_chain_with_settings(
+            vec![&intermediate, &root].into_iter(),
+            &now,
+            &ChainValidationSettings::new().trust_store(&store),
+        )
+        .expect("trusted root");
+
+        // a trust anchor of the store ends the chain even when it isn't a self-signed root
+        let store = TrustStore::from_certs(vec![intermediate.clone()]);
+        leaf.verify_chain_with_settings(
+            vec![&intermediate].into_iter(),
+            &now,
+            &ChainValidationSettings::new().trust_store(&store),
+        )
+        .expect("trusted intermediate");
+
+        let store = TrustStore::from_certs(vec![parse_cert(crate::test_files::ED25519_ROOT_CA)]);
+        let err = leaf
+            .verify_chain_with_settings(
+                vec![&intermediate, &root].into_iter(),
+                &now,
+                &ChainValidationSettings::new().trust_store(&store),
+            )
+            .expect_err("untrusted root");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: root certificate 'CN=Picky EC Root CA' is not in the trust store"
+        );
+    }
+
+    #[test]
+    fn invalid_pem_in_bundle() {
+        let path = Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../test_assets/ca_bundle_with_invalid.pem"
+        ));
+
+        let mut store = TrustStore::new();
+        store.load_pem_file(path, false).expect_err("strict loading");
+
+        let mut store = TrustStore::new();
+        store.load_pem_file(path, true).expect("lenient loading");
+        assert_eq!(store.len(), 2);
+        assert!(store.contains(&parse_cert(crate::test_files::EC_ROOT_CA)));
+        assert!(store.contains(&parse_cert(crate::test_files::ED25519_ROOT_CA)));
+    }
+
+    #[test]
+    fn issuer_without_ski() {
+        let root = parse_cert(crate::test_files::EC_ROOT_CA_NO_SKI);
+        let intermediate = parse_cert(crate::test_files::EC_INTERMEDIATE_CA);
+        assert!(root.subject_key_identifier().is_err());
+        assert!(intermediate.authority_key_identifier().is_ok());
+
+        let store = TrustStore::from_certs(vec![root.clone()]);
+        assert_eq!(store.find_issuers(&intermediate), vec![&root]);
+
+        let leaf = parse_cert(crate::test_files::EC_LEAF);
+        let path = ChainBuilder::new()
+            .leaf(&leaf)
+            .intermediates(vec![&intermediate])
+            .trust_store(&store)
+            .now(UTCDate::ymd(2030, 1, 1).unwrap())
+            .build()
+            .expect("path");
+        assert_eq!(path.len(), 3);
+        assert_eq!(path[2], &root);
+    }
+
+    #[test]
+    fn hashed_cert_file_names() {
+        assert!(is_hashed_cert_file_name("bbb9211d.0"));
+        assert!(is_hashed_cert_file_name("2bce7365.12"));
+        assert!(!is_hashed_cert_file_name("2bce7365.r0"));
+        assert!(!is_hashed_cert_file_name("ca-certificates.crt"));
+        assert!(!is_hashed_cert_file_name("README"));
+    }
+
+    #[test]
+    fn chain_validation_with_trust_store() {
+        let store = TrustStore::from_certs(vec![
+            parse_cert(crate::test_files::EC_ROOT_CA),
+            parse_cert(crate::test_files::RSA_PSS_ROOT_CA),
+        ]);
+
+        let leaf = parse_cert(crate::test_files::EC_LEAF);
+        let intermediate = parse_cert(crate::test_files::EC_INTERMEDIATE_CA);
+        let now = UTCDate::ymd(2030, 1, 1).unwrap();
+
+        let path = ChainBuilder::new()
+            .leaf(&leaf)
+            .intermediates(vec![&intermediate])
+            .trust_store(&store)
+            .now(now.clone())
+            .build()
+            .expect("path");
+        assert_eq!(path.len(), 3);
+        assert_eq!(path[2], &parse_cert(crate::test_files::EC_ROOT_CA));
+
+        // a valid self-signed root that isn't in the trust store is rejected
+        let ed_leaf = parse_cert(crate::test_files::ED25519_LEAF);
+        let ed_intermediate = parse_cert(crate::test_files::ED448_INTERMEDIATE_CA);
+        let ed_root = parse_cert(crate::test_files::ED25519_ROOT_CA);
+        let err = ChainBuilder::new()
+            .leaf(&ed_leaf)
+            .intermediates(vec![&ed_intermediate, &ed_root])
+            .trust_store(&store)
+            .now(now)
+            .build()
+            .expect_err("untrusted root");
+        assert_eq!(
+            err.to_string(),
+            "CA chain error: no certification path found from 'CN=device.picky.example' to a trust anchor"
+        );
+    }
+}
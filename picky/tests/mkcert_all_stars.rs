@@ -2,8 +2,11 @@
 //! This test attempts to parse all root certificate provided by https://mkcert.org/
 //! Data fetched on the 2019/10 are in a file named `mkcert_all_root_ca_2019_10.txt`.
 
-use picky::{pem::parse_pem, x509::Cert};
-use std::{cmp::min, fs};
+use picky::{
+    pem::{parse_pem, parse_pem_iter, PemReader},
+    x509::Cert,
+};
+use std::{cmp::min, fs, io::BufReader};
 
 const ALL_STARS_FILE_PATH: &str = "../test_assets/mkcert_all_root_ca_2019_10.txt";
 
@@ -87,3 +90,31 @@ fn all_stars_parsing() {
     // we currently support 132 certificates out of the 136.
     assert!(number_decoded >= 132);
 }
+
+#[test]
+fn all_stars_pem_iter() {
+    let contents = fs::read_to_string(ALL_STARS_FILE_PATH).expect("couldn't read the mkcert file");
+
+    let mut expected = Vec::new();
+    let mut last_cursor: usize = 0;
+    while let Some(cursor) = next_cursor(&contents, last_cursor) {
+        let pem = parse_pem(&contents[cursor..]).expect("couldn't parse pem");
+        expected.push((cursor, pem));
+        last_cursor = cursor + 1;
+    }
+
+    let from_iter = parse_pem_iter(&contents)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("couldn't iterate over pem");
+    let from_reader = PemReader::new(BufReader::new(contents.as_bytes()))
+        .collect::<Result<Vec<_>, _>>()
+        .expect("couldn't read pem");
+
+    assert_eq!(from_iter.len(), expected.len());
+    assert_eq!(from_reader, from_iter);
+    for ((pem, range), (cursor, expected_pem)) in from_iter.iter().zip(expected.iter()) {
+        assert_eq!(range.start, *cursor);
+        assert!(contents[range.clone()].ends_with("-----"));
+        assert_eq!(pem, expected_pem);
+    }
+}